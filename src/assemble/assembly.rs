@@ -1,7 +1,15 @@
 use scirs2_sparse::bsr::BsrMatrix;
-use scirs2_sparse::SparseResult;
+use scirs2_sparse::csr::CsrMatrix;
+use scirs2_sparse::{SparseError, SparseResult};
 use itertools::Itertools;
 use num_integer::binomial;
+use ndarray::Array2;
+use rayon::prelude::*;
+use std::collections::HashMap;
+use std::io::{self, Write};
+
+use crate::elements::element_library::hypercube_elements::ShapeFunctionKind;
+use crate::elements::quadrature::quadrature_rules::QuadratureRuleDyn;
 
 /// Initialize a stiffness matrix with proper block structure
 ///
@@ -58,6 +66,64 @@ pub fn initialize_stiffness_matrix(
     BsrMatrix::from_blocks(data, indices, indptr, shape, block_size)
 }
 
+/// Initialize a symmetric stiffness matrix storing only the upper block triangle
+///
+/// Same as `initialize_stiffness_matrix`, but each block row i only holds block columns j >= i.
+/// Blocks below the diagonal are implied by symmetry, so downstream code must use a
+/// symmetric SpMV (each off-diagonal block contributes both K_ij * x_j and K_ij^T * x_i).
+///
+/// # Arguments
+/// * `num_node` - Number of nodes in the mesh
+/// * `elements` - List of element connectivity (each element is a list of node indices)
+/// * `dimension` - Block size (e.g., 2 for 2D problems, 3 for 3D)
+///
+/// # Returns
+/// BSR matrix with the upper triangular block structure
+pub fn initialize_stiffness_matrix_symmetric(
+    num_node: usize,
+    elements: &[Vec<usize>],
+    dimension: usize,
+) -> SparseResult<BsrMatrix<f64>> {
+    // Track only the block positions (indices)
+    let mut rows_of_blocks: Vec<Vec<usize>> = vec![Vec::new(); num_node];
+
+    // Process each element
+    for nodes in elements {
+        for &i in nodes {
+            let row = &mut rows_of_blocks[i];
+            for &j in nodes.iter().filter(|&&j| j >= i) {
+                // Insert block if not already present
+                if let Err(pos) = row.binary_search(&j) {
+                    row.insert(pos, j);
+                }
+            }
+        }
+    }
+
+    // Calculate total blocks and allocate data
+    let total_blocks: usize = rows_of_blocks.iter().map(Vec::len).sum();
+
+    // Convert to BSR format components
+    let mut indices: Vec<Vec<usize>> = Vec::with_capacity(total_blocks);
+    let mut indptr: Vec<usize> = Vec::with_capacity(num_node + 1);
+    indptr.push(0);
+
+    for row in rows_of_blocks {
+        indptr.push(indptr.last().unwrap() + row.len());
+        indices.extend(row.into_iter().map(|col| vec![col]));
+    }
+
+    // Create data array filled with ones matrices
+    let block_row: Vec<f64> = vec![1.0; dimension];
+    let block_values: Vec<Vec<f64>> = vec![block_row; dimension];
+    let data: Vec<Vec<Vec<f64>>> = vec![block_values; total_blocks];
+
+    let block_size: (usize, usize) = (dimension, dimension);
+    let shape: (usize, usize) = (num_node * dimension, num_node * dimension);
+
+    BsrMatrix::from_blocks(data, indices, indptr, shape, block_size)
+}
+
 /// Initialize a nonlinear stiffness matrix with proper block structure
 ///
 /// # Arguments
@@ -205,6 +271,760 @@ pub fn get_data_indices_from_block_positions_binary_search(
         .collect()
 }
 
+/// Add a dense element matrix into the blocks of an assembled stiffness matrix
+///
+/// # Arguments
+/// * `matrix` - BSR matrix whose block structure contains all node pairs of the element
+/// * `element_nodes` - Global node indices of the element
+/// * `local` - Element matrix of shape (n_nodes * dimension, n_nodes * dimension),
+///   where the DOF of component c at local node a is a * dimension + c
+/// * `dimension` - Block size (e.g., 2 for 2D problems, 3 for 3D)
+///
+/// # Returns
+/// Error if the local matrix has the wrong shape or a block (i,j) is missing from the structure.
+/// The matrix is left unchanged in that case.
+pub fn scatter_element_matrix(
+    matrix: &mut BsrMatrix<f64>,
+    element_nodes: &[usize],
+    local: &Array2<f64>,
+    dimension: usize,
+) -> SparseResult<()> {
+    let local_size: usize = element_nodes.len() * dimension;
+    if local.dim() != (local_size, local_size) {
+        return Err(SparseError::ValueError(format!(
+            "Element matrix has shape {:?}, expected ({}, {})", local.dim(), local_size, local_size
+        )));
+    }
+
+    let data_indices: Vec<usize> = element_data_indices(matrix, element_nodes)?;
+    scatter_element_matrix_mapped(matrix, &data_indices, local, dimension)
+}
+
+/// Resolve the data index of every block (i,j) of every element, once, for repeated assembly
+///
+/// Assembly loops that scatter the same elements many times (e.g. every Newton iteration) can use
+/// `scatter_element_matrix_mapped` with these indices instead of a binary search per block.
+///
+/// # Arguments
+/// * `matrix` - BSR matrix whose block structure contains all node pairs of the elements
+/// * `elements` - List of element connectivity (each element is a list of node indices)
+///
+/// # Returns
+/// For each element, the n_nodes * n_nodes data indices of its blocks (i,j), row-major over
+/// local nodes. Error if a block (i,j) is missing from the structure.
+pub fn precompute_assembly_map(matrix: &BsrMatrix<f64>, elements: &[Vec<usize>]) -> SparseResult<Vec<Vec<usize>>> {
+    elements
+        .iter()
+        .map(|element_nodes| element_data_indices(matrix, element_nodes))
+        .collect()
+}
+
+/// Add a dense element matrix into the blocks given by `precompute_assembly_map`
+///
+/// # Arguments
+/// * `matrix` - BSR matrix the map was computed for
+/// * `data_indices` - Data indices of the element blocks, an entry of `precompute_assembly_map`
+/// * `local` - Element matrix, laid out as in `scatter_element_matrix`
+/// * `dimension` - Block size (e.g., 2 for 2D problems, 3 for 3D)
+///
+/// # Returns
+/// Error if the local matrix does not match the number of blocks, or if a data index is out of
+/// range. The matrix is left unchanged in that case.
+pub fn scatter_element_matrix_mapped(
+    matrix: &mut BsrMatrix<f64>,
+    data_indices: &[usize],
+    local: &Array2<f64>,
+    dimension: usize,
+) -> SparseResult<()> {
+    let n_nodes: usize = local.nrows() / dimension.max(1);
+    let local_size: usize = n_nodes * dimension;
+    if local.dim() != (local_size, local_size) || data_indices.len() != n_nodes * n_nodes {
+        return Err(SparseError::ValueError(format!(
+            "Element matrix has shape {:?}, expected {} blocks of size {}", local.dim(), data_indices.len(), dimension
+        )));
+    }
+
+    let data = matrix.data_mut();
+    if let Some(&data_index) = data_indices.iter().find(|&&data_index| data_index >= data.len()) {
+        return Err(SparseError::ValueError(format!(
+            "Data index {} out of range for {} blocks", data_index, data.len()
+        )));
+    }
+
+    for (pair, &data_index) in data_indices.iter().enumerate() {
+        let (a, b) = (pair / n_nodes, pair % n_nodes);
+        let block = &mut data[data_index];
+        for (r, block_row) in block.iter_mut().enumerate() {
+            for (c, value) in block_row.iter_mut().enumerate() {
+                *value += local[[a * dimension + r, b * dimension + c]];
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Data indices of all (i,j) node pairs of an element, row-major over local nodes
+fn element_data_indices(matrix: &BsrMatrix<f64>, element_nodes: &[usize]) -> SparseResult<Vec<usize>> {
+    let block_positions: Vec<(usize, usize)> = element_nodes
+        .iter()
+        .flat_map(|&i| element_nodes.iter().map(move |&j| (i, j)))
+        .collect();
+
+    let data_indices: Vec<Option<usize>> = get_data_indices_from_block_positions_binary_search(
+        matrix.indptr(), matrix.indices(), &block_positions
+    );
+
+    data_indices
+        .into_iter()
+        .zip(block_positions.iter())
+        .map(|(index, &(i, j))| index.ok_or_else(|| {
+            SparseError::ValueError(format!("Block ({}, {}) is not part of the sparsity pattern", i, j))
+        }))
+        .collect()
+}
+
+/// Assemble a stiffness matrix in parallel from per-element dense matrices
+///
+/// Elements are split across the rayon thread pool. Each thread adds its element matrices into a
+/// private map from data index to block, so no locking is needed, and the maps are then reduced
+/// into the blocks of the matrix. Entries are summed in a different order than a serial loop,
+/// so results agree up to rounding.
+///
+/// # Arguments
+/// * `num_node` - Number of nodes in the mesh
+/// * `elements` - List of element connectivity (each element is a list of node indices)
+/// * `dimension` - Block size (e.g., 2 for 2D problems, 3 for 3D)
+/// * `element_matrix_fn` - Dense matrix of element `e`, laid out as in `scatter_element_matrix`
+///
+/// # Returns
+/// BSR matrix with the block structure of `initialize_stiffness_matrix`, or an error if an
+/// element matrix has the wrong shape
+pub fn parallel_assemble(
+    num_node: usize,
+    elements: &[Vec<usize>],
+    dimension: usize,
+    element_matrix_fn: impl Fn(usize) -> Array2<f64> + Sync,
+) -> SparseResult<BsrMatrix<f64>> {
+    let mut matrix: BsrMatrix<f64> = initialize_stiffness_matrix(num_node, elements, dimension)?;
+
+    let blocks: HashMap<usize, Vec<f64>> = {
+        let indptr: &Vec<usize> = matrix.indptr();
+        let indices: &Vec<Vec<usize>> = matrix.indices();
+
+        (0..elements.len())
+            .into_par_iter()
+            .try_fold(HashMap::new, |mut blocks: HashMap<usize, Vec<f64>>, e| {
+                let element_nodes: &[usize] = &elements[e];
+                let local: Array2<f64> = element_matrix_fn(e);
+                let local_size: usize = element_nodes.len() * dimension;
+                if local.dim() != (local_size, local_size) {
+                    return Err(SparseError::ValueError(format!(
+                        "Element {} matrix has shape {:?}, expected ({}, {})", e, local.dim(), local_size, local_size
+                    )));
+                }
+
+                let block_positions: Vec<(usize, usize)> = element_nodes
+                    .iter()
+                    .flat_map(|&i| element_nodes.iter().map(move |&j| (i, j)))
+                    .collect();
+                let data_indices = get_data_indices_from_block_positions_binary_search(indptr, indices, &block_positions);
+
+                let n_nodes: usize = element_nodes.len();
+                for (pair, data_index) in data_indices.into_iter().enumerate() {
+                    // The pattern is built from the same elements, so every block exists
+                    let data_index: usize = data_index.expect("element block missing from its own sparsity pattern");
+                    let (a, b) = (pair / n_nodes, pair % n_nodes);
+                    let block = blocks.entry(data_index).or_insert_with(|| vec![0.0; dimension * dimension]);
+                    for r in 0..dimension {
+                        for c in 0..dimension {
+                            block[r * dimension + c] += local[[a * dimension + r, b * dimension + c]];
+                        }
+                    }
+                }
+                Ok(blocks)
+            })
+            .try_reduce(HashMap::new, |mut into, mut from| {
+                if into.len() < from.len() {
+                    std::mem::swap(&mut into, &mut from);
+                }
+                for (data_index, values) in from {
+                    let block = into.entry(data_index).or_insert_with(|| vec![0.0; dimension * dimension]);
+                    block.iter_mut().zip(values).for_each(|(sum, value)| *sum += value);
+                }
+                Ok(into)
+            })?
+    };
+
+    // Every block of the pattern belongs to some element
+    for (data_index, block) in matrix.data_mut().iter_mut().enumerate() {
+        let values: &[f64] = blocks.get(&data_index).map_or(&[], Vec::as_slice);
+        for (r, block_row) in block.iter_mut().enumerate() {
+            for (c, value) in block_row.iter_mut().enumerate() {
+                *value = values.get(r * dimension + c).copied().unwrap_or(0.0);
+            }
+        }
+    }
+
+    Ok(matrix)
+}
+
+/// Greedy coloring of the elements such that elements of the same color share no node
+///
+/// Elements are visited in order and each one takes the smallest color not used by an earlier
+/// element sharing one of its nodes, so the coloring is deterministic. Structured quadrilateral
+/// or hexahedral meshes get 4 or 8 colors.
+///
+/// # Arguments
+/// * `elements` - List of element connectivity (each element is a list of node indices)
+///
+/// # Returns
+/// Color index of each element, from 0 to the number of colors - 1
+pub fn color_elements(elements: &[Vec<usize>]) -> Vec<usize> {
+    let num_node: usize = elements.iter().flatten().max().map_or(0, |&node| node + 1);
+
+    // Colors of the elements already visited, per node
+    let mut node_colors: Vec<Vec<usize>> = vec![Vec::new(); num_node];
+    // forbidden[c] == e + 1 when color c is taken by a neighbour of element e
+    let mut forbidden: Vec<usize> = Vec::new();
+    let mut colors: Vec<usize> = Vec::with_capacity(elements.len());
+
+    for (e, nodes) in elements.iter().enumerate() {
+        for &node in nodes {
+            for &color in &node_colors[node] {
+                if color >= forbidden.len() {
+                    forbidden.resize(color + 1, 0);
+                }
+                forbidden[color] = e + 1;
+            }
+        }
+
+        let color: usize = forbidden.iter().position(|&stamp| stamp != e + 1).unwrap_or(forbidden.len());
+        for &node in nodes {
+            node_colors[node].push(color);
+        }
+        colors.push(color);
+    }
+
+    colors
+}
+
+/// Blocks of one row of a BSR matrix, as split by `parallel_assemble_colored`
+type BlockRow<'a> = &'a mut [Vec<Vec<f64>>];
+
+/// Assemble a stiffness matrix in parallel, one color of `color_elements` at a time
+///
+/// Elements of the same color share no node, so they write to disjoint block rows of the matrix
+/// and are scattered concurrently without locks or per-thread buffers. Colors are processed in
+/// order, so every block receives its contributions in the same order on every run and the
+/// result is deterministic.
+///
+/// # Arguments
+/// * `num_node` - Number of nodes in the mesh
+/// * `elements` - List of element connectivity; the nodes of an element must be distinct
+/// * `dimension` - Block size (e.g., 2 for 2D problems, 3 for 3D)
+/// * `element_matrix_fn` - Dense matrix of element `e`, laid out as in `scatter_element_matrix`
+///
+/// # Returns
+/// BSR matrix with the block structure of `initialize_stiffness_matrix`, or an error if an
+/// element matrix has the wrong shape
+pub fn parallel_assemble_colored(
+    num_node: usize,
+    elements: &[Vec<usize>],
+    dimension: usize,
+    element_matrix_fn: impl Fn(usize) -> Array2<f64> + Sync,
+) -> SparseResult<BsrMatrix<f64>> {
+    let mut matrix: BsrMatrix<f64> = initialize_stiffness_matrix(num_node, elements, dimension)?;
+    let indptr: Vec<usize> = matrix.indptr().clone();
+    let indices: Vec<Vec<usize>> = matrix.indices().clone();
+
+    let colors: Vec<usize> = color_elements(elements);
+    let mut elements_by_color: Vec<Vec<usize>> = vec![Vec::new(); colors.iter().max().map_or(0, |&color| color + 1)];
+    for (e, &color) in colors.iter().enumerate() {
+        elements_by_color[color].push(e);
+    }
+
+    // Split the blocks into one mutable row per node, lent to the element being scattered
+    let data: &mut [Vec<Vec<f64>>] = matrix.data_mut();
+    data.iter_mut().flatten().for_each(|block_row| block_row.fill(0.0));
+    let mut rows: Vec<Option<BlockRow>> = Vec::with_capacity(num_node);
+    let mut rest: BlockRow = data;
+    for node in 0..num_node {
+        let (row, tail) = std::mem::take(&mut rest).split_at_mut(indptr[node + 1] - indptr[node]);
+        rows.push(Some(row));
+        rest = tail;
+    }
+
+    for color_elements in &elements_by_color {
+        let mut work: Vec<(usize, Vec<BlockRow>)> = color_elements
+            .iter()
+            .map(|&e| {
+                let element_rows = elements[e]
+                    .iter()
+                    .map(|&node| rows[node].take().expect("elements of one color share no node"))
+                    .collect();
+                (e, element_rows)
+            })
+            .collect();
+
+        let result: SparseResult<()> = work.par_iter_mut().try_for_each(|(e, element_rows)| {
+            let element_nodes: &[usize] = &elements[*e];
+            let local: Array2<f64> = element_matrix_fn(*e);
+            let local_size: usize = element_nodes.len() * dimension;
+            if local.dim() != (local_size, local_size) {
+                return Err(SparseError::ValueError(format!(
+                    "Element {} matrix has shape {:?}, expected ({}, {})", e, local.dim(), local_size, local_size
+                )));
+            }
+
+            for (a, (&i, row)) in element_nodes.iter().zip(element_rows.iter_mut()).enumerate() {
+                let columns: &[Vec<usize>] = &indices[indptr[i]..indptr[i + 1]];
+                for (b, &j) in element_nodes.iter().enumerate() {
+                    // The pattern is built from the same elements, so every block exists
+                    let position: usize = columns
+                        .binary_search(&vec![j])
+                        .expect("element block missing from its own sparsity pattern");
+                    for (r, block_row) in row[position].iter_mut().enumerate() {
+                        for (c, value) in block_row.iter_mut().enumerate() {
+                            *value += local[[a * dimension + r, b * dimension + c]];
+                        }
+                    }
+                }
+            }
+            Ok(())
+        });
+
+        // Give the rows back for the next color
+        for (e, element_rows) in work {
+            for (&node, row) in elements[e].iter().zip(element_rows) {
+                rows[node] = Some(row);
+            }
+        }
+        result?;
+    }
+
+    Ok(matrix)
+}
+
+/// Add a symmetric dense element matrix into an upper triangular stiffness matrix
+///
+/// Counterpart of `scatter_element_matrix` for `initialize_stiffness_matrix_symmetric`.
+/// Only the local blocks (a,b) whose global nodes satisfy i <= j are read, which folds the
+/// lower triangular local contributions (element nodes in decreasing global order) into the
+/// upper blocks. The local matrix is assumed symmetric.
+///
+/// # Arguments
+/// * `matrix` - Upper triangular BSR matrix containing all node pairs i <= j of the element
+/// * `element_nodes` - Global node indices of the element
+/// * `local` - Element matrix of shape (n_nodes * dimension, n_nodes * dimension)
+/// * `dimension` - Block size (e.g., 2 for 2D problems, 3 for 3D)
+///
+/// # Returns
+/// Error if the local matrix has the wrong shape or a block (i,j) is missing from the structure.
+/// The matrix is left unchanged in that case.
+pub fn scatter_element_matrix_symmetric(
+    matrix: &mut BsrMatrix<f64>,
+    element_nodes: &[usize],
+    local: &Array2<f64>,
+    dimension: usize,
+) -> SparseResult<()> {
+    let local_size: usize = element_nodes.len() * dimension;
+    if local.dim() != (local_size, local_size) {
+        return Err(SparseError::ValueError(format!(
+            "Element matrix has shape {:?}, expected ({}, {})", local.dim(), local_size, local_size
+        )));
+    }
+
+    // Local node pairs (a,b) that map to the upper block triangle
+    let local_pairs: Vec<(usize, usize)> = (0..element_nodes.len())
+        .flat_map(|a| (0..element_nodes.len()).map(move |b| (a, b)))
+        .filter(|&(a, b)| element_nodes[a] <= element_nodes[b])
+        .collect();
+    let block_positions: Vec<(usize, usize)> = local_pairs
+        .iter()
+        .map(|&(a, b)| (element_nodes[a], element_nodes[b]))
+        .collect();
+
+    let data_indices: Vec<Option<usize>> = get_data_indices_from_block_positions_binary_search(
+        matrix.indptr(), matrix.indices(), &block_positions
+    );
+
+    let data_indices: Vec<usize> = data_indices
+        .into_iter()
+        .zip(block_positions.iter())
+        .map(|(index, &(i, j))| index.ok_or_else(|| {
+            SparseError::ValueError(format!("Block ({}, {}) is not part of the sparsity pattern", i, j))
+        }))
+        .collect::<SparseResult<_>>()?;
+
+    let data = matrix.data_mut();
+
+    for (&(a, b), &data_index) in local_pairs.iter().zip(data_indices.iter()) {
+        let block = &mut data[data_index];
+        for (r, block_row) in block.iter_mut().enumerate() {
+            for (c, value) in block_row.iter_mut().enumerate() {
+                *value += local[[a * dimension + r, b * dimension + c]];
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Convert a BSR matrix to CSR by expanding each block into scalar entries
+///
+/// # Arguments
+/// * `matrix` - BSR matrix, e.g. from `initialize_stiffness_matrix`
+/// * `drop_explicit_zeros` - Skip scalar entries equal to zero. When false, every
+///   entry of every stored block is kept so the CSR pattern matches the block pattern.
+///
+/// # Returns
+/// CSR matrix with the same shape. Within each scalar row, entries follow the block
+/// ordering of the BSR matrix.
+pub fn bsr_to_csr(matrix: &BsrMatrix<f64>, drop_explicit_zeros: bool) -> SparseResult<CsrMatrix<f64>> {
+    let (block_rows, block_cols): (usize, usize) = matrix.block_size();
+    let indptr: &Vec<usize> = matrix.indptr();
+    let indices: &Vec<Vec<usize>> = matrix.indices();
+    let blocks: &Vec<Vec<Vec<f64>>> = matrix.data();
+
+    let capacity: usize = blocks.len() * block_rows * block_cols;
+    let mut data: Vec<f64> = Vec::with_capacity(capacity);
+    let mut rows: Vec<usize> = Vec::with_capacity(capacity);
+    let mut cols: Vec<usize> = Vec::with_capacity(capacity);
+
+    for (block_row, range) in indptr.windows(2).enumerate() {
+        let row_blocks = blocks[range[0]..range[1]].iter().zip(&indices[range[0]..range[1]]);
+        for r in 0..block_rows {
+            for (block, column) in row_blocks.clone() {
+                let column_offset: usize = column[0] * block_cols;
+                for (c, &value) in block[r].iter().enumerate() {
+                    if drop_explicit_zeros && value == 0.0 {
+                        continue;
+                    }
+                    data.push(value);
+                    rows.push(block_row * block_rows + r);
+                    cols.push(column_offset + c);
+                }
+            }
+        }
+    }
+
+    CsrMatrix::new(data, rows, cols, matrix.shape())
+}
+
+/// Expand a BSR matrix into a dense array, with zeros for absent blocks
+///
+/// Intended for debugging and testing small systems only: the result stores every
+/// entry of the (rows, cols) matrix.
+pub fn to_dense(matrix: &BsrMatrix<f64>) -> Array2<f64> {
+    let (block_rows, block_cols): (usize, usize) = matrix.block_size();
+    let indptr: &Vec<usize> = matrix.indptr();
+    let indices: &Vec<Vec<usize>> = matrix.indices();
+    let blocks: &Vec<Vec<Vec<f64>>> = matrix.data();
+
+    let mut dense: Array2<f64> = Array2::zeros(matrix.shape());
+    for (block_row, range) in indptr.windows(2).enumerate() {
+        for (block, column) in blocks[range[0]..range[1]].iter().zip(&indices[range[0]..range[1]]) {
+            for (r, block_row_values) in block.iter().enumerate() {
+                for (c, &value) in block_row_values.iter().enumerate() {
+                    dense[[block_row * block_rows + r, column[0] * block_cols + c]] = value;
+                }
+            }
+        }
+    }
+    dense
+}
+
+/// Write a BSR matrix as a MatrixMarket coordinate file, e.g. to load it in SciPy or MATLAB
+///
+/// Blocks are expanded to scalar `(row, col, value)` entries with 1-based indices, in the row
+/// order of `bsr_to_csr`. Entries equal to zero are skipped, so the declared number of nonzeros
+/// only counts the stored nonzero values.
+///
+/// # Arguments
+/// * `matrix` - BSR matrix, e.g. an assembled stiffness matrix
+/// * `writer` - Destination of the `.mtx` text
+pub fn write_matrix_market<W: Write>(matrix: &BsrMatrix<f64>, writer: W) -> io::Result<()> {
+    let (block_rows, block_cols): (usize, usize) = matrix.block_size();
+    let indptr: &Vec<usize> = matrix.indptr();
+    let indices: &Vec<Vec<usize>> = matrix.indices();
+    let blocks: &Vec<Vec<Vec<f64>>> = matrix.data();
+
+    let nnz: usize = blocks.iter().flatten().flatten().filter(|&&value| value != 0.0).count();
+    let (n_rows, n_cols): (usize, usize) = matrix.shape();
+
+    let mut writer = io::BufWriter::new(writer);
+    writeln!(writer, "%%MatrixMarket matrix coordinate real general")?;
+    writeln!(writer, "{} {} {}", n_rows, n_cols, nnz)?;
+
+    for (block_row, range) in indptr.windows(2).enumerate() {
+        let row_blocks = blocks[range[0]..range[1]].iter().zip(&indices[range[0]..range[1]]);
+        for r in 0..block_rows {
+            for (block, column) in row_blocks.clone() {
+                let column_offset: usize = column[0] * block_cols;
+                for (c, &value) in block[r].iter().enumerate() {
+                    if value != 0.0 {
+                        writeln!(writer, "{} {} {:e}", block_row * block_rows + r + 1, column_offset + c + 1, value)?;
+                    }
+                }
+            }
+        }
+    }
+
+    writer.flush()
+}
+
+/// Sparse matrix-vector product y += A * x for a BSR matrix
+///
+/// # Arguments
+/// * `matrix` - BSR matrix, e.g. an assembled stiffness matrix
+/// * `x` - Input vector of length equal to the number of matrix columns (num_node * dimension)
+/// * `y` - Output vector of length equal to the number of matrix rows, accumulated into
+///
+/// # Returns
+/// Error if the vector lengths do not match the matrix shape
+pub fn bsr_matvec(matrix: &BsrMatrix<f64>, x: &[f64], y: &mut [f64]) -> SparseResult<()> {
+    let (n_rows, n_cols): (usize, usize) = matrix.shape();
+    if x.len() != n_cols {
+        return Err(SparseError::DimensionMismatch { expected: n_cols, found: x.len() });
+    }
+    if y.len() != n_rows {
+        return Err(SparseError::DimensionMismatch { expected: n_rows, found: y.len() });
+    }
+
+    let (block_rows, block_cols): (usize, usize) = matrix.block_size();
+    let indices: &Vec<Vec<usize>> = matrix.indices();
+    let blocks: &Vec<Vec<Vec<f64>>> = matrix.data();
+
+    for (y_block, range) in y.chunks_exact_mut(block_rows).zip(matrix.indptr().windows(2)) {
+        for data_index in range[0]..range[1] {
+            let column_offset: usize = indices[data_index][0] * block_cols;
+            let x_block: &[f64] = &x[column_offset..column_offset + block_cols];
+            for (y_value, block_row) in y_block.iter_mut().zip(blocks[data_index].iter()) {
+                *y_value += block_row.iter().zip(x_block).map(|(a, b)| a * b).sum::<f64>();
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Apply Dirichlet boundary conditions to an assembled linear system K u = f
+///
+/// For each constrained global DOF, the prescribed value is first moved to the right-hand
+/// side (f -= K g, with g holding the prescribed values), then the matrix row and column
+/// are zeroed and the diagonal entry set to 1, and finally the right-hand side entry is
+/// set to the prescribed value. This keeps the matrix symmetric.
+///
+/// # Arguments
+/// * `matrix` - Square BSR matrix with full (non-symmetric) block storage
+/// * `rhs` - Right-hand side of length num_node * dimension
+/// * `constraints` - List of (global DOF, prescribed value). A repeated DOF keeps the last value.
+///
+/// # Returns
+/// Error if the rhs length or a DOF is out of range, or if a constrained DOF has no diagonal block
+pub fn apply_dirichlet(
+    matrix: &mut BsrMatrix<f64>,
+    rhs: &mut [f64],
+    constraints: &[(usize, f64)],
+) -> SparseResult<()> {
+    let (n_rows, n_cols): (usize, usize) = matrix.shape();
+    let (block_rows, block_cols): (usize, usize) = matrix.block_size();
+    if n_rows != n_cols || block_rows != block_cols {
+        return Err(SparseError::ValueError(
+            "Dirichlet constraints require a square matrix with square blocks".to_string()
+        ));
+    }
+    if rhs.len() != n_rows {
+        return Err(SparseError::DimensionMismatch { expected: n_rows, found: rhs.len() });
+    }
+    let dimension: usize = block_rows;
+
+    let mut prescribed: Vec<Option<f64>> = vec![None; n_rows];
+    for &(dof, value) in constraints {
+        if dof >= n_rows {
+            return Err(SparseError::IndexOutOfBounds { index: (dof, dof), shape: (n_rows, n_cols) });
+        }
+        prescribed[dof] = Some(value);
+    }
+
+    let diagonal_positions: Vec<(usize, usize)> = constraints
+        .iter()
+        .map(|&(dof, _)| {
+            let (node, _) = dof_to_node_component(dof, dimension);
+            (node, node)
+        })
+        .collect();
+    let diagonal_indices: Vec<Option<usize>> = get_data_indices_from_block_positions_binary_search(
+        matrix.indptr(), matrix.indices(), &diagonal_positions
+    );
+    if let Some(position) = diagonal_indices.iter().position(Option::is_none) {
+        let (i, j) = diagonal_positions[position];
+        return Err(SparseError::ValueError(format!("Block ({}, {}) is not part of the sparsity pattern", i, j)));
+    }
+
+    // Move the prescribed values to the right-hand side: f -= K g
+    let g: Vec<f64> = prescribed.iter().map(|value| value.unwrap_or(0.0)).collect();
+    let mut k_g: Vec<f64> = vec![0.0; n_rows];
+    bsr_matvec(matrix, &g, &mut k_g)?;
+    for (f, k_g) in rhs.iter_mut().zip(k_g.iter()) {
+        *f -= k_g;
+    }
+
+    // Zero the constrained rows and columns, with unit diagonal
+    let indptr: Vec<usize> = matrix.indptr().clone();
+    let block_columns: Vec<usize> = matrix.indices().iter().map(|column| column[0]).collect();
+    let data = matrix.data_mut();
+
+    for (block_row, range) in indptr.windows(2).enumerate() {
+        for data_index in range[0]..range[1] {
+            let block_column: usize = block_columns[data_index];
+            for (r, block_row_values) in data[data_index].iter_mut().enumerate() {
+                let row: usize = block_row * dimension + r;
+                for (c, value) in block_row_values.iter_mut().enumerate() {
+                    let column: usize = block_column * dimension + c;
+                    if prescribed[row].is_some() || prescribed[column].is_some() {
+                        *value = if row == column { 1.0 } else { 0.0 };
+                    }
+                }
+            }
+        }
+    }
+
+    for (f, value) in rhs.iter_mut().zip(prescribed.iter()) {
+        if let Some(value) = value {
+            *f = *value;
+        }
+    }
+
+    Ok(())
+}
+
+/// Add the load vector of a uniform surface traction (Neumann condition) to a right-hand side
+///
+/// Every face is integrated with the shape functions of `kind.face_kind()` and a Gauss-Legendre
+/// rule, f_(a,c) += ∫ N_a t_c dA. The area element dA is computed from the face tangents
+/// dx/dξ_k, the columns of the surface Jacobian T, as sqrt(det(Tᵀ T)).
+///
+/// # Arguments
+/// * `faces` - Global node ids of each face, in the local order of `kind.faces()`
+/// * `coords` - Nodal coordinates of shape (dimension, num_node)
+/// * `kind` - Element type of the volume elements the faces belong to
+/// * `traction` - Force per unit area, one component per dimension (-p n for a pressure p)
+/// * `rhs` - Right-hand side of length num_node * dimension
+///
+/// # Returns
+/// Error if `kind` has no face element type, or if its dimension, the traction, the rhs or a face
+/// does not match the coordinates. The rhs is left unchanged in that case.
+pub fn assemble_surface_load(
+    faces: &[Vec<u32>],
+    coords: &Array2<f64>,
+    kind: ShapeFunctionKind,
+    traction: &[f64],
+    rhs: &mut [f64],
+) -> SparseResult<()> {
+    let (dimension, num_node): (usize, usize) = coords.dim();
+    let face_kind: ShapeFunctionKind = kind.face_kind().ok_or_else(|| {
+        SparseError::ValueError(format!("Elements of type {:?} have no face element type", kind))
+    })?;
+    if kind.dimension() as usize != dimension {
+        return Err(SparseError::ValueError(format!(
+            "Elements of type {:?} do not match coordinates of dimension {}", kind, dimension
+        )));
+    }
+    if traction.len() != dimension {
+        return Err(SparseError::DimensionMismatch { expected: dimension, found: traction.len() });
+    }
+    if rhs.len() != num_node * dimension {
+        return Err(SparseError::DimensionMismatch { expected: num_node * dimension, found: rhs.len() });
+    }
+
+    let n_face_nodes: usize = face_kind.number_of_nodes() as usize;
+    for face in faces {
+        if face.len() != n_face_nodes {
+            return Err(SparseError::ValueError(format!(
+                "Face has {} nodes, expected {} for {:?}", face.len(), n_face_nodes, face_kind
+            )));
+        }
+        if let Some(&node) = face.iter().find(|&&node| node as usize >= num_node) {
+            return Err(SparseError::ValueError(format!("Node {} out of range for {} nodes", node, num_node)));
+        }
+    }
+
+    let num_points: usize = match face_kind {
+        ShapeFunctionKind::Line1 | ShapeFunctionKind::Square1 => 2,
+        _ => 3,
+    };
+    let rule = QuadratureRuleDyn::gauss_legendre(num_points, face_kind.dimension() as usize);
+
+    for face in faces {
+        let face_coords: Array2<f64> = Array2::from_shape_fn((dimension, n_face_nodes), |(d, a)| coords[[d, face[a] as usize]]);
+        for (point, &weight) in rule.iter() {
+            // Tangents dx/dξ_k as columns, shape (dimension, dimension - 1)
+            let tangents: Array2<f64> = face_coords.dot(&face_kind.evaluate_jacobian_of_shape_functions(point));
+            let area_weight: f64 = surface_area_element(&tangents) * weight;
+
+            for (&node, shape_function) in face.iter().zip(face_kind.evaluate_shape_functions(point)) {
+                for (component, t) in traction.iter().enumerate() {
+                    rhs[node_component_to_dof(node as usize, component, dimension)] += shape_function * t * area_weight;
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Area element sqrt(det(Tᵀ T)) of a surface Jacobian T with one or two tangent columns
+/// (the length of the tangent for edges of 2D elements)
+fn surface_area_element(tangents: &Array2<f64>) -> f64 {
+    let gram: Array2<f64> = tangents.t().dot(tangents);
+    let determinant: f64 = match gram.dim() {
+        (1, 1) => gram[[0, 0]],
+        _ => gram[[0, 0]] * gram[[1, 1]] - gram[[0, 1]] * gram[[1, 0]],
+    };
+    determinant.max(0.0).sqrt()
+}
+
+/// Global DOF of component `component` of node `node`, node * dimension + component, as laid
+/// out by the block matrices of this module
+///
+/// # Panics
+/// Panics if `component` is not below `dimension`
+pub fn node_component_to_dof(node: usize, component: usize, dimension: usize) -> usize {
+    assert!(component < dimension, "Component {} out of range for dimension {}", component, dimension);
+    node * dimension + component
+}
+
+/// Inverse of `node_component_to_dof`: the (node, component) of a global DOF
+///
+/// # Panics
+/// Panics if `dimension` is 0
+pub fn dof_to_node_component(dof: usize, dimension: usize) -> (usize, usize) {
+    assert!(dimension > 0, "Dimension must be positive");
+    (dof / dimension, dof % dimension)
+}
+
+/// Split a flat solution vector into the component vector of each node
+///
+/// # Arguments
+/// * `solution` - Vector of length num_node * dimension, in the DOF order of `node_component_to_dof`
+/// * `dimension` - Number of components per node
+///
+/// # Returns
+/// One vector of `dimension` values per node
+///
+/// # Panics
+/// Panics if `dimension` is 0 or does not divide the length of `solution`
+pub fn split_solution(solution: &[f64], dimension: usize) -> Vec<Vec<f64>> {
+    assert!(dimension > 0, "Dimension must be positive");
+    assert!(
+        solution.len().is_multiple_of(dimension),
+        "Solution length {} is not a multiple of dimension {}", solution.len(), dimension
+    );
+    solution.chunks_exact(dimension).map(<[f64]>::to_vec).collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -228,6 +1048,490 @@ mod tests {
         //println!("data = {:?}", matrix.data_mut());
     }
 
+    #[test]
+    fn test_to_dense() {
+        // Node 1 belongs to no element, so its rows and columns stay zero
+        let elements: Vec<Vec<usize>> = vec![vec![0, 2]];
+        let mut matrix: BsrMatrix<f64> = initialize_stiffness_matrix(3, &elements, 2).unwrap();
+        for block in matrix.data_mut().iter_mut() {
+            for row in block.iter_mut() {
+                row.fill(0.0);
+            }
+        }
+        let local = Array2::from_shape_fn((4, 4), |(i, j)| (4 * i + j + 1) as f64);
+        scatter_element_matrix(&mut matrix, &elements[0], &local, 2).unwrap();
+
+        assert_eq!(to_dense(&matrix), ndarray::array![
+            [1.0, 2.0, 0.0, 0.0, 3.0, 4.0],
+            [5.0, 6.0, 0.0, 0.0, 7.0, 8.0],
+            [0.0, 0.0, 0.0, 0.0, 0.0, 0.0],
+            [0.0, 0.0, 0.0, 0.0, 0.0, 0.0],
+            [9.0, 10.0, 0.0, 0.0, 11.0, 12.0],
+            [13.0, 14.0, 0.0, 0.0, 15.0, 16.0],
+        ]);
+    }
+
+    #[test]
+    fn test_scatter_element_matrix() {
+        let num_node: usize = 3;
+        let elements: Vec<Vec<usize>> = vec![vec![0, 1], vec![1, 2]];
+        let dimension: usize = 1;
+
+        let mut matrix: BsrMatrix<f64> = initialize_stiffness_matrix(num_node, &elements, dimension).unwrap();
+        for block in matrix.data_mut().iter_mut() {
+            for row in block.iter_mut() {
+                row.fill(0.0);
+            }
+        }
+
+        // Two bar elements with stiffness 1 and 2
+        let local_0 = ndarray::array![[1.0, -1.0], [-1.0, 1.0]];
+        let local_1 = ndarray::array![[2.0, -2.0], [-2.0, 2.0]];
+        scatter_element_matrix(&mut matrix, &elements[0], &local_0, dimension).unwrap();
+        scatter_element_matrix(&mut matrix, &elements[1], &local_1, dimension).unwrap();
+
+        // The shared DOF of node 1 accumulates both contributions
+        assert_eq!(matrix.to_dense(), vec![
+            vec![1.0, -1.0, 0.0],
+            vec![-1.0, 3.0, -2.0],
+            vec![0.0, -2.0, 2.0],
+        ]);
+
+        // Block (0, 2) is not part of the sparsity pattern
+        let local = Array2::zeros((2, 2));
+        assert!(scatter_element_matrix(&mut matrix, &[0, 2], &local, dimension).is_err());
+
+        // Wrong local shape
+        let local = Array2::zeros((3, 3));
+        assert!(scatter_element_matrix(&mut matrix, &[0, 1], &local, dimension).is_err());
+    }
+
+    #[test]
+    fn test_parallel_assemble_matches_serial() {
+        // Overlapping 4-node elements on a chain of 40 nodes, 2 DOFs per node
+        let num_node: usize = 40;
+        let dimension: usize = 2;
+        let elements: Vec<Vec<usize>> = (0..37).map(|e| vec![e, e + 1, e + 2, e + 3]).collect();
+        let element_matrix = |e: usize| Array2::from_shape_fn((8, 8), |(r, c)| (e * 64 + r * 8 + c) as f64 * 0.01 + if r == c { 1.0 } else { 0.0 });
+
+        let mut serial: BsrMatrix<f64> = initialize_stiffness_matrix(num_node, &elements, dimension).unwrap();
+        for block in serial.data_mut().iter_mut() {
+            for row in block.iter_mut() {
+                row.fill(0.0);
+            }
+        }
+        for (e, nodes) in elements.iter().enumerate() {
+            scatter_element_matrix(&mut serial, nodes, &element_matrix(e), dimension).unwrap();
+        }
+
+        let parallel: BsrMatrix<f64> = parallel_assemble(num_node, &elements, dimension, element_matrix).unwrap();
+        assert_eq!(parallel.indptr(), serial.indptr());
+        assert_eq!(parallel.indices(), serial.indices());
+        for (p, s) in to_dense(&parallel).iter().zip(to_dense(&serial).iter()) {
+            assert!((p - s).abs() < 1e-9);
+        }
+
+        // Wrong element matrix shape
+        assert!(parallel_assemble(num_node, &elements, dimension, |_| Array2::zeros((4, 4))).is_err());
+    }
+
+    #[test]
+    fn test_color_elements() {
+        // 4x3 grid of quadrilaterals on 5x4 nodes
+        let elements: Vec<Vec<usize>> = (0..3)
+            .flat_map(|y| (0..4).map(move |x| {
+                let n = y * 5 + x;
+                vec![n, n + 1, n + 5, n + 6]
+            }))
+            .collect();
+
+        let colors: Vec<usize> = color_elements(&elements);
+        assert_eq!(colors.len(), elements.len());
+        assert_eq!(colors.iter().max(), Some(&3));
+        assert_eq!(colors, color_elements(&elements));
+        for (e, nodes_e) in elements.iter().enumerate() {
+            for (f, nodes_f) in elements.iter().enumerate().skip(e + 1) {
+                if colors[e] == colors[f] {
+                    assert!(nodes_e.iter().all(|node| !nodes_f.contains(node)), "elements {} and {} share a node", e, f);
+                }
+            }
+        }
+        assert!(color_elements(&[]).is_empty());
+
+        // Colored assembly matches the buffer-reduction assembly
+        let dimension: usize = 2;
+        let element_matrix = |e: usize| Array2::from_shape_fn((8, 8), |(r, c)| (e * 64 + r * 8 + c) as f64 * 0.01 + if r == c { 1.0 } else { 0.0 });
+        let colored: BsrMatrix<f64> = parallel_assemble_colored(20, &elements, dimension, element_matrix).unwrap();
+        let reduced: BsrMatrix<f64> = parallel_assemble(20, &elements, dimension, element_matrix).unwrap();
+        assert_eq!(colored.indices(), reduced.indices());
+        for (p, s) in to_dense(&colored).iter().zip(to_dense(&reduced).iter()) {
+            assert!((p - s).abs() < 1e-9);
+        }
+
+        assert!(parallel_assemble_colored(20, &elements, dimension, |_| Array2::zeros((4, 4))).is_err());
+    }
+
+    #[test]
+    fn test_precomputed_assembly_map() {
+        let num_node: usize = 12;
+        let dimension: usize = 2;
+        let elements: Vec<Vec<usize>> = (0..9).map(|e| vec![e + 3, e, e + 2, e + 1]).collect();
+        let element_matrix = |e: usize| Array2::from_shape_fn((8, 8), |(r, c)| (e * 64 + r * 8 + c) as f64 * 0.01);
+
+        let mut searched: BsrMatrix<f64> = initialize_stiffness_matrix(num_node, &elements, dimension).unwrap();
+        let mut mapped: BsrMatrix<f64> = initialize_stiffness_matrix(num_node, &elements, dimension).unwrap();
+        let map: Vec<Vec<usize>> = precompute_assembly_map(&mapped, &elements).unwrap();
+        assert!(map.iter().all(|data_indices| data_indices.len() == 16));
+
+        // Two assembly passes, as in two solver iterations
+        for _ in 0..2 {
+            for (e, nodes) in elements.iter().enumerate() {
+                scatter_element_matrix(&mut searched, nodes, &element_matrix(e), dimension).unwrap();
+                scatter_element_matrix_mapped(&mut mapped, &map[e], &element_matrix(e), dimension).unwrap();
+            }
+        }
+        assert_eq!(to_dense(&mapped), to_dense(&searched));
+
+        // Block (0, 11) is not part of the pattern
+        assert!(precompute_assembly_map(&mapped, &[vec![0, 11]]).is_err());
+        // Local matrix of another element size, or data index past the blocks
+        assert!(scatter_element_matrix_mapped(&mut mapped, &map[0], &Array2::zeros((4, 4)), dimension).is_err());
+        assert!(scatter_element_matrix_mapped(&mut mapped, &[0, 1, 2, 10_000], &Array2::zeros((4, 4)), dimension).is_err());
+        assert_eq!(to_dense(&mapped), to_dense(&searched));
+    }
+
+    #[test]
+    fn test_scatter_element_matrix_blocks() {
+        let elements: Vec<Vec<usize>> = vec![vec![0, 1]];
+        let dimension: usize = 2;
+
+        let mut matrix: BsrMatrix<f64> = initialize_stiffness_matrix(2, &elements, dimension).unwrap();
+
+        // Adds on top of the initial ones
+        let local = Array2::from_shape_fn((4, 4), |(r, c)| (4 * r + c) as f64);
+        scatter_element_matrix(&mut matrix, &elements[0], &local, dimension).unwrap();
+
+        let dense = matrix.to_dense();
+        for r in 0..4 {
+            for c in 0..4 {
+                assert_eq!(dense[r][c], 1.0 + local[[r, c]]);
+            }
+        }
+    }
+
+    #[test]
+    fn test_bsr_to_csr() {
+        let num_node: usize = 3;
+        let elements: Vec<Vec<usize>> = vec![vec![0, 1], vec![1, 2]];
+        let dimension: usize = 2;
+
+        let mut matrix: BsrMatrix<f64> = initialize_stiffness_matrix(num_node, &elements, dimension).unwrap();
+        let csr: CsrMatrix<f64> = bsr_to_csr(&matrix, false).unwrap();
+
+        assert_eq!(csr.shape(), (num_node * dimension, num_node * dimension));
+        assert_eq!(csr.nnz(), 7 * dimension * dimension);
+
+        let bsr_dense: Vec<Vec<f64>> = matrix.to_dense();
+        let csr_dense: Vec<Vec<f64>> = csr.to_dense();
+        assert_eq!(csr_dense, bsr_dense);
+        for (csr_row, bsr_row) in csr_dense.iter().zip(bsr_dense.iter()) {
+            assert_eq!(csr_row.iter().sum::<f64>(), bsr_row.iter().sum::<f64>());
+        }
+
+        // Zero out the (1, 1) block: kept by default, dropped on request
+        let data_index: usize = get_data_indices_from_block_positions_binary_search(
+            matrix.indptr(), matrix.indices(), &[(1, 1)]
+        )[0].unwrap();
+        for row in matrix.data_mut()[data_index].iter_mut() {
+            row.fill(0.0);
+        }
+        assert_eq!(bsr_to_csr(&matrix, false).unwrap().nnz(), 7 * dimension * dimension);
+        let csr: CsrMatrix<f64> = bsr_to_csr(&matrix, true).unwrap();
+        assert_eq!(csr.nnz(), 6 * dimension * dimension);
+        assert_eq!(csr.to_dense(), matrix.to_dense());
+    }
+
+    /// Minimal reader of `write_matrix_market` output: shape, declared nnz and dense values
+    fn read_matrix_market(text: &str) -> ((usize, usize), usize, Vec<Vec<f64>>) {
+        let mut lines = text.lines().filter(|line| !line.starts_with('%'));
+        let size: Vec<usize> = lines.next().unwrap().split_whitespace().map(|x| x.parse().unwrap()).collect();
+        let mut dense: Vec<Vec<f64>> = vec![vec![0.0; size[1]]; size[0]];
+        let mut entries: usize = 0;
+        for line in lines {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let (row, col): (usize, usize) = (fields[0].parse().unwrap(), fields[1].parse().unwrap());
+            dense[row - 1][col - 1] += fields[2].parse::<f64>().unwrap();
+            entries += 1;
+        }
+        assert_eq!(entries, size[2]);
+        ((size[0], size[1]), size[2], dense)
+    }
+
+    #[test]
+    fn test_write_matrix_market() {
+        let elements: Vec<Vec<usize>> = vec![vec![0, 1], vec![1, 2]];
+        let mut matrix: BsrMatrix<f64> = initialize_stiffness_matrix(3, &elements, 2).unwrap();
+        for block in matrix.data_mut().iter_mut() {
+            for row in block.iter_mut() {
+                row.fill(0.0);
+            }
+        }
+        let mut local = Array2::from_shape_fn((4, 4), |(i, j)| if i == j { 2.5 } else { -0.125 * (i + j) as f64 });
+        local[[0, 1]] = 0.0;
+        scatter_element_matrix(&mut matrix, &elements[0], &local, 2).unwrap();
+        scatter_element_matrix(&mut matrix, &elements[1], &(local * 1e-7), 2).unwrap();
+
+        let mut buffer: Vec<u8> = Vec::new();
+        write_matrix_market(&matrix, &mut buffer).unwrap();
+        let text = String::from_utf8(buffer).unwrap();
+        assert!(text.starts_with("%%MatrixMarket matrix coordinate real general\n6 6 "));
+
+        // Entry (0, 1) is stored in block (0, 0) but zero, so it is skipped
+        let (shape, nnz, dense) = read_matrix_market(&text);
+        assert_eq!(shape, (6, 6));
+        assert_eq!(nnz, 7 * 4 - 1);
+        assert_eq!(dense, matrix.to_dense());
+    }
+
+    /// Mirror the strictly upper triangle of a dense matrix into the lower triangle
+    fn symmetrize_upper(dense: Vec<Vec<f64>>) -> Vec<Vec<f64>> {
+        let n: usize = dense.len();
+        (0..n)
+            .map(|r| (0..n).map(|c| if c < r { dense[c][r] } else { dense[r][c] }).collect())
+            .collect()
+    }
+
+    #[test]
+    fn test_initialize_stiffness_matrix_symmetric() {
+        let num_node: usize = 3;
+        let elements: Vec<Vec<usize>> = vec![vec![0, 1], vec![2, 1]];
+        let dimension: usize = 2;
+
+        let full: BsrMatrix<f64> = initialize_stiffness_matrix(num_node, &elements, dimension).unwrap();
+        let symmetric: BsrMatrix<f64> = initialize_stiffness_matrix_symmetric(num_node, &elements, dimension).unwrap();
+
+        assert_eq!(full.data().len(), 7);
+        assert_eq!(symmetric.data().len(), 5);
+        assert_eq!(symmetric.indptr(), &vec![0, 2, 4, 5]);
+        assert_eq!(symmetric.shape(), full.shape());
+        assert_eq!(symmetrize_upper(symmetric.to_dense()), full.to_dense());
+    }
+
+    #[test]
+    fn test_scatter_element_matrix_symmetric() {
+        let num_node: usize = 3;
+        // Second element lists its nodes in decreasing global order
+        let elements: Vec<Vec<usize>> = vec![vec![0, 1], vec![2, 1]];
+        let dimension: usize = 2;
+
+        let mut full: BsrMatrix<f64> = initialize_stiffness_matrix(num_node, &elements, dimension).unwrap();
+        let mut symmetric: BsrMatrix<f64> = initialize_stiffness_matrix_symmetric(num_node, &elements, dimension).unwrap();
+
+        for nodes in &elements {
+            let local = Array2::from_shape_fn((4, 4), |(r, c)| (r * c + r + c) as f64 + nodes[0] as f64);
+            scatter_element_matrix(&mut full, nodes, &local, dimension).unwrap();
+            scatter_element_matrix_symmetric(&mut symmetric, nodes, &local, dimension).unwrap();
+        }
+
+        assert_eq!(symmetrize_upper(symmetric.to_dense()), full.to_dense());
+
+        // Lower blocks are not stored
+        let local = Array2::zeros((4, 4));
+        assert!(scatter_element_matrix(&mut symmetric, &[1, 0], &local, dimension).is_err());
+        assert!(scatter_element_matrix_symmetric(&mut symmetric, &[1, 0], &local, dimension).is_ok());
+    }
+
+    #[test]
+    fn test_bsr_matvec() {
+        let num_node: usize = 3;
+        let elements: Vec<Vec<usize>> = vec![vec![0, 1], vec![1, 2]];
+        let dimension: usize = 2;
+
+        let matrix: BsrMatrix<f64> = initialize_stiffness_matrix(num_node, &elements, dimension).unwrap();
+        let x: Vec<f64> = (0..num_node * dimension).map(|i| i as f64 + 1.0).collect();
+        let mut y: Vec<f64> = vec![0.5; num_node * dimension];
+
+        bsr_matvec(&matrix, &x, &mut y).unwrap();
+
+        let expected: Vec<f64> = matrix
+            .to_dense()
+            .iter()
+            .map(|row| 0.5 + row.iter().zip(x.iter()).map(|(a, b)| a * b).sum::<f64>())
+            .collect();
+        assert_eq!(y, expected);
+        assert_eq!(y, vec![10.5, 10.5, 21.5, 21.5, 18.5, 18.5]);
+
+        // Length validation
+        assert!(bsr_matvec(&matrix, &x[1..], &mut y).is_err());
+        assert!(bsr_matvec(&matrix, &x, &mut y[1..]).is_err());
+    }
+
+    /// Solve a small dense system by Gaussian elimination with partial pivoting
+    fn solve_dense(mut a: Vec<Vec<f64>>, mut b: Vec<f64>) -> Vec<f64> {
+        let n: usize = b.len();
+        for k in 0..n {
+            let pivot: usize = (k..n).max_by(|&i, &j| a[i][k].abs().total_cmp(&a[j][k].abs())).unwrap();
+            a.swap(k, pivot);
+            b.swap(k, pivot);
+            for i in k + 1..n {
+                let factor: f64 = a[i][k] / a[k][k];
+                let row_k: Vec<f64> = a[k].clone();
+                for (a_ij, a_kj) in a[i].iter_mut().zip(row_k.iter()).skip(k) {
+                    *a_ij -= factor * a_kj;
+                }
+                b[i] -= factor * b[k];
+            }
+        }
+        let mut x: Vec<f64> = vec![0.0; n];
+        for k in (0..n).rev() {
+            let sum: f64 = (k + 1..n).map(|j| a[k][j] * x[j]).sum();
+            x[k] = (b[k] - sum) / a[k][k];
+        }
+        x
+    }
+
+    #[test]
+    fn test_apply_dirichlet() {
+        let num_node: usize = 4;
+        let elements: Vec<Vec<usize>> = vec![vec![0, 1], vec![1, 2], vec![2, 3]];
+        let dimension: usize = 1;
+
+        // Chain of bars with stiffness 1, 2, 1
+        let mut matrix: BsrMatrix<f64> = initialize_stiffness_matrix(num_node, &elements, dimension).unwrap();
+        for block in matrix.data_mut().iter_mut() {
+            for row in block.iter_mut() {
+                row.fill(0.0);
+            }
+        }
+        for (nodes, k) in elements.iter().zip([1.0, 2.0, 1.0]) {
+            let local = ndarray::array![[k, -k], [-k, k]];
+            scatter_element_matrix(&mut matrix, nodes, &local, dimension).unwrap();
+        }
+
+        let mut rhs: Vec<f64> = vec![0.0, 1.0, 0.0, 0.0];
+        apply_dirichlet(&mut matrix, &mut rhs, &[(0, 1.0), (3, 4.0)]).unwrap();
+
+        let dense: Vec<Vec<f64>> = matrix.to_dense();
+        for (r, row) in dense.iter().enumerate() {
+            for (c, &value) in row.iter().enumerate() {
+                assert_eq!(value, dense[c][r]);
+            }
+        }
+        assert_eq!(dense[0], vec![1.0, 0.0, 0.0, 0.0]);
+        assert_eq!(dense[3], vec![0.0, 0.0, 0.0, 1.0]);
+
+        // Interior equations: 3 u1 - 2 u2 = 1 + 1, -2 u1 + 3 u2 = 4
+        let u: Vec<f64> = solve_dense(dense, rhs);
+        let expected: Vec<f64> = vec![1.0, 14.0 / 5.0, 16.0 / 5.0, 4.0];
+        for (value, expected) in u.iter().zip(expected.iter()) {
+            assert!((value - expected).abs() < 1e-12);
+        }
+
+        assert!(apply_dirichlet(&mut matrix, &mut [0.0; 4], &[(4, 0.0)]).is_err());
+        assert!(apply_dirichlet(&mut matrix, &mut [0.0; 3], &[(0, 0.0)]).is_err());
+    }
+
+    #[test]
+    fn test_apply_dirichlet_blocks() {
+        let elements: Vec<Vec<usize>> = vec![vec![0, 1]];
+        let dimension: usize = 2;
+
+        let mut matrix: BsrMatrix<f64> = initialize_stiffness_matrix(2, &elements, dimension).unwrap();
+        let mut rhs: Vec<f64> = vec![0.0; 4];
+        apply_dirichlet(&mut matrix, &mut rhs, &[(1, 2.0)]).unwrap();
+
+        assert_eq!(matrix.to_dense(), vec![
+            vec![1.0, 0.0, 1.0, 1.0],
+            vec![0.0, 1.0, 0.0, 0.0],
+            vec![1.0, 0.0, 1.0, 1.0],
+            vec![1.0, 0.0, 1.0, 1.0],
+        ]);
+        assert_eq!(rhs, vec![-2.0, 2.0, -2.0, -2.0]);
+    }
+
+    #[test]
+    fn test_assemble_surface_load() {
+        // Uniform pressure p on the face z = 1 of a unit cube: total force -p * area along z
+        let pressure = 5.0;
+        let kind = ShapeFunctionKind::Cube1;
+        let nodes = kind.reference_nodes();
+        let coords = Array2::from_shape_fn((3, 8), |(d, i)| nodes[i][d]);
+        let top: Vec<u32> = kind.faces()[5].iter().map(|&local| local as u32).collect();
+
+        let mut rhs = vec![0.0; 24];
+        assemble_surface_load(std::slice::from_ref(&top), &coords, kind, &[0.0, 0.0, -pressure], &mut rhs).unwrap();
+        let (_, components): (Vec<usize>, Vec<usize>) = (0..24).map(|dof| dof_to_node_component(dof, 3)).unzip();
+        let total = |rhs: &[f64], component: usize| -> f64 {
+            rhs.iter().zip(&components).filter(|&(_, &c)| c == component).map(|(f, _)| f).sum()
+        };
+        assert!((total(&rhs, 2) + pressure).abs() < 1e-12);
+        assert!(total(&rhs, 0).abs() < 1e-12 && total(&rhs, 1).abs() < 1e-12);
+        for node in 0..8 {
+            let expected = if top.contains(&(node as u32)) { -pressure / 4.0 } else { 0.0 };
+            assert!((rhs[node * 3 + 2] - expected).abs() < 1e-12);
+        }
+
+        // Quadratic box of size 2 x 1 x 3, oblique traction on the face x = 1 of area 3
+        let kind = ShapeFunctionKind::Cube2;
+        let nodes = kind.reference_nodes();
+        let coords = Array2::from_shape_fn((3, 27), |(d, i)| nodes[i][d] * [2.0, 1.0, 3.0][d]);
+        let face: Vec<u32> = kind.faces()[1].iter().map(|&local| local as u32).collect();
+        let traction = [1.5, -0.5, 2.0];
+        let mut rhs = vec![0.0; 81];
+        assemble_surface_load(&[face], &coords, kind, &traction, &mut rhs).unwrap();
+        let components: Vec<usize> = (0..81).map(|dof| dof_to_node_component(dof, 3).1).collect();
+        for (component, t) in traction.iter().enumerate() {
+            let force: f64 = rhs.iter().zip(&components).filter(|&(_, &c)| c == component).map(|(f, _)| f).sum();
+            assert!((force - t * 3.0).abs() < 1e-12);
+        }
+
+        // 2D: edge of length 5 of a quadrilateral
+        let coords = ndarray::array![[0.0, 3.0, 0.0, 3.0], [0.0, 4.0, 1.0, 5.0]];
+        let mut rhs = vec![0.0; 8];
+        assemble_surface_load(&[vec![0, 1]], &coords, ShapeFunctionKind::Square1, &[0.0, 2.0], &mut rhs).unwrap();
+        for (f, expected) in rhs.iter().zip([0.0, 5.0, 0.0, 5.0, 0.0, 0.0, 0.0, 0.0]) {
+            assert!((f - expected).abs() < 1e-12);
+        }
+
+        // Errors leave the rhs unchanged
+        let loaded = rhs.clone();
+        assert!(assemble_surface_load(&[vec![0, 1]], &coords, ShapeFunctionKind::Square1, &[1.0], &mut rhs).is_err());
+        assert!(assemble_surface_load(&[vec![0, 9]], &coords, ShapeFunctionKind::Square1, &[0.0, 1.0], &mut rhs).is_err());
+        assert!(assemble_surface_load(&[vec![0, 1, 2]], &coords, ShapeFunctionKind::Square1, &[0.0, 1.0], &mut rhs).is_err());
+        assert!(assemble_surface_load(&[vec![0, 1]], &coords, ShapeFunctionKind::Cube1, &[0.0, 1.0], &mut rhs).is_err());
+        assert!(assemble_surface_load(&[], &coords, ShapeFunctionKind::CubeSerendipity, &[0.0, 1.0], &mut rhs).is_err());
+        assert_eq!(rhs, loaded);
+    }
+
+    #[test]
+    fn test_dof_mapping() {
+        for dimension in 1..=3 {
+            for dof in 0..12 {
+                let (node, component) = dof_to_node_component(dof, dimension);
+                assert!(component < dimension);
+                assert_eq!(node_component_to_dof(node, component, dimension), dof);
+            }
+        }
+        assert_eq!(node_component_to_dof(4, 2, 3), 14);
+        assert_eq!(dof_to_node_component(14, 3), (4, 2));
+
+        // Displacements (u_x, u_y) of three nodes
+        let solution = [0.0, 0.5, 1.0, 1.5, 2.0, 2.5];
+        let by_node = split_solution(&solution, 2);
+        assert_eq!(by_node, vec![vec![0.0, 0.5], vec![1.0, 1.5], vec![2.0, 2.5]]);
+        for (dof, &value) in solution.iter().enumerate() {
+            let (node, component) = dof_to_node_component(dof, 2);
+            assert_eq!(by_node[node][component], value);
+        }
+        assert!(split_solution(&[], 3).is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "not a multiple of dimension")]
+    fn test_split_solution_length_mismatch() {
+        split_solution(&[1.0, 2.0, 3.0], 2);
+    }
+
     /*
     #[test]
     fn test_get_data_indices() {
@@ -18,19 +18,24 @@
 //! - **Reader-Writer Lock (RwLock)**: Multiple concurrent readers or single writer
 //! - **Thread-Safe Wrapper**: `ThreadSafeArrayUpdater` for concurrent FEM simulations
 //! - **Poison Detection**: Proper error handling for thread synchronization failures
+//! - **Poison Recovery**: `clear_poison()` resumes access after a writer thread panicked
 //! - **Cross-Thread Compatibility**: Safe sharing between simulation threads
 //!
 //! ## Performance Optimizations:
 //! - **Batch Operations**: `update_values()` for efficient stiffness matrix assembly
+//! - **Batch Reads**: `gather()` reads the scattered DOFs of an element in one call
+//! - **Block Access**: `get_block()` / `set_block()` for contiguous rows and vector segments
 //! - **Direct Memory Access**: Bypasses filesystem overhead for frequent updates
 //! - **Bounds Checking**: Minimal overhead with compile-time known array size
 //! - **Type-Safe Operations**: Guaranteed f64 alignment and memory safety
 //!
 //! ## FEM-Specific Features:
 //! - **Fixed-Length Arrays**: Perfect for pre-allocated stiffness matrices and load vectors
+//! - **Resizing**: `resize()` grows or truncates the file when adaptive refinement changes the DOF count
 //! - **Random Access Pattern**: Optimized for sparse matrix operations in FEM
 //! - **Element-wise Operations**: Support for local stiffness matrix integration
 //! - **Persistence**: Crash recovery and incremental saving during long simulations
+//! - **Restart Files**: `export_checksummed()` / `import_checksummed()` detect truncated or corrupted files
 //!
 //! # Typical FEM Usage Scenarios:
 //! - Global stiffness matrix assembly and modification
@@ -106,13 +111,16 @@
 //! # Performance Characteristics:
 //! - Memory usage: O(1) overhead regardless of array size
 //! - Access time: ~RAM speed for recently accessed elements
-//! - Persistence: Configurable flush frequency for I/O optimization
+//! - Persistence: Configurable flush frequency for I/O optimization, plus a flush on drop
+//!   unless disabled with `with_flush_on_drop(false)`
 //! - Concurrency: Linear scaling with number of reader threads
 //!
 //! # File Format:
 //! - Binary format with native-endian f64 values
-//! - Fixed-length: ARRAY_LENGTH * sizeof(f64) bytes
+//! - ARRAY_LENGTH * sizeof(f64) bytes when created, then length * sizeof(f64) after `resize()`
 //! - Directly mappable to memory for zero-copy access
+//! - Checksummed export: 8-byte magic "FEMARRAY", u64 value count, u128 xxHash checksum of the
+//!   data (as in the HyperNode mesh format), then the values, all native-endian
 //!
 //! # Safety Guarantees:
 //! - Bounds checking on all array accesses
@@ -122,13 +130,19 @@
 
 use memmap2::MmapMut;
 use std::fs::{OpenOptions, File};
-use std::io;
+use std::io::{self, Read, Write};
 use std::mem::size_of;
 use std::sync::RwLock;
 
+use crate::mesh::hypernode::calculate_checksum;
+
 const ARRAY_LENGTH: usize = 1_000_000; // Your array size
 const F64_SIZE: usize = size_of::<f64>();
 
+const CHECKSUMMED_MAGIC: [u8; 8] = *b"FEMARRAY";
+// Magic, value count and checksum
+const CHECKSUMMED_HEADER_SIZE: usize = 8 + size_of::<u64>() + size_of::<u128>();
+
 /// A memory-mapped array updater for efficient random access to large fixed-length f64 arrays
 /// stored in a file. Uses memory mapping for high-performance updates with persistence.
 ///
@@ -140,6 +154,7 @@ const F64_SIZE: usize = size_of::<f64>();
 pub struct ArrayUpdater {
     mmap: MmapMut,    // Memory-mapped view of the file
     file: File,       // Underlying file handle
+    flush_on_drop: bool,
 }
 
 impl ArrayUpdater {
@@ -153,8 +168,10 @@ impl ArrayUpdater {
     /// 
     /// # Behavior
     /// - Opens or creates the file with read/write access
-    /// - Ensures the file is exactly the right size for the array
+    /// - Sizes a new (empty) file for ARRAY_LENGTH values; an existing file keeps its length,
+    ///   so a file shrunk or grown with `resize()` reopens at that size
     /// - Creates a memory mapping for efficient access
+    /// - Flushes when dropped, see `with_flush_on_drop`
     pub fn new(file_path: &str) -> io::Result<Self> {
         let file = OpenOptions::new()
             .read(true)
@@ -162,13 +179,25 @@ impl ArrayUpdater {
             .create(true)
             .open(file_path)?;
         
-        // Ensure file is the right size
-        file.set_len((ARRAY_LENGTH * F64_SIZE) as u64)?;
+        // Size new files only, existing ones may have been resized
+        if file.metadata()?.len() == 0 {
+            file.set_len((ARRAY_LENGTH * F64_SIZE) as u64)?;
+        }
         
         // SAFETY: We ensure the file is properly sized and we do bounds checking on all accesses
         let mmap = unsafe { MmapMut::map_mut(&file)? };
         
-        Ok(Self { mmap, file })
+        Ok(Self { mmap, file, flush_on_drop: true })
+    }
+
+    /// Sets whether the updater flushes the mapping when dropped (enabled by default).
+    /// 
+    /// # Note
+    /// Disabling it is meant for throughput-critical scratch buffers whose contents need not
+    /// survive; the OS still writes dirty pages back eventually, but not at a defined point.
+    pub fn with_flush_on_drop(mut self, enabled: bool) -> Self {
+        self.flush_on_drop = enabled;
+        self
     }
 
     /// Reads the value at the specified index without modifying it.
@@ -182,12 +211,7 @@ impl ArrayUpdater {
     /// # Errors
     /// - Returns `InvalidInput` error if index is out of bounds
     pub fn get_value(&self, index: usize) -> io::Result<f64> {
-        if index >= ARRAY_LENGTH {
-            return Err(io::Error::new(
-                io::ErrorKind::InvalidInput,
-                format!("Index {} out of bounds (max {})", index, ARRAY_LENGTH - 1),
-            ));
-        }
+        self.check_index(index)?;
 
         let offset = index * F64_SIZE;
         
@@ -197,6 +221,68 @@ impl ArrayUpdater {
         Ok(f64::from_ne_bytes(bytes))
     }
 
+    /// Reads the values at the specified indices, e.g. the global DOFs of an element.
+    /// 
+    /// # Arguments
+    /// * `indices` - Slice of indices to read, in any order and possibly repeated
+    /// 
+    /// # Returns
+    /// * `std::io::Result<Vec<f64>>` - The values in the order of `indices`
+    /// 
+    /// # Errors
+    /// - Returns `InvalidInput` error if any index is out of bounds; nothing is read in that case
+    pub fn gather(&self, indices: &[usize]) -> io::Result<Vec<f64>> {
+        for &index in indices {
+            self.check_index(index)?;
+        }
+
+        Ok(indices
+            .iter()
+            .map(|&index| {
+                let offset = index * F64_SIZE;
+                let mut bytes = [0u8; F64_SIZE];
+                bytes.copy_from_slice(&self.mmap[offset..offset + F64_SIZE]);
+                f64::from_ne_bytes(bytes)
+            })
+            .collect())
+    }
+
+    /// Zero-copy view of `len` consecutive values starting at `start`, e.g. one row of a dense
+    /// row-major matrix stored in the array.
+    /// 
+    /// # Errors
+    /// - Returns `InvalidInput` error if `start + len` exceeds the array length
+    /// - Returns `InvalidData` error if the mapping is not aligned for f64, which does not
+    ///   happen for page-aligned file mappings
+    pub fn get_block(&self, start: usize, len: usize) -> io::Result<&[f64]> {
+        let range = self.block_range(start, len)?;
+        bytemuck::try_cast_slice(&self.mmap[range]).map_err(|e| {
+            io::Error::new(io::ErrorKind::InvalidData, format!("Cannot view block as f64: {}", e))
+        })
+    }
+
+    /// Overwrites `values.len()` consecutive values starting at `start`.
+    /// 
+    /// # Errors
+    /// - Returns `InvalidInput` error if `start + values.len()` exceeds the array length;
+    ///   nothing is written in that case
+    pub fn set_block(&mut self, start: usize, values: &[f64]) -> io::Result<()> {
+        let range = self.block_range(start, values.len())?;
+        self.mmap[range].copy_from_slice(bytemuck::cast_slice(values));
+        Ok(())
+    }
+
+    // Byte range of a block of values, bounds checked
+    fn block_range(&self, start: usize, len: usize) -> io::Result<std::ops::Range<usize>> {
+        match start.checked_add(len) {
+            Some(end) if end <= self.len() => Ok(start * F64_SIZE..end * F64_SIZE),
+            _ => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("Block of {} values at {} out of bounds (length {})", len, start, self.len()),
+            )),
+        }
+    }
+
     /// Updates a value at the specified index using the provided operation.
     /// 
     /// # Arguments
@@ -209,12 +295,7 @@ impl ArrayUpdater {
     /// # Errors
     /// - Returns `InvalidInput` error if index is out of bounds
     pub fn update_value(&mut self, index: usize, operation: impl Fn(f64) -> f64) -> io::Result<()> {
-        if index >= ARRAY_LENGTH {
-            return Err(io::Error::new(
-                io::ErrorKind::InvalidInput,
-                format!("Index {} out of bounds (max {})", index, ARRAY_LENGTH - 1),
-            ));
-        }
+        self.check_index(index)?;
 
         let offset = index * F64_SIZE;
         
@@ -247,12 +328,7 @@ impl ArrayUpdater {
     /// as it avoids repeated bounds checking and error handling for each index.
     pub fn update_values(&mut self, indices: &[usize], operation: impl Fn(f64) -> f64 + Copy) -> io::Result<()> {
         for &index in indices {
-            if index >= ARRAY_LENGTH {
-                return Err(io::Error::new(
-                    io::ErrorKind::InvalidInput,
-                    format!("Index {} out of bounds (max {})", index, ARRAY_LENGTH - 1),
-                ));
-            }
+            self.check_index(index)?;
         }
 
         for &index in indices {
@@ -286,14 +362,175 @@ impl ArrayUpdater {
         self.mmap.flush()
     }
 
+    /// Sets every value of the array, e.g. to reset a load vector between load steps.
+    /// 
+    /// # Arguments
+    /// * `value` - Value written into every slot
+    pub fn fill(&mut self, value: f64) {
+        // Page-aligned mappings can be viewed as f64 directly
+        match bytemuck::try_cast_slice_mut::<u8, f64>(&mut self.mmap) {
+            Ok(values) => values.fill(value),
+            Err(_) => {
+                let bytes = value.to_ne_bytes();
+                for slot in self.mmap.chunks_exact_mut(F64_SIZE) {
+                    slot.copy_from_slice(&bytes);
+                }
+            }
+        }
+    }
+
+    /// Sets every value of the array to 0.0.
+    pub fn zero(&mut self) {
+        self.fill(0.0)
+    }
+
+    /// Saves the current array contents to a separate snapshot file.
+    /// 
+    /// # Arguments
+    /// * `path` - Path of the snapshot file, replaced if it exists
+    /// 
+    /// # Note
+    /// The contents are written to `<path>.tmp` and synced before being renamed to `path`,
+    /// so a crash during the snapshot never leaves a partially written snapshot behind.
+    pub fn snapshot(&self, path: &str) -> io::Result<()> {
+        let temp_path = format!("{}.tmp", path);
+        {
+            let mut temp_file = File::create(&temp_path)?;
+            temp_file.write_all(&self.mmap)?;
+            temp_file.sync_all()?;
+        }
+        std::fs::rename(&temp_path, path)
+    }
+
+    /// Overwrites the array with the contents of a snapshot file.
+    /// 
+    /// # Arguments
+    /// * `path` - Path of a file written by `snapshot`
+    /// 
+    /// # Errors
+    /// - Returns `InvalidData` error if the snapshot length differs from the array file;
+    ///   the array is left unchanged in that case
+    pub fn restore(&mut self, path: &str) -> io::Result<()> {
+        let mut snapshot = File::open(path)?;
+        let snapshot_len = snapshot.metadata()?.len();
+        if snapshot_len != self.mmap.len() as u64 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("Snapshot has {} bytes, expected {}", snapshot_len, self.mmap.len()),
+            ));
+        }
+
+        snapshot.read_exact(&mut self.mmap)
+    }
+
+    /// Writes the array to a self-describing restart file protected by a checksum.
+    /// 
+    /// # Arguments
+    /// * `path` - Path of the restart file, replaced if it exists
+    /// 
+    /// # Note
+    /// Unlike `snapshot`, the file carries a magic, the value count and an xxHash checksum of the
+    /// values, so `import_checksummed` rejects truncated or corrupted files. It is written to
+    /// `<path>.tmp` and renamed, as in `snapshot`.
+    pub fn export_checksummed(&self, path: &str) -> io::Result<()> {
+        let temp_path = format!("{}.tmp", path);
+        {
+            let mut temp_file = io::BufWriter::new(File::create(&temp_path)?);
+            temp_file.write_all(&CHECKSUMMED_MAGIC)?;
+            temp_file.write_all(&(self.len() as u64).to_ne_bytes())?;
+            temp_file.write_all(&calculate_checksum(&self.mmap).to_ne_bytes())?;
+            temp_file.write_all(&self.mmap)?;
+            temp_file.into_inner().map_err(|e| e.into_error())?.sync_all()?;
+        }
+        std::fs::rename(&temp_path, path)
+    }
+
+    /// Reads and verifies a file written by `export_checksummed`.
+    /// 
+    /// # Arguments
+    /// * `path` - Path of the restart file
+    /// 
+    /// # Returns
+    /// * `std::io::Result<Vec<f64>>` - The exported values
+    /// 
+    /// # Errors
+    /// - Returns `InvalidData` error if the magic is wrong, the file length does not match the
+    ///   stored value count, or the checksum does not match the values
+    pub fn import_checksummed(path: &str) -> io::Result<Vec<f64>> {
+        let bytes = std::fs::read(path)?;
+        let invalid = |message: String| io::Error::new(io::ErrorKind::InvalidData, message);
+
+        if bytes.len() < CHECKSUMMED_HEADER_SIZE || bytes[..8] != CHECKSUMMED_MAGIC {
+            return Err(invalid(format!("{} is not a checksummed array file", path)));
+        }
+        let count = u64::from_ne_bytes(bytes[8..16].try_into().unwrap());
+        let checksum = u128::from_ne_bytes(bytes[16..CHECKSUMMED_HEADER_SIZE].try_into().unwrap());
+
+        let data = &bytes[CHECKSUMMED_HEADER_SIZE..];
+        if count.checked_mul(F64_SIZE as u64) != Some(data.len() as u64) {
+            return Err(invalid(format!("Expected {} values, found {} bytes of data", count, data.len())));
+        }
+        if calculate_checksum(data) != checksum {
+            return Err(invalid(format!("Checksum mismatch in {}", path)));
+        }
+
+        Ok(data
+            .chunks_exact(F64_SIZE)
+            .map(|chunk| f64::from_ne_bytes(chunk.try_into().unwrap()))
+            .collect())
+    }
+
+    /// Changes the length of the array, e.g. after adaptive refinement added degrees of freedom.
+    /// 
+    /// # Arguments
+    /// * `new_length` - New number of f64 values
+    /// 
+    /// # Behavior
+    /// - Flushes pending writes and releases the current mapping before changing the file size
+    /// - Values below `min(old length, new length)` are preserved, new values are 0.0
+    /// - The file is remapped at its new size
+    /// 
+    /// # Note
+    /// The mapping moves to a new address, so anything derived from the old mapping (slices,
+    /// raw pointers) is invalidated; `&mut self` keeps safe borrows from outliving the call.
+    /// If `set_len` fails, the file is remapped at its current size and the error is returned.
+    pub fn resize(&mut self, new_length: usize) -> io::Result<()> {
+        self.flush()?;
+        // Release the file mapping: some platforms refuse to change the size of a mapped file
+        self.mmap = MmapMut::map_anon(0)?;
+        let resized = self.file.set_len((new_length * F64_SIZE) as u64);
+
+        // SAFETY: The mapping covers the file as it is now, and all accesses are bounds checked
+        self.mmap = unsafe { MmapMut::map_mut(&self.file)? };
+        resized
+    }
+
     /// Returns the length of the array.
     pub fn len(&self) -> usize {
-        ARRAY_LENGTH
+        self.mmap.len() / F64_SIZE
     }
 
-    /// Checks if the array is empty (always false for fixed-length array).
+    /// Checks if the array is empty (only after `resize(0)`).
     pub fn is_empty(&self) -> bool {
-        ARRAY_LENGTH == 0
+        self.len() == 0
+    }
+
+    fn check_index(&self, index: usize) -> io::Result<()> {
+        if index >= self.len() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("Index {} out of bounds (length {})", index, self.len()),
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl Drop for ArrayUpdater {
+    fn drop(&mut self) {
+        if self.flush_on_drop && let Err(e) = self.mmap.flush() {
+            eprintln!("ArrayUpdater: flush on drop failed: {}", e);
+        }
     }
 }
 
@@ -321,6 +558,14 @@ impl ThreadSafeArrayUpdater {
         guard.get_value(index)
     }
 
+    /// Reads the values at the specified indices (thread-safe).
+    pub fn gather(&self, indices: &[usize]) -> io::Result<Vec<f64>> {
+        let guard = self.inner.read().map_err(|_| {
+            io::Error::other("RwLock poisoned")
+        })?;
+        guard.gather(indices)
+    }
+
     /// Updates a value at the specified index (thread-safe).
     pub fn update_value(&self, index: usize, operation: impl Fn(f64) -> f64) -> io::Result<()> {
         let mut guard = self.inner.write().map_err(|_| {
@@ -345,16 +590,81 @@ impl ThreadSafeArrayUpdater {
         guard.flush()
     }
 
+    /// Sets every value of the array (thread-safe).
+    pub fn fill(&self, value: f64) -> io::Result<()> {
+        let mut guard = self.inner.write().map_err(|_| {
+            io::Error::other("RwLock poisoned")
+        })?;
+        guard.fill(value);
+        Ok(())
+    }
+
+    /// Sets every value of the array to 0.0 (thread-safe).
+    pub fn zero(&self) -> io::Result<()> {
+        self.fill(0.0)
+    }
+
+    /// Saves the current array contents to a snapshot file (thread-safe).
+    pub fn snapshot(&self, path: &str) -> io::Result<()> {
+        let guard = self.inner.read().map_err(|_| {
+            io::Error::other("RwLock poisoned")
+        })?;
+        guard.snapshot(path)
+    }
+
+    /// Writes the array to a checksummed restart file (thread-safe).
+    pub fn export_checksummed(&self, path: &str) -> io::Result<()> {
+        let guard = self.inner.read().map_err(|_| {
+            io::Error::other("RwLock poisoned")
+        })?;
+        guard.export_checksummed(path)
+    }
+
+    /// Overwrites the array with the contents of a snapshot file (thread-safe).
+    pub fn restore(&self, path: &str) -> io::Result<()> {
+        let mut guard = self.inner.write().map_err(|_| {
+            io::Error::other("RwLock poisoned")
+        })?;
+        guard.restore(path)
+    }
+
+    /// Changes the length of the array (thread-safe).
+    pub fn resize(&self, new_length: usize) -> io::Result<()> {
+        let mut guard = self.inner.write().map_err(|_| {
+            io::Error::other("RwLock poisoned")
+        })?;
+        guard.resize(new_length)
+    }
+
     /// Returns the length of the array (thread-safe).
-    pub fn len(&self) -> usize {
-        let guard = self.inner.read().unwrap(); // Should not panic in normal use
-        guard.len()
+    pub fn len(&self) -> io::Result<usize> {
+        let guard = self.inner.read().map_err(|_| {
+            io::Error::other("RwLock poisoned")
+        })?;
+        Ok(guard.len())
     }
 
     /// Checks if the array is empty (thread-safe).
-    pub fn is_empty(&self) -> bool {
-        let guard = self.inner.read().unwrap(); // Should not panic in normal use
-        guard.is_empty()
+    pub fn is_empty(&self) -> io::Result<bool> {
+        let guard = self.inner.read().map_err(|_| {
+            io::Error::other("RwLock poisoned")
+        })?;
+        Ok(guard.is_empty())
+    }
+
+    /// Returns true if a thread panicked while holding the write lock.
+    pub fn is_poisoned(&self) -> bool {
+        self.inner.is_poisoned()
+    }
+
+    /// Clears the poisoned state left by a thread that panicked while writing, so that the
+    /// other methods stop returning "RwLock poisoned" errors.
+    ///
+    /// The array is not rolled back: values written by the panicking thread before it panicked
+    /// stay in the mapped file, and a batch update may be only partially applied. Callers that
+    /// need a consistent state should `restore` a snapshot or recompute the affected values.
+    pub fn clear_poison(&self) {
+        self.inner.clear_poison();
     }
 }
 
@@ -460,6 +770,33 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_flush_on_drop() -> io::Result<()> {
+        let temp_file = NamedTempFile::new()?;
+        let file_path = temp_file.path().to_str().unwrap();
+
+        {
+            let mut updater = ArrayUpdater::new(file_path)?;
+            assert!(updater.flush_on_drop);
+            updater.update_value(7, |_| 2.75)?;
+            // Dropped without an explicit flush
+        }
+
+        // Read back through the file rather than a new mapping
+        let bytes = fs::read(file_path)?;
+        let value = f64::from_ne_bytes(bytes[7 * F64_SIZE..8 * F64_SIZE].try_into().unwrap());
+        assert_eq!(value, 2.75);
+
+        {
+            let mut updater = ArrayUpdater::new(file_path)?.with_flush_on_drop(false);
+            assert!(!updater.flush_on_drop);
+            assert_eq!(updater.get_value(7)?, 2.75);
+            updater.update_value(8, |_| 1.0)?;
+        }
+
+        Ok(())
+    }
+
     #[test]
     fn test_file_size_correctness() -> io::Result<()> {
         let temp_file = NamedTempFile::new()?;
@@ -508,6 +845,237 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_fill_and_zero() -> io::Result<()> {
+        let temp_file = NamedTempFile::new()?;
+        let file_path = temp_file.path().to_str().unwrap();
+        let indices = [0, 1, 12_345, ARRAY_LENGTH / 2, ARRAY_LENGTH - 1];
+
+        let mut updater = ArrayUpdater::new(file_path)?;
+        updater.fill(7.0);
+        for &k in &indices {
+            assert_eq!(updater.get_value(k)?, 7.0);
+        }
+        updater.zero();
+        for &k in &indices {
+            assert_eq!(updater.get_value(k)?, 0.0);
+        }
+        drop(updater);
+
+        let safe_updater = ThreadSafeArrayUpdater::new(file_path)?;
+        safe_updater.fill(-2.5)?;
+        assert_eq!(safe_updater.get_value(ARRAY_LENGTH - 1)?, -2.5);
+        safe_updater.zero()?;
+        assert_eq!(safe_updater.get_value(ARRAY_LENGTH - 1)?, 0.0);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_snapshot_and_restore() -> io::Result<()> {
+        let temp_file = NamedTempFile::new()?;
+        let file_path = temp_file.path().to_str().unwrap();
+        let snapshot_dir = tempfile::tempdir()?;
+        let snapshot_path = snapshot_dir.path().join("checkpoint.bin");
+        let snapshot_path = snapshot_path.to_str().unwrap();
+
+        let mut updater = ArrayUpdater::new(file_path)?;
+        updater.update_values(&[0, 10, ARRAY_LENGTH - 1], |_| 1.5)?;
+        updater.snapshot(snapshot_path)?;
+        assert_eq!(fs::metadata(snapshot_path)?.len(), fs::metadata(file_path)?.len());
+        assert!(!fs::exists(format!("{}.tmp", snapshot_path))?);
+
+        // Mutate after the snapshot, then roll back
+        updater.update_values(&[0, 10, 20], |x| x + 100.0)?;
+        updater.restore(snapshot_path)?;
+        assert_eq!(updater.get_value(0)?, 1.5);
+        assert_eq!(updater.get_value(10)?, 1.5);
+        assert_eq!(updater.get_value(20)?, 0.0);
+        assert_eq!(updater.get_value(ARRAY_LENGTH - 1)?, 1.5);
+
+        // A snapshot of the wrong length is rejected
+        fs::write(snapshot_path, [0u8; 16])?;
+        let result = updater.restore(snapshot_path);
+        assert_eq!(result.unwrap_err().kind(), io::ErrorKind::InvalidData);
+        assert_eq!(updater.get_value(0)?, 1.5);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_checksummed_export_and_import() -> io::Result<()> {
+        let temp_file = NamedTempFile::new()?;
+        let file_path = temp_file.path().to_str().unwrap();
+        let export_dir = tempfile::tempdir()?;
+        let export_path = export_dir.path().join("restart.bin");
+        let export_path = export_path.to_str().unwrap();
+
+        let mut updater = ArrayUpdater::new(file_path)?;
+        updater.resize(16)?;
+        updater.update_values(&[0, 7, 15], |_| -2.5)?;
+        updater.export_checksummed(export_path)?;
+        assert!(!fs::exists(format!("{}.tmp", export_path))?);
+
+        let values = ArrayUpdater::import_checksummed(export_path)?;
+        assert_eq!(values.len(), 16);
+        assert_eq!(updater.gather(&(0..16).collect::<Vec<_>>())?, values);
+
+        // One flipped bit in the data
+        let mut bytes = fs::read(export_path)?;
+        bytes[CHECKSUMMED_HEADER_SIZE + 7 * F64_SIZE] ^= 0x01;
+        fs::write(export_path, &bytes)?;
+        let error = ArrayUpdater::import_checksummed(export_path).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        assert!(error.to_string().contains("Checksum"));
+
+        // Truncated file, and a raw snapshot that has no header
+        bytes.truncate(bytes.len() - F64_SIZE);
+        fs::write(export_path, &bytes)?;
+        assert_eq!(ArrayUpdater::import_checksummed(export_path).unwrap_err().kind(), io::ErrorKind::InvalidData);
+        updater.snapshot(export_path)?;
+        assert_eq!(ArrayUpdater::import_checksummed(export_path).unwrap_err().kind(), io::ErrorKind::InvalidData);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_poison_recovery() -> io::Result<()> {
+        let temp_file = NamedTempFile::new()?;
+        let file_path = temp_file.path().to_str().unwrap();
+
+        let safe_updater = Arc::new(ThreadSafeArrayUpdater::new(file_path)?);
+        safe_updater.update_values(&[0, 1], |_| 1.0)?;
+
+        // A worker panics while holding the write lock
+        let worker = {
+            let updater = Arc::clone(&safe_updater);
+            thread::spawn(move || {
+                let _ = updater.update_value(0, |_| panic!("worker failed during assembly"));
+            })
+        };
+        assert!(worker.join().is_err());
+
+        assert!(safe_updater.is_poisoned());
+        assert!(safe_updater.len().is_err());
+        assert!(safe_updater.is_empty().is_err());
+        assert!(safe_updater.get_value(1).is_err());
+
+        // Another thread recovers and keeps working with the data left in place
+        let recovery = {
+            let updater = Arc::clone(&safe_updater);
+            thread::spawn(move || -> io::Result<f64> {
+                updater.clear_poison();
+                updater.update_value(1, |x| x + 1.0)?;
+                updater.get_value(0)
+            })
+        };
+        assert_eq!(recovery.join().unwrap()?, 1.0);
+
+        assert!(!safe_updater.is_poisoned());
+        assert_eq!(safe_updater.len()?, ARRAY_LENGTH);
+        assert_eq!(safe_updater.get_value(1)?, 2.0);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_gather() -> io::Result<()> {
+        let temp_file = NamedTempFile::new()?;
+        let file_path = temp_file.path().to_str().unwrap();
+
+        let mut updater = ArrayUpdater::new(file_path)?;
+        for index in [0, 5, 9] {
+            updater.update_value(index, |_| index as f64 * 10.0 + 1.0)?;
+        }
+
+        assert_eq!(updater.gather(&[9, 0, 5])?, vec![91.0, 1.0, 51.0]);
+        assert_eq!(updater.gather(&[5, 5, 2])?, vec![51.0, 51.0, 0.0]);
+        assert!(updater.gather(&[])?.is_empty());
+        let result = updater.gather(&[0, ARRAY_LENGTH]);
+        assert_eq!(result.unwrap_err().kind(), io::ErrorKind::InvalidInput);
+
+        updater.flush()?;
+        let safe_updater = ThreadSafeArrayUpdater::new(file_path)?;
+        assert_eq!(safe_updater.gather(&[9, 0, 5])?, vec![91.0, 1.0, 51.0]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_block_access() -> io::Result<()> {
+        let temp_file = NamedTempFile::new()?;
+        let file_path = temp_file.path().to_str().unwrap();
+
+        let mut updater = ArrayUpdater::new(file_path)?;
+        updater.fill(-1.0);
+        updater.set_block(10, &[1.0, 2.0, 3.0, 4.0, 5.0])?;
+
+        assert_eq!(updater.get_block(10, 5)?, &[1.0, 2.0, 3.0, 4.0, 5.0]);
+        assert_eq!(updater.get_block(9, 7)?, &[-1.0, 1.0, 2.0, 3.0, 4.0, 5.0, -1.0]);
+        assert_eq!(updater.get_value(9)?, -1.0);
+        assert_eq!(updater.get_value(15)?, -1.0);
+        assert!(updater.get_block(3, 0)?.is_empty());
+
+        // Blocks ending exactly at the end are allowed, past the end or overflowing are not
+        updater.set_block(ARRAY_LENGTH - 2, &[7.0, 8.0])?;
+        assert_eq!(updater.get_block(ARRAY_LENGTH - 2, 2)?, &[7.0, 8.0]);
+        let result = updater.set_block(ARRAY_LENGTH - 1, &[0.0, 0.0]);
+        assert_eq!(result.unwrap_err().kind(), io::ErrorKind::InvalidInput);
+        assert_eq!(updater.get_value(ARRAY_LENGTH - 1)?, 8.0);
+        assert_eq!(updater.get_block(usize::MAX, 2).unwrap_err().kind(), io::ErrorKind::InvalidInput);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_resize() -> io::Result<()> {
+        let temp_file = NamedTempFile::new()?;
+        let file_path = temp_file.path().to_str().unwrap();
+
+        let mut updater = ArrayUpdater::new(file_path)?;
+        updater.resize(10)?;
+        assert_eq!(updater.len(), 10);
+        let indices: Vec<usize> = (0..10).collect();
+        for &index in &indices {
+            updater.update_value(index, |_| index as f64 + 1.0)?;
+        }
+
+        // Growing keeps the old values and zero-fills the new ones
+        updater.resize(20)?;
+        assert_eq!(updater.len(), 20);
+        assert_eq!(fs::metadata(file_path)?.len(), (20 * F64_SIZE) as u64);
+        for index in 0..10 {
+            assert_eq!(updater.get_value(index)?, index as f64 + 1.0);
+        }
+        for index in 10..20 {
+            assert_eq!(updater.get_value(index)?, 0.0);
+        }
+        updater.update_value(19, |x| x + 5.0)?;
+
+        // Shrinking truncates
+        updater.resize(4)?;
+        assert_eq!(updater.len(), 4);
+        assert_eq!(fs::metadata(file_path)?.len(), (4 * F64_SIZE) as u64);
+        assert_eq!(updater.get_value(3)?, 4.0);
+        assert_eq!(updater.get_value(4).unwrap_err().kind(), io::ErrorKind::InvalidInput);
+        drop(updater);
+
+        // Reopening keeps the resized length and contents
+        let reopened = ArrayUpdater::new(file_path)?;
+        assert_eq!(reopened.len(), 4);
+        assert_eq!(fs::metadata(file_path)?.len(), (4 * F64_SIZE) as u64);
+        assert_eq!(reopened.get_block(0, 4)?, &[1.0, 2.0, 3.0, 4.0]);
+        drop(reopened);
+
+        let safe_updater = ThreadSafeArrayUpdater::new(file_path)?;
+        assert_eq!(safe_updater.len()?, 4);
+        safe_updater.resize(6)?;
+        assert_eq!(safe_updater.len()?, 6);
+        assert_eq!(safe_updater.get_value(5)?, 0.0);
+
+        Ok(())
+    }
+
     #[test]
     fn test_array_length() -> io::Result<()> {
         let temp_file = NamedTempFile::new()?;
@@ -517,9 +1085,9 @@ mod tests {
         let safe_updater = ThreadSafeArrayUpdater::new(file_path)?;
         
         assert_eq!(updater.len(), ARRAY_LENGTH);
-        assert_eq!(safe_updater.len(), ARRAY_LENGTH);
+        assert_eq!(safe_updater.len()?, ARRAY_LENGTH);
         assert!(!updater.is_empty());
-        assert!(!safe_updater.is_empty());
+        assert!(!safe_updater.is_empty()?);
         
         Ok(())
     }
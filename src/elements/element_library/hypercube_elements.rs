@@ -14,8 +14,19 @@
 //!     type Coordinates;
 //!     const DIMENSION: u8;
 //!     const NUMBER_OF_NODES: u8;
+//!     const ORDERS: &'static [u8];
+//!     const SERENDIPITY: bool = false;
+//!     const SIMPLEX: bool = false;
+//!     const SUPPORTED_ORDERS: &'static [u8] = &[1, 2];
 //!     fn evaluate_shape_functions(coords: &Self::Coordinates) -> Vec<f64>;
 //!     fn evaluate_jacobian_of_shape_functions(coords: &Self::Coordinates) -> DMatrix<f64>;
+//!     fn try_evaluate_shape_functions(coords: &Self::Coordinates) -> Result<Vec<f64>, ShapeFunctionError>;
+//!     fn try_evaluate_jacobian_of_shape_functions(coords: &Self::Coordinates) -> Result<DMatrix<f64>, ShapeFunctionError>;
+//!     fn center() -> Self::Coordinates;
+//!     fn reference_domain() -> ReferenceDomain;
+//!     fn reference_node_coordinates() -> Vec<Vec<f64>>;
+//!     fn evaluate_all<const LEN: usize>(points: &[Self::Coordinates; LEN]) -> [Vec<f64>; LEN];
+//!     fn evaluate_jacobian_all<const LEN: usize>(points: &[Self::Coordinates; LEN]) -> [DMatrix<f64>; LEN];
 //! }
 //! ```
 //!
@@ -38,6 +49,19 @@
 //!     - `CubeOrder2ShapeFunctions`: Triquadratic hexahedron (27 nodes)
 //! - `CubeSerendipityShapeFunctions`: 20-node serendipity element (quadratic with no internal nodes)
 //!
+//! ## Reference Domain
+//! - `ReferenceDomain`: interval spanned by the reference coordinates, [0, 1] for every element
+//!   here. `NodalBasedShapeFunctions::reference_domain` lets other families declare [-1, 1].
+//!
+//! ## Node Ordering
+//! - `tensor_product_node_order(orders)`: reference coordinates of the nodes of a tensor-product
+//!   element, in the order of its shape functions (x varies fastest, then y, then z)
+//!
+//! ## Runtime Selection
+//! - `ShapeFunctionKind`: enum over the element types above, for code that only knows the element
+//!   type at runtime. Also provides the reference node coordinates and the local boundary facets.
+//! - `interpolate(kind, nodal_values, coords)`: value of a nodal field at a parametric point
+//!
 //! # Examples
 //!
 //! ```
@@ -57,36 +81,121 @@ pub trait NodalBasedShapeFunctions {
     type Coordinates;
     const DIMENSION: u8;
     const NUMBER_OF_NODES: u8;
+    /// Polynomial order along each reference axis (the total degree on every axis for simplices)
+    const ORDERS: &'static [u8];
+    /// Serendipity elements only keep part of the tensor-product nodes of `ORDERS`
+    const SERENDIPITY: bool = false;
+    /// Simplices live on the reference triangle or tetrahedron instead of ```[0,1]^DIM```
+    const SIMPLEX: bool = false;
+    /// Orders along an axis that the implementation can evaluate
+    const SUPPORTED_ORDERS: &'static [u8] = &[1, 2];
+    /// Panics for orders outside `SUPPORTED_ORDERS`; see `try_evaluate_shape_functions`
     fn evaluate_shape_functions(coords: &Self::Coordinates) -> Vec<f64>;
+    /// Panics for orders outside `SUPPORTED_ORDERS`; see `try_evaluate_jacobian_of_shape_functions`
     fn evaluate_jacobian_of_shape_functions(coords: &Self::Coordinates) -> Array2<f64>;
+    /// `evaluate_shape_functions`, returning an error instead of panicking for an unsupported order
+    fn try_evaluate_shape_functions(coords: &Self::Coordinates) -> Result<Vec<f64>, ShapeFunctionError> {
+        check_supported_orders(Self::ORDERS, Self::SUPPORTED_ORDERS)?;
+        Ok(Self::evaluate_shape_functions(coords))
+    }
+    /// `evaluate_jacobian_of_shape_functions`, returning an error instead of panicking for an
+    /// unsupported order
+    fn try_evaluate_jacobian_of_shape_functions(coords: &Self::Coordinates) -> Result<Array2<f64>, ShapeFunctionError> {
+        check_supported_orders(Self::ORDERS, Self::SUPPORTED_ORDERS)?;
+        Ok(Self::evaluate_jacobian_of_shape_functions(coords))
+    }
+    /// Centroid of the reference element. The shape functions evaluated there map it to the
+    /// centroid of the nodes for undistorted elements, which is where quality metrics and
+    /// one-point rules evaluate the element.
+    fn center() -> Self::Coordinates;
+    /// Interval spanned by every reference coordinate. Quadrature rules of this crate are on
+    /// [0, 1]; shape functions on another domain need `compute_position_jacobian_scaled`.
+    fn reference_domain() -> ReferenceDomain {
+        ReferenceDomain::ZeroToOne
+    }
+    /// Reference coordinates of every node, in the order of the shape functions. The default
+    /// is `tensor_product_node_order(Self::ORDERS)`; elements that are not full tensor products
+    /// override it.
+    fn reference_node_coordinates() -> Vec<Vec<f64>> {
+        tensor_product_node_order(Self::ORDERS)
+    }
+    /// Shape functions at every point of a rule; implementations may share work between points
+    fn evaluate_all<const LEN: usize>(points: &[Self::Coordinates; LEN]) -> [Vec<f64>; LEN] {
+        std::array::from_fn(|q| Self::evaluate_shape_functions(&points[q]))
+    }
+    /// Jacobians of the shape functions at every point of a rule
+    fn evaluate_jacobian_all<const LEN: usize>(points: &[Self::Coordinates; LEN]) -> [Array2<f64>; LEN] {
+        std::array::from_fn(|q| Self::evaluate_jacobian_of_shape_functions(&points[q]))
+    }
     fn node_ids(&self) -> &[u32] {
         &[]
     }
 }
 
+/// Interval spanned by each reference coordinate of an element or quadrature rule
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ReferenceDomain {
+    /// [0, 1], the convention of the elements and quadrature rules of this crate
+    #[default]
+    ZeroToOne,
+    /// [-1, 1], as used by many other element families
+    MinusOneToOne,
+}
+
+impl ReferenceDomain {
+    pub fn bounds(&self) -> (f64, f64) {
+        match self {
+            ReferenceDomain::ZeroToOne => (0.0, 1.0),
+            ReferenceDomain::MinusOneToOne => (-1.0, 1.0),
+        }
+    }
+
+    pub fn length(&self) -> f64 {
+        let (lower, upper) = self.bounds();
+        upper - lower
+    }
+
+    /// Coordinate of this domain matching `coordinate` of `from` under the affine map between them
+    pub fn map_from(&self, from: ReferenceDomain, coordinate: f64) -> f64 {
+        let (lower, _) = self.bounds();
+        let (from_lower, _) = from.bounds();
+        lower + (coordinate - from_lower) * self.scale_from(from)
+    }
+
+    /// Derivative of a coordinate of this domain with respect to the matching coordinate of `from`
+    pub fn scale_from(&self, from: ReferenceDomain) -> f64 {
+        self.length() / from.length()
+    }
+}
+
 // 1D Line elements
-struct LineShapeFunctions<const ORDER: u8>;
+pub struct LineShapeFunctions<const ORDER: u8>;
 
 impl<const ORDER: u8> NodalBasedShapeFunctions for LineShapeFunctions<ORDER> {
-    type Coordinates = f64;
+    type Coordinates = [f64; 1];
     const DIMENSION: u8 = 1;
     const NUMBER_OF_NODES: u8 = ORDER + 1;
+    const ORDERS: &'static [u8] = &[ORDER];
 
-    fn evaluate_shape_functions(x: &f64) -> Vec<f64> {
+    fn evaluate_shape_functions(coords: &[f64; 1]) -> Vec<f64> {
         match ORDER {
-            1 => LineShapeFunctions::<1>::evaluate_shape_functions_impl(x),
-            2 => LineShapeFunctions::<2>::evaluate_shape_functions_impl(x),
+            1 => LineShapeFunctions::<1>::evaluate_shape_functions_impl(&coords[0]),
+            2 => LineShapeFunctions::<2>::evaluate_shape_functions_impl(&coords[0]),
             _ => panic!("Unsupported order for line shape functions"),
         }
     }
     
-    fn evaluate_jacobian_of_shape_functions(x: &f64) -> Array2<f64> {
+    fn evaluate_jacobian_of_shape_functions(coords: &[f64; 1]) -> Array2<f64> {
         match ORDER {
-            1 => LineShapeFunctions::<1>::evaluate_jacobian_impl(x),
-            2 => LineShapeFunctions::<2>::evaluate_jacobian_impl(x),
+            1 => LineShapeFunctions::<1>::evaluate_jacobian_impl(&coords[0]),
+            2 => LineShapeFunctions::<2>::evaluate_jacobian_impl(&coords[0]),
             _ => panic!("Unsupported order for line shape functions"),
         }
     }
+
+    fn center() -> [f64; 1] {
+        [0.5]
+    }
 }
 
 impl LineShapeFunctions<1> {
@@ -99,7 +208,7 @@ impl LineShapeFunctions<1> {
     }
     
     fn evaluate_jacobian_impl(_x: &f64) -> Array2<f64> {
-        Array2::from_shape_vec((1, 2), vec![-1.0, 1.0]).unwrap()
+        Array2::from_shape_vec((2, 1), vec![-1.0, 1.0]).unwrap()
     }
 }
 
@@ -120,7 +229,7 @@ impl LineShapeFunctions<2> {
     
     fn evaluate_jacobian_impl(x: &f64) -> Array2<f64> {
         let aux: f64 = 4.0 * x;
-        Array2::from_shape_vec((1, 3), vec![aux - 3.0, 4.0 - 2.0*aux, aux - 1.0]).unwrap()
+        Array2::from_shape_vec((3, 1), vec![aux - 3.0, 4.0 - 2.0*aux, aux - 1.0]).unwrap()
     }
 }
 
@@ -131,13 +240,14 @@ impl<const ORDER_X: u8, const ORDER_Y: u8> NodalBasedShapeFunctions for SquareSh
     type Coordinates = [f64; 2];
     const DIMENSION: u8 = 2;
     const NUMBER_OF_NODES: u8 = (ORDER_X+1) * (ORDER_Y+1);
+    const ORDERS: &'static [u8] = &[ORDER_X, ORDER_Y];
     
     fn evaluate_shape_functions(coords: &[f64; 2]) -> Vec<f64> {
         let x: f64 = coords[0];
         let y: f64 = coords[1];
         
-        let line_functions_x = LineShapeFunctions::<ORDER_X>::evaluate_shape_functions(&x);
-        let line_functions_y = LineShapeFunctions::<ORDER_Y>::evaluate_shape_functions(&y);
+        let line_functions_x = LineShapeFunctions::<ORDER_X>::evaluate_shape_functions(&[x]);
+        let line_functions_y = LineShapeFunctions::<ORDER_Y>::evaluate_shape_functions(&[y]);
         
         // Outer product and flatten
         let mut result = vec![0.0; line_functions_x.len() * line_functions_y.len()];
@@ -153,11 +263,11 @@ impl<const ORDER_X: u8, const ORDER_Y: u8> NodalBasedShapeFunctions for SquareSh
         let x: f64 = coords[0];
         let y: f64 = coords[1];
         
-        let line_functions_x = LineShapeFunctions::<ORDER_X>::evaluate_shape_functions(&x);
-        let line_jacobian_x = LineShapeFunctions::<ORDER_X>::evaluate_jacobian_of_shape_functions(&x);
+        let line_functions_x = LineShapeFunctions::<ORDER_X>::evaluate_shape_functions(&[x]);
+        let line_jacobian_x = LineShapeFunctions::<ORDER_X>::evaluate_jacobian_of_shape_functions(&[x]);
         
-        let line_functions_y = LineShapeFunctions::<ORDER_Y>::evaluate_shape_functions(&y);
-        let line_jacobian_y = LineShapeFunctions::<ORDER_Y>::evaluate_jacobian_of_shape_functions(&y);
+        let line_functions_y = LineShapeFunctions::<ORDER_Y>::evaluate_shape_functions(&[y]);
+        let line_jacobian_y = LineShapeFunctions::<ORDER_Y>::evaluate_jacobian_of_shape_functions(&[y]);
         
         let n_nodes = line_functions_x.len() * line_functions_y.len();
         let mut jacobian = Array2::zeros((n_nodes, 2));
@@ -166,7 +276,7 @@ impl<const ORDER_X: u8, const ORDER_Y: u8> NodalBasedShapeFunctions for SquareSh
         for i in 0..line_functions_y.len() {
             for j in 0..line_functions_x.len() {
                 let idx = i * line_functions_x.len() + j;
-                jacobian[[idx, 0]] = line_functions_y[i] * line_jacobian_x[(0, j)];
+                jacobian[[idx, 0]] = line_functions_y[i] * line_jacobian_x[(j, 0)];
             }
         }
         
@@ -174,12 +284,16 @@ impl<const ORDER_X: u8, const ORDER_Y: u8> NodalBasedShapeFunctions for SquareSh
         for i in 0..line_functions_y.len() {
             for j in 0..line_functions_x.len() {
                 let idx = i * line_functions_x.len() + j;
-                jacobian[[idx, 1]] = line_jacobian_y[(0, i)] * line_functions_x[j];
+                jacobian[[idx, 1]] = line_jacobian_y[(i, 0)] * line_functions_x[j];
             }
         }
         
         jacobian
     }
+
+    fn center() -> [f64; 2] {
+        [0.5; 2]
+    }
 }
 
 // Type aliases for common cases
@@ -188,10 +302,10 @@ type SquareOrder1ShapeFunctions = SquareShapeFunctions<1, 1>;
     Number of nodes of a linear square element (4)
 
     x   y
-    1   0
     0   0
-    1   1
+    1   0
     0   1
+    1   1
 */
 
 type SquareOrder2ShapeFunctions = SquareShapeFunctions<2, 2>;
@@ -220,6 +334,7 @@ for CubeShapeFunctions<ORDER_X, ORDER_Y, ORDER_Z> {
     type Coordinates = [f64; 3];
     const DIMENSION: u8 = 3;
     const NUMBER_OF_NODES: u8 = (ORDER_X+1) * (ORDER_Y+1) * (ORDER_Z+1);
+    const ORDERS: &'static [u8] = &[ORDER_X, ORDER_Y, ORDER_Z];
     
     fn evaluate_shape_functions(coords: &[f64; 3]) -> Vec<f64> {
         let x = coords[0];
@@ -227,7 +342,7 @@ for CubeShapeFunctions<ORDER_X, ORDER_Y, ORDER_Z> {
         let z = coords[2];
         
         let square_functions = SquareShapeFunctions::<ORDER_X, ORDER_Y>::evaluate_shape_functions(&[x, y]);
-        let line_functions_z = LineShapeFunctions::<ORDER_Z>::evaluate_shape_functions(&z);
+        let line_functions_z = LineShapeFunctions::<ORDER_Z>::evaluate_shape_functions(&[z]);
         
         // Outer product and flatten
         let mut result = vec![0.0; square_functions.len() * line_functions_z.len()];
@@ -247,8 +362,8 @@ for CubeShapeFunctions<ORDER_X, ORDER_Y, ORDER_Z> {
         let square_functions = SquareShapeFunctions::<ORDER_X, ORDER_Y>::evaluate_shape_functions(&[x, y]);
         let square_jacobian = SquareShapeFunctions::<ORDER_X, ORDER_Y>::evaluate_jacobian_of_shape_functions(&[x, y]);
         
-        let line_functions_z = LineShapeFunctions::<ORDER_Z>::evaluate_shape_functions(&z);
-        let line_jacobian_z = LineShapeFunctions::<ORDER_Z>::evaluate_jacobian_of_shape_functions(&z);
+        let line_functions_z = LineShapeFunctions::<ORDER_Z>::evaluate_shape_functions(&[z]);
+        let line_jacobian_z = LineShapeFunctions::<ORDER_Z>::evaluate_jacobian_of_shape_functions(&[z]);
         
         let n_nodes = square_functions.len() * line_functions_z.len();
         let mut jacobian = Array2::zeros((n_nodes, 3));
@@ -273,12 +388,16 @@ for CubeShapeFunctions<ORDER_X, ORDER_Y, ORDER_Z> {
         for i in 0..line_functions_z.len() {
             for j in 0..square_functions.len() {
                 let idx = i * square_functions.len() + j;
-                jacobian[[idx, 2]] = line_jacobian_z[(0, i)] * square_functions[j];
+                jacobian[[idx, 2]] = line_jacobian_z[(i, 0)] * square_functions[j];
             }
         }
         
         jacobian
     }
+
+    fn center() -> [f64; 3] {
+        [0.5; 3]
+    }
 }
 
 // Type aliases for common cases
@@ -335,237 +454,558 @@ type CubeOrder2ShapeFunctions = CubeShapeFunctions<2, 2, 2>;
 
 struct CubeSerendipityShapeFunctions;
 
+// (L0, B, L1) along one axis: the linear functions of the end nodes and the quadratic bubble
+// of the mid node, or their derivatives
+type LineValues = (f64, f64, f64);
+
 impl CubeSerendipityShapeFunctions {
-    // Computes the shape functions for a quadratic line segment
-    // Returns tuple of (N0, N05, N1) corresponding to nodes at 0, 0.5, and 1
-    fn line_shape_functions(t: f64) -> (f64, f64, f64) {
-        let a1: f64 = 1.0-t;      // t=0 -> 1 ,  t=0.5 -> 0.5 ,  t=1 -> 0
-        let a2: f64 = 1.0-2.0*t;  // t=0 -> 1 ,  t=0.5 -> 0 ,    t=1 -> -1
-        let n00: f64 = a1*a2;     // t=0 -> 1 ,  t=0.5 -> 0 ,    t=1 -> 0
-        let n05: f64 = 4.0*t*a1;  // t=0 -> 0 ,  t=0.5 -> 1 ,    t=1 -> 0
-        let n10: f64 = -t*a2;     // t=0 -> 0 ,  t=0.5 -> 0 ,    t=1 -> -1
-
-        (n00, n05, n10)
-    }
-
-    // Computes derivatives of the shape functions for a quadratic line segment
-    // Returns tuple of (dN0/dt, dN05/dt, dN1/dt)
-    fn jacobian_of_line_shape_functions(t: f64) -> (f64, f64, f64) {
-        let aux: f64 = 4.0 * t;
-        
-        (
-            aux - 3.0, 
-            4.0 - 2.0 * aux, 
-            aux - 1.0
-        )
-    }     
+    /*
+    Index of the line function along x, y and z of every node: 0 for L0, 1 for B, 2 for L1.
+    Corner nodes: N = L_x L_y L_z (2 (L_x + L_y + L_z) - 5)
+    Mid-edge nodes: N = B_x L_y L_z, and likewise along y and z
+    */
+    const NODES: [[usize; 3]; 20] = [
+        [0, 0, 0], [1, 0, 0], [2, 0, 0], [0, 1, 0], [2, 1, 0], [0, 2, 0], [1, 2, 0], [2, 2, 0],
+        [0, 0, 1], [2, 0, 1], [0, 2, 1], [2, 2, 1],
+        [0, 0, 2], [1, 0, 2], [2, 0, 2], [0, 1, 2], [2, 1, 2], [0, 2, 2], [1, 2, 2], [2, 2, 2],
+    ];
+
+    // Line functions (L0, B, L1) at t, corresponding to nodes at 0, 0.5 and 1
+    fn line_shape_functions(t: f64) -> LineValues {
+        let l0: f64 = 1.0 - t;      // t=0 -> 1 ,  t=0.5 -> 0.5 ,  t=1 -> 0
+        let b: f64 = 4.0 * t * l0;  // t=0 -> 0 ,  t=0.5 -> 1 ,    t=1 -> 0
+
+        (l0, b, t)
+    }
+
+    // Derivatives (dL0/dt, dB/dt, dL1/dt)
+    fn jacobian_of_line_shape_functions(t: f64) -> LineValues {
+        (-1.0, 4.0 - 8.0 * t, 1.0)
+    }
+
+    // Applies `line` to every coordinate of every point, once per distinct value along each axis
+    fn lines_at_points<const LEN: usize>(
+        points: &[[f64; 3]; LEN],
+        line: fn(f64) -> LineValues,
+    ) -> [[LineValues; 3]; LEN] {
+        let mut cache: [Vec<(f64, LineValues)>; 3] = Default::default();
+        points.map(|point| {
+            std::array::from_fn(|axis| {
+                let t: f64 = point[axis];
+                match cache[axis].iter().find(|(cached_t, _)| *cached_t == t) {
+                    Some(&(_, values)) => values,
+                    None => {
+                        let values = line(t);
+                        cache[axis].push((t, values));
+                        values
+                    }
+                }
+            })
+        })
+    }
+
+    // Values of the line functions of every node along x, y and z
+    fn node_values(lines: [LineValues; 3], node: &[usize; 3]) -> [f64; 3] {
+        std::array::from_fn(|axis| {
+            let (l0, b, l1) = lines[axis];
+            [l0, b, l1][node[axis]]
+        })
+    }
+
+    // Shape functions from the line functions along x, y and z
+    fn shape_functions_from_lines(lines: [LineValues; 3]) -> Vec<f64> {
+        Self::NODES
+            .iter()
+            .map(|node| {
+                let v: [f64; 3] = Self::node_values(lines, node);
+                let product: f64 = v[0] * v[1] * v[2];
+                if node.contains(&1) {
+                    product
+                } else {
+                    product * (2.0 * (v[0] + v[1] + v[2]) - 5.0)
+                }
+            })
+            .collect()
+    }
+
+    // Jacobian of the shape functions from the line functions along x, y and z and their derivatives
+    fn jacobian_from_lines(lines: [LineValues; 3], derivatives: [LineValues; 3]) -> Array2<f64> {
+        let mut jacobian = Array2::zeros((20, 3));
+
+        for (i, node) in Self::NODES.iter().enumerate() {
+            let v: [f64; 3] = Self::node_values(lines, node);
+            let dv: [f64; 3] = Self::node_values(derivatives, node);
+
+            for axis in 0..3 {
+                let others: f64 = v[(axis + 1) % 3] * v[(axis + 2) % 3];
+                jacobian[(i, axis)] = if node.contains(&1) {
+                    dv[axis] * others
+                } else {
+                    let corner_factor: f64 = 2.0 * (v[0] + v[1] + v[2]) - 5.0;
+                    dv[axis] * others * corner_factor + 2.0 * dv[axis] * v[0] * v[1] * v[2]
+                };
+            }
+        }
+
+        jacobian
+    }
 }
 
 impl NodalBasedShapeFunctions for CubeSerendipityShapeFunctions {
     type Coordinates = [f64; 3];
     const DIMENSION: u8 = 3;
     const NUMBER_OF_NODES: u8 = 20;
+    const ORDERS: &'static [u8] = &[2, 2, 2];
+    const SERENDIPITY: bool = true;
 
     fn evaluate_shape_functions(coords: &[f64; 3]) -> Vec<f64> {
-        let x: f64 = coords[0];
-        let y: f64 = coords[1];
-        let z: f64 = coords[2];
-
-        let mut result = vec![0.0; 20];
-        
-        let (x00, x05, x10) = Self::line_shape_functions(x);
-        let (y00, y05, y10) = Self::line_shape_functions(y);
-        let (z00, z05, z10) = Self::line_shape_functions(z);
-
-        let y00z00: f64 = y00 * z00;
-        let y05z00: f64 = y05 * z00;
-        let y10z00: f64 = y10 * z00;
-
-        // Bottom face (z=0)
-        result[0] = x00 * y00z00;  // Node 0: (0, 0, 0)
-        result[1] = x05 * y00z00;  // Node 1: (0.5, 0, 0)
-        result[2] = x10 * y00z00;  // Node 2: (1, 0, 0)
-        result[3] = x00 * y05z00;  // Node 3: (0, 0.5, 0)
-        result[4] = x10 * y05z00;  // Node 4: (1, 0.5, 0)
-        result[5] = x00 * y10z00;  // Node 5: (0, 1, 0)
-        result[6] = x05 * y10z00;  // Node 6: (0.5, 1, 0)
-        result[7] = x10 * y10z00;  // Node 7: (1, 1, 0)
-        
-        let y00z05: f64 = y00 * z05;
-        let y10z05: f64 = y10 * z05;
-
-        // Mid-edge nodes (z=0.5)
-        result[8] = x00 * y00z05;   // Node 8: (0, 0, 0.5)
-        result[9] = x10 * y00z05;   // Node 9: (1, 0, 0.5)
-        result[10] = x00 * y10z05;  // Node 10: (0, 1, 0.5)
-        result[11] = x10 * y10z05;  // Node 11: (1, 1, 0.5)
-        
-        let y00z10: f64 = y00 * z10;
-        let y05z10: f64 = y05 * z10;
-        let y10z10: f64 = y10 * z10;
-
-        // Top face (z=1)
-        result[12] = x00 * y00z10;  // Node 12: (0, 0, 1)
-        result[13] = x05 * y00z10;  // Node 13: (0.5, 0, 1)
-        result[14] = x10 * y00z10;  // Node 14: (1, 0, 1)
-        result[15] = x00 * y05z10;  // Node 15: (0, 0.5, 1)
-        result[16] = x10 * y05z10;  // Node 16: (1, 0.5, 1)
-        result[17] = x00 * y10z10;  // Node 17: (0, 1, 1)
-        result[18] = x05 * y10z10;  // Node 18: (0.5, 1, 1)
-        result[19] = x10 * y10z10;  // Node 19: (1, 1, 1)
-        
-        result
+        Self::shape_functions_from_lines(coords.map(Self::line_shape_functions))
     }
 
     fn evaluate_jacobian_of_shape_functions(coords: &[f64; 3]) -> Array2<f64> {
-        let x: f64 = coords[0];
-        let y: f64 = coords[1];
-        let z: f64 = coords[2];
+        Self::jacobian_from_lines(coords.map(Self::line_shape_functions), coords.map(Self::jacobian_of_line_shape_functions))
+    }
 
-        let mut jacobian = Array2::zeros((20, 3));
-    
-        let (x00, x05, x10) = Self::line_shape_functions(x);
-        let (y00, y05, y10) = Self::line_shape_functions(y);
-        let (z00, z05, z10) = Self::line_shape_functions(z);
-
-        let (dx00, dx05, dx10) = Self::jacobian_of_line_shape_functions(x);
-        let (dy00, dy05, dy10) = Self::jacobian_of_line_shape_functions(y);
-        let (dz00, dz05, dz10) = Self::jacobian_of_line_shape_functions(z);
-
-        let y00z00: f64 = y00 * z00;
-        let y05z00: f64 = y05 * z00;
-        let y10z00: f64 = y10 * z00;
-
-        let dy00z00: f64 = dy00 * z00;
-        let dy05z00: f64 = dy05 * z00;
-        let dy10z00: f64 = dy10 * z00;
-
-        let y00dz00: f64 = y00 * dz00;
-        let y05dz00: f64 = y05 * dz00;
-        let y10dz00: f64 = y10 * dz00;
-
-        // Node 0: (0, 0, 0)
-        jacobian[(0, 0)] = dx00 * y00z00;
-        jacobian[(0, 1)] = x00 * dy00z00;
-        jacobian[(0, 2)] = x00 * y00dz00;
-
-        // Node 1: (0.5, 0, 0)
-        jacobian[(1, 0)] = dx05 * y00z00;
-        jacobian[(1, 1)] = x05 * dy00z00;
-        jacobian[(1, 2)] = x05 * y00dz00;
-
-        // Node 2: (1, 0, 0)
-        jacobian[(2, 0)] = dx10 * y00z00;
-        jacobian[(2, 1)] = x10 * dy00z00;
-        jacobian[(2, 2)] = x10 * y00dz00;
-
-        // Node 3: (0, 0.5, 0)
-        jacobian[(3, 0)] = dx00 * y05z00;
-        jacobian[(3, 1)] = x00 * dy05z00;
-        jacobian[(3, 2)] = x00 * y05dz00;
-
-        // Node 4: (1, 0.5, 0)
-        jacobian[(4, 0)] = dx10 * y05z00;
-        jacobian[(4, 1)] = x10 * dy05z00;
-        jacobian[(4, 2)] = x10 * y05dz00;
-
-        // Node 5: (0, 1, 0)
-        jacobian[(5, 0)] = dx00 * y10z00;
-        jacobian[(5, 1)] = x00 * dy10z00;
-        jacobian[(5, 2)] = x00 * y10dz00;
-
-        // Node 6: (0.5, 1, 0)
-        jacobian[(6, 0)] = dx05 * y10z00;
-        jacobian[(6, 1)] = x05 * dy10z00;
-        jacobian[(6, 2)] = x05 * y10dz00;
-
-        // Node 7: (1, 1, 0)
-        jacobian[(7, 0)] = dx10 * y10z00;
-        jacobian[(7, 1)] = x10 * dy10z00;
-        jacobian[(7, 2)] = x10 * y10dz00;
-
-        let y00z05: f64 = y00 * z05;
-        let y10z05: f64 = y10 * z05;
-
-        let dy00z05: f64 = dy00 * z05;
-        let dy10z05: f64 = dy10 * z05;
-
-        let y00dz05: f64 = y00 * dz05;
-        let y10dz05: f64 = y10 * dz05;
-
-        // Node 8: (0, 0, 0.5)
-        jacobian[(8, 0)] = dx00 * y00z05;
-        jacobian[(8, 1)] = x00 * dy00z05;
-        jacobian[(8, 2)] = x00 * y00dz05;
-
-        // Node 9: (1, 0, 0.5)
-        jacobian[(9, 0)] = dx10 * y00z05;
-        jacobian[(9, 1)] = x10 * dy00z05;
-        jacobian[(9, 2)] = x10 * y00dz05;
-
-        // Node 10: (0, 1, 0.5)
-        jacobian[(10, 0)] = dx00 * y10z05;
-        jacobian[(10, 1)] = x00 * dy10z05;
-        jacobian[(10, 2)] = x00 * y10dz05;
-
-        // Node 11: (1, 1, 0.5)
-        jacobian[(11, 0)] = dx10 * y10z05;
-        jacobian[(11, 1)] = x10 * dy10z05;
-        jacobian[(11, 2)] = x10 * y10dz05;
-
-        let y00z10: f64 = y00 * z10;
-        let y05z10: f64 = y05 * z10;
-        let y10z10: f64 = y10 * z10;
-
-        let dy00z10: f64 = dy00 * z10;
-        let dy05z10: f64 = dy05 * z10;
-        let dy10z10: f64 = dy10 * z10;
-
-        let y00dz10: f64 = y00 * dz10;
-        let y05dz10: f64 = y05 * dz10;
-        let y10dz10: f64 = y10 * dz10;
-
-        // Node 12: (0, 0, 1)
-        jacobian[(12, 0)] = dx00 * y00z10;
-        jacobian[(12, 1)] = x00 * dy00z10;
-        jacobian[(12, 2)] = x00 * y00dz10;
-
-        // Node 13: (0.5, 0, 1)
-        jacobian[(13, 0)] = dx05 * y00z10;
-        jacobian[(13, 1)] = x05 * dy00z10;
-        jacobian[(13, 2)] = x05 * y00dz10;
-
-        // Node 14: (1, 0, 1)
-        jacobian[(14, 0)] = dx10 * y00z10;
-        jacobian[(14, 1)] = x10 * dy00z10;
-        jacobian[(14, 2)] = x10 * y00dz10;
-
-        // Node 15: (0, 0.5, 1)
-        jacobian[(15, 0)] = dx00 * y05z10;
-        jacobian[(15, 1)] = x00 * dy05z10;
-        jacobian[(15, 2)] = x00 * y05dz10;
-
-        // Node 16: (1, 0.5, 1)
-        jacobian[(16, 0)] = dx10 * y05z10;
-        jacobian[(16, 1)] = x10 * dy05z10;
-        jacobian[(16, 2)] = x10 * y05dz10;
-
-        // Node 17: (0, 1, 1)
-        jacobian[(17, 0)] = dx00 * y10z10;
-        jacobian[(17, 1)] = x00 * dy10z10;
-        jacobian[(17, 2)] = x00 * y10dz10;
-
-        // Node 18: (0.5, 1, 1)
-        jacobian[(18, 0)] = dx05 * y10z10;
-        jacobian[(18, 1)] = x05 * dy10z10;
-        jacobian[(18, 2)] = x05 * y10dz10;
-
-        // Node 19: (1, 1, 1)
-        jacobian[(19, 0)] = dx10 * y10z10;
-        jacobian[(19, 1)] = x10 * dy10z10;
-        jacobian[(19, 2)] = x10 * y10dz10;
+    fn center() -> [f64; 3] {
+        [0.5; 3]
+    }
 
-        jacobian
+    // Triquadratic nodes without face and body centres
+    fn reference_node_coordinates() -> Vec<Vec<f64>> {
+        tensor_product_node_order(Self::ORDERS)
+            .into_iter()
+            .filter(|node| node.iter().filter(|&&x| x == 0.5).count() <= 1)
+            .collect()
+    }
+
+    // The line functions only depend on one coordinate, which tensor-product rules share between points
+    fn evaluate_all<const LEN: usize>(points: &[[f64; 3]; LEN]) -> [Vec<f64>; LEN] {
+        Self::lines_at_points(points, Self::line_shape_functions).map(Self::shape_functions_from_lines)
+    }
+
+    fn evaluate_jacobian_all<const LEN: usize>(points: &[[f64; 3]; LEN]) -> [Array2<f64>; LEN] {
+        let lines = Self::lines_at_points(points, Self::line_shape_functions);
+        let derivatives = Self::lines_at_points(points, Self::jacobian_of_line_shape_functions);
+        std::array::from_fn(|q| Self::jacobian_from_lines(lines[q], derivatives[q]))
     }
 
-}
\ No newline at end of file
+}
+
+/// Reference coordinates of the nodes of a tensor-product element with the given order along
+/// each axis, equally spaced in [0, 1], in the order in which `SquareShapeFunctions` and
+/// `CubeShapeFunctions` flatten their outer products: x varies fastest, then y, then z.
+pub fn tensor_product_node_order(orders: &[u8]) -> Vec<Vec<f64>> {
+    orders.iter().fold(vec![Vec::new()], |nodes, &order| {
+        (0..=order)
+            .flat_map(|i| {
+                nodes.iter().map(move |node| {
+                    let mut node = node.clone();
+                    node.push(i as f64 / order as f64);
+                    node
+                })
+            })
+            .collect()
+    })
+}
+
+// Runtime selection of the element types above
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ShapeFunctionKind {
+    Line1,
+    Line2,
+    Square1,
+    Square2,
+    Cube1,
+    Cube2,
+    CubeSerendipity,
+}
+
+impl ShapeFunctionKind {
+    pub fn dimension(&self) -> u8 {
+        match self {
+            ShapeFunctionKind::Line1 => LineShapeFunctions::<1>::DIMENSION,
+            ShapeFunctionKind::Line2 => LineShapeFunctions::<2>::DIMENSION,
+            ShapeFunctionKind::Square1 => SquareOrder1ShapeFunctions::DIMENSION,
+            ShapeFunctionKind::Square2 => SquareOrder2ShapeFunctions::DIMENSION,
+            ShapeFunctionKind::Cube1 => CubeOrder1ShapeFunctions::DIMENSION,
+            ShapeFunctionKind::Cube2 => CubeOrder2ShapeFunctions::DIMENSION,
+            ShapeFunctionKind::CubeSerendipity => CubeSerendipityShapeFunctions::DIMENSION,
+        }
+    }
+
+    pub fn number_of_nodes(&self) -> u8 {
+        match self {
+            ShapeFunctionKind::Line1 => LineShapeFunctions::<1>::NUMBER_OF_NODES,
+            ShapeFunctionKind::Line2 => LineShapeFunctions::<2>::NUMBER_OF_NODES,
+            ShapeFunctionKind::Square1 => SquareOrder1ShapeFunctions::NUMBER_OF_NODES,
+            ShapeFunctionKind::Square2 => SquareOrder2ShapeFunctions::NUMBER_OF_NODES,
+            ShapeFunctionKind::Cube1 => CubeOrder1ShapeFunctions::NUMBER_OF_NODES,
+            ShapeFunctionKind::Cube2 => CubeOrder2ShapeFunctions::NUMBER_OF_NODES,
+            ShapeFunctionKind::CubeSerendipity => CubeSerendipityShapeFunctions::NUMBER_OF_NODES,
+        }
+    }
+
+    // Panics if coords.len() differs from the element dimension, see `try_evaluate_shape_functions`
+    pub fn evaluate_shape_functions(&self, coords: &[f64]) -> Vec<f64> {
+        match self {
+            ShapeFunctionKind::Line1 => LineShapeFunctions::<1>::evaluate_shape_functions(&Self::coordinates(coords)),
+            ShapeFunctionKind::Line2 => LineShapeFunctions::<2>::evaluate_shape_functions(&Self::coordinates(coords)),
+            ShapeFunctionKind::Square1 => SquareOrder1ShapeFunctions::evaluate_shape_functions(&Self::coordinates(coords)),
+            ShapeFunctionKind::Square2 => SquareOrder2ShapeFunctions::evaluate_shape_functions(&Self::coordinates(coords)),
+            ShapeFunctionKind::Cube1 => CubeOrder1ShapeFunctions::evaluate_shape_functions(&Self::coordinates(coords)),
+            ShapeFunctionKind::Cube2 => CubeOrder2ShapeFunctions::evaluate_shape_functions(&Self::coordinates(coords)),
+            ShapeFunctionKind::CubeSerendipity => CubeSerendipityShapeFunctions::evaluate_shape_functions(&Self::coordinates(coords)),
+        }
+    }
+
+    // Panics if coords.len() differs from the element dimension, see
+    // `try_evaluate_jacobian_of_shape_functions`
+    pub fn evaluate_jacobian_of_shape_functions(&self, coords: &[f64]) -> Array2<f64> {
+        match self {
+            ShapeFunctionKind::Line1 => LineShapeFunctions::<1>::evaluate_jacobian_of_shape_functions(&Self::coordinates(coords)),
+            ShapeFunctionKind::Line2 => LineShapeFunctions::<2>::evaluate_jacobian_of_shape_functions(&Self::coordinates(coords)),
+            ShapeFunctionKind::Square1 => SquareOrder1ShapeFunctions::evaluate_jacobian_of_shape_functions(&Self::coordinates(coords)),
+            ShapeFunctionKind::Square2 => SquareOrder2ShapeFunctions::evaluate_jacobian_of_shape_functions(&Self::coordinates(coords)),
+            ShapeFunctionKind::Cube1 => CubeOrder1ShapeFunctions::evaluate_jacobian_of_shape_functions(&Self::coordinates(coords)),
+            ShapeFunctionKind::Cube2 => CubeOrder2ShapeFunctions::evaluate_jacobian_of_shape_functions(&Self::coordinates(coords)),
+            ShapeFunctionKind::CubeSerendipity => CubeSerendipityShapeFunctions::evaluate_jacobian_of_shape_functions(&Self::coordinates(coords)),
+        }
+    }
+
+    pub fn try_evaluate_shape_functions(&self, coords: &[f64]) -> Result<Vec<f64>, ShapeFunctionError> {
+        self.check_coordinate_count(coords)?;
+        Ok(self.evaluate_shape_functions(coords))
+    }
+
+    pub fn try_evaluate_jacobian_of_shape_functions(&self, coords: &[f64]) -> Result<Array2<f64>, ShapeFunctionError> {
+        self.check_coordinate_count(coords)?;
+        Ok(self.evaluate_jacobian_of_shape_functions(coords))
+    }
+
+    fn check_coordinate_count(&self, coords: &[f64]) -> Result<(), ShapeFunctionError> {
+        let dimension = self.dimension() as usize;
+        if coords.len() != dimension {
+            return Err(ShapeFunctionError::CoordinateCount { expected: dimension, actual: coords.len() });
+        }
+        Ok(())
+    }
+
+    // Centroid of the reference element, see `NodalBasedShapeFunctions::center`
+    pub fn center(&self) -> Vec<f64> {
+        match self {
+            ShapeFunctionKind::Line1 => LineShapeFunctions::<1>::center().to_vec(),
+            ShapeFunctionKind::Line2 => LineShapeFunctions::<2>::center().to_vec(),
+            ShapeFunctionKind::Square1 => SquareOrder1ShapeFunctions::center().to_vec(),
+            ShapeFunctionKind::Square2 => SquareOrder2ShapeFunctions::center().to_vec(),
+            ShapeFunctionKind::Cube1 => CubeOrder1ShapeFunctions::center().to_vec(),
+            ShapeFunctionKind::Cube2 => CubeOrder2ShapeFunctions::center().to_vec(),
+            ShapeFunctionKind::CubeSerendipity => CubeSerendipityShapeFunctions::center().to_vec(),
+        }
+    }
+
+    // Reference coordinates of every node, in local node order
+    pub fn reference_nodes(&self) -> Vec<Vec<f64>> {
+        match self {
+            ShapeFunctionKind::Line1 => LineShapeFunctions::<1>::reference_node_coordinates(),
+            ShapeFunctionKind::Line2 => LineShapeFunctions::<2>::reference_node_coordinates(),
+            ShapeFunctionKind::Square1 => SquareOrder1ShapeFunctions::reference_node_coordinates(),
+            ShapeFunctionKind::Square2 => SquareOrder2ShapeFunctions::reference_node_coordinates(),
+            ShapeFunctionKind::Cube1 => CubeOrder1ShapeFunctions::reference_node_coordinates(),
+            ShapeFunctionKind::Cube2 => CubeOrder2ShapeFunctions::reference_node_coordinates(),
+            ShapeFunctionKind::CubeSerendipity => CubeSerendipityShapeFunctions::reference_node_coordinates(),
+        }
+    }
+
+    /*
+    Local node numbers of each boundary facet (points for lines, edges for squares,
+    faces for cubes), ordered x=0, x=1, y=0, y=1, z=0, z=1.
+    Nodes within a facet keep the element's local order, so for tensor-product elements
+    they follow the tensor-product numbering of the lower-dimensional element.
+    */
+    pub fn faces(&self) -> Vec<Vec<usize>> {
+        let reference_nodes = self.reference_nodes();
+
+        (0..self.dimension() as usize)
+            .flat_map(|axis| [(axis, 0.0), (axis, 1.0)])
+            .map(|(axis, value)| {
+                reference_nodes
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, node)| node[axis] == value)
+                    .map(|(local_node, _)| local_node)
+                    .collect()
+            })
+            .collect()
+    }
+
+    /*
+    Element type of the facets returned by `faces`, whose nodes are in the local order of that type.
+    None for lines, whose facets are points, and for the serendipity hexahedron, whose 8-node
+    faces have no element type here.
+    */
+    pub fn face_kind(&self) -> Option<ShapeFunctionKind> {
+        match self {
+            ShapeFunctionKind::Square1 => Some(ShapeFunctionKind::Line1),
+            ShapeFunctionKind::Square2 => Some(ShapeFunctionKind::Line2),
+            ShapeFunctionKind::Cube1 => Some(ShapeFunctionKind::Square1),
+            ShapeFunctionKind::Cube2 => Some(ShapeFunctionKind::Square2),
+            ShapeFunctionKind::Line1 | ShapeFunctionKind::Line2 | ShapeFunctionKind::CubeSerendipity => None,
+        }
+    }
+
+    fn coordinates<const DIM: usize>(coords: &[f64]) -> [f64; DIM] {
+        coords.try_into().unwrap_or_else(|_| {
+            panic!("Expected {} coordinates, got {}", DIM, coords.len())
+        })
+    }
+}
+
+/// Errors of the checked evaluations of `ShapeFunctionKind`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ShapeFunctionError {
+    /// The parametric point does not have one coordinate per element dimension
+    CoordinateCount { expected: usize, actual: usize },
+    /// The field does not have one value per element node
+    NodalValueCount { expected: usize, actual: usize },
+    /// The shape functions are not implemented for this polynomial order
+    UnsupportedOrder { order: u8 },
+}
+
+impl std::fmt::Display for ShapeFunctionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ShapeFunctionError::CoordinateCount { expected, actual } => {
+                write!(f, "Expected {} parametric coordinates, got {}", expected, actual)
+            }
+            ShapeFunctionError::NodalValueCount { expected, actual } => {
+                write!(f, "Expected {} nodal values, got {}", expected, actual)
+            }
+            ShapeFunctionError::UnsupportedOrder { order } => {
+                write!(f, "Unsupported shape function order {}", order)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ShapeFunctionError {}
+
+fn check_supported_orders(orders: &[u8], supported: &[u8]) -> Result<(), ShapeFunctionError> {
+    match orders.iter().find(|order| !supported.contains(order)) {
+        Some(&order) => Err(ShapeFunctionError::UnsupportedOrder { order }),
+        None => Ok(()),
+    }
+}
+
+/// Value at the parametric point `coords` of the field with the given values at the nodes of an
+/// element of type `kind`: u(ξ) = Σ N_i(ξ) u_i. The point may lie anywhere in the reference element.
+pub fn interpolate(kind: ShapeFunctionKind, nodal_values: &[f64], coords: &[f64]) -> Result<f64, ShapeFunctionError> {
+    kind.check_coordinate_count(coords)?;
+    let number_of_nodes = kind.number_of_nodes() as usize;
+    if nodal_values.len() != number_of_nodes {
+        return Err(ShapeFunctionError::NodalValueCount { expected: number_of_nodes, actual: nodal_values.len() });
+    }
+
+    Ok(kind
+        .evaluate_shape_functions(coords)
+        .iter()
+        .zip(nodal_values)
+        .map(|(n, u)| n * u)
+        .sum())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elements::element_library::simplex_elements::{TetrahedronShapeFunctions, TriangleShapeFunctions};
+    use crate::elements::quadrature::quadrature_rules::QuadratureRuleDyn;
+
+    #[test]
+    fn test_interpolate() {
+        // Linear field u = 1 + 2x - y + 3z sampled at the nodes
+        let field = |point: &[f64]| 1.0 + [2.0, -1.0, 3.0].iter().zip(point).map(|(a, x)| a * x).sum::<f64>();
+
+        for kind in [ShapeFunctionKind::Line1, ShapeFunctionKind::Square1, ShapeFunctionKind::Cube1] {
+            let nodal_values: Vec<f64> = kind.reference_nodes().iter().map(|node| field(node)).collect();
+            let average = nodal_values.iter().sum::<f64>() / nodal_values.len() as f64;
+            assert!((interpolate(kind, &nodal_values, &kind.center()).unwrap() - average).abs() < 1e-14);
+        }
+
+        // Higher order elements reproduce the linear field anywhere in the element
+        for kind in [ShapeFunctionKind::Line2, ShapeFunctionKind::Square2, ShapeFunctionKind::Cube2, ShapeFunctionKind::CubeSerendipity] {
+            let nodal_values: Vec<f64> = kind.reference_nodes().iter().map(|node| field(node)).collect();
+            let point = &[0.2, 0.7, 0.35][..kind.dimension() as usize];
+            assert!((interpolate(kind, &nodal_values, point).unwrap() - field(point)).abs() < 1e-13);
+        }
+
+        assert_eq!(
+            interpolate(ShapeFunctionKind::Square1, &[1.0; 3], &[0.5, 0.5]),
+            Err(ShapeFunctionError::NodalValueCount { expected: 4, actual: 3 })
+        );
+        assert_eq!(
+            interpolate(ShapeFunctionKind::Square1, &[1.0; 4], &[0.5]),
+            Err(ShapeFunctionError::CoordinateCount { expected: 2, actual: 1 })
+        );
+    }
+
+    #[test]
+    fn test_try_evaluate_shape_functions() {
+        assert_eq!(
+            LineShapeFunctions::<3>::try_evaluate_shape_functions(&[0.5]),
+            Err(ShapeFunctionError::UnsupportedOrder { order: 3 })
+        );
+        assert_eq!(
+            SquareShapeFunctions::<1, 3>::try_evaluate_jacobian_of_shape_functions(&[0.5, 0.5]),
+            Err(ShapeFunctionError::UnsupportedOrder { order: 3 })
+        );
+        assert_eq!(
+            SquareOrder2ShapeFunctions::try_evaluate_shape_functions(&[0.25, 0.75]),
+            Ok(SquareOrder2ShapeFunctions::evaluate_shape_functions(&[0.25, 0.75]))
+        );
+
+        assert_eq!(
+            ShapeFunctionKind::Cube1.try_evaluate_shape_functions(&[0.5, 0.5]),
+            Err(ShapeFunctionError::CoordinateCount { expected: 3, actual: 2 })
+        );
+        assert_eq!(
+            ShapeFunctionKind::Line2.try_evaluate_jacobian_of_shape_functions(&[0.5, 0.5]),
+            Err(ShapeFunctionError::CoordinateCount { expected: 1, actual: 2 })
+        );
+        assert_eq!(
+            ShapeFunctionKind::Cube2.try_evaluate_jacobian_of_shape_functions(&[0.2, 0.4, 0.6]),
+            Ok(ShapeFunctionKind::Cube2.evaluate_jacobian_of_shape_functions(&[0.2, 0.4, 0.6]))
+        );
+    }
+
+    #[test]
+    fn test_reported_orders() {
+        assert_eq!(LineShapeFunctions::<1>::ORDERS, &[1]);
+        assert_eq!(LineShapeFunctions::<2>::ORDERS, &[2]);
+        assert_eq!(SquareOrder1ShapeFunctions::ORDERS, &[1, 1]);
+        assert_eq!(SquareOrder2ShapeFunctions::ORDERS, &[2, 2]);
+        assert_eq!(SquareShapeFunctions::<2, 1>::ORDERS, &[2, 1]);
+        assert_eq!(CubeOrder1ShapeFunctions::ORDERS, &[1, 1, 1]);
+        assert_eq!(CubeOrder2ShapeFunctions::ORDERS, &[2, 2, 2]);
+        assert_eq!(CubeShapeFunctions::<2, 1, 1>::ORDERS, &[2, 1, 1]);
+        assert_eq!(CubeSerendipityShapeFunctions::ORDERS, &[2, 2, 2]);
+        assert_eq!(TriangleShapeFunctions::<2>::ORDERS, &[2, 2]);
+        assert_eq!(TetrahedronShapeFunctions::<1>::ORDERS, &[1, 1, 1]);
+
+        // Only the 20-node hexahedron is serendipity
+        assert_eq!(
+            [CubeSerendipityShapeFunctions::SERENDIPITY, CubeOrder2ShapeFunctions::SERENDIPITY, TetrahedronShapeFunctions::<2>::SERENDIPITY],
+            [true, false, false]
+        );
+    }
+
+    #[test]
+    fn test_batch_evaluation_matches_per_point() {
+        let rule = QuadratureRuleDyn::gauss_legendre(3, 3);
+        let points: [[f64; 3]; 27] = std::array::from_fn(|q| [rule.points[q][0], rule.points[q][1], rule.points[q][2]]);
+
+        // Serendipity overrides the batch methods, the tensor-product cube uses the defaults
+        let values = CubeSerendipityShapeFunctions::evaluate_all(&points);
+        let jacobians = CubeSerendipityShapeFunctions::evaluate_jacobian_all(&points);
+        for (q, point) in points.iter().enumerate() {
+            assert_eq!(values[q], CubeSerendipityShapeFunctions::evaluate_shape_functions(point));
+            assert_eq!(jacobians[q], CubeSerendipityShapeFunctions::evaluate_jacobian_of_shape_functions(point));
+        }
+
+        let values = CubeOrder2ShapeFunctions::evaluate_all(&points);
+        let jacobians = CubeOrder2ShapeFunctions::evaluate_jacobian_all(&points);
+        for (q, point) in points.iter().enumerate() {
+            assert_eq!(values[q], CubeOrder2ShapeFunctions::evaluate_shape_functions(point));
+            assert_eq!(jacobians[q], CubeOrder2ShapeFunctions::evaluate_jacobian_of_shape_functions(point));
+        }
+    }
+
+    #[test]
+    fn test_tensor_product_node_order() {
+        assert_eq!(
+            tensor_product_node_order(&[1, 1]),
+            vec![vec![0.0, 0.0], vec![1.0, 0.0], vec![0.0, 1.0], vec![1.0, 1.0]]
+        );
+
+        // 27-node hexahedron: x fastest, then y, then z
+        let nodes = tensor_product_node_order(&[2, 2, 2]);
+        assert_eq!(nodes.len(), 27);
+        let values = [0.0, 0.5, 1.0];
+        for (index, node) in nodes.iter().enumerate() {
+            assert_eq!(node, &vec![values[index % 3], values[index / 3 % 3], values[index / 9]]);
+        }
+        assert_eq!(nodes[4], vec![0.5, 0.5, 0.0]);
+        assert_eq!(nodes[13], vec![0.5, 0.5, 0.5]);
+
+        // Every element interpolates at its reference nodes
+        fn check_kronecker<Element: NodalBasedShapeFunctions>(to_coordinates: impl Fn(&[f64]) -> Element::Coordinates) {
+            let nodes = Element::reference_node_coordinates();
+            assert_eq!(nodes.len(), Element::NUMBER_OF_NODES as usize);
+            for (i, node) in nodes.iter().enumerate() {
+                let n = Element::evaluate_shape_functions(&to_coordinates(node));
+                for (j, value) in n.iter().enumerate() {
+                    assert!((value - if i == j { 1.0 } else { 0.0 }).abs() < 1e-14);
+                }
+            }
+        }
+        check_kronecker::<LineShapeFunctions<2>>(|node| [node[0]]);
+        check_kronecker::<SquareShapeFunctions<2, 1>>(|node| [node[0], node[1]]);
+        check_kronecker::<CubeOrder2ShapeFunctions>(|node| [node[0], node[1], node[2]]);
+        check_kronecker::<CubeShapeFunctions<1, 2, 1>>(|node| [node[0], node[1], node[2]]);
+        check_kronecker::<CubeSerendipityShapeFunctions>(|node| [node[0], node[1], node[2]]);
+        check_kronecker::<TriangleShapeFunctions<2>>(|node| [node[0], node[1]]);
+        check_kronecker::<TetrahedronShapeFunctions<2>>(|node| [node[0], node[1], node[2]]);
+    }
+
+    #[test]
+    fn test_center_partition_of_unity() {
+        fn sum_at_center<Element: NodalBasedShapeFunctions>() -> f64 {
+            Element::evaluate_shape_functions(&Element::center()).iter().sum()
+        }
+        let sums = [
+            sum_at_center::<LineShapeFunctions<1>>(),
+            sum_at_center::<LineShapeFunctions<2>>(),
+            sum_at_center::<SquareOrder1ShapeFunctions>(),
+            sum_at_center::<SquareOrder2ShapeFunctions>(),
+            sum_at_center::<CubeOrder1ShapeFunctions>(),
+            sum_at_center::<CubeOrder2ShapeFunctions>(),
+            sum_at_center::<CubeSerendipityShapeFunctions>(),
+            sum_at_center::<TriangleShapeFunctions<1>>(),
+            sum_at_center::<TriangleShapeFunctions<2>>(),
+            sum_at_center::<TetrahedronShapeFunctions<1>>(),
+            sum_at_center::<TetrahedronShapeFunctions<2>>(),
+        ];
+        for sum in sums {
+            assert!((sum - 1.0).abs() < 1e-12);
+        }
+
+        // The serendipity functions sum to one everywhere, so their derivatives sum to zero
+        let point = [0.15, 0.6, 0.85];
+        let n: f64 = CubeSerendipityShapeFunctions::evaluate_shape_functions(&point).iter().sum();
+        assert!((n - 1.0).abs() < 1e-12);
+        let jacobian = CubeSerendipityShapeFunctions::evaluate_jacobian_of_shape_functions(&point);
+        assert!(jacobian.sum_axis(ndarray::Axis(0)).iter().all(|dn| dn.abs() < 1e-12));
+
+        // And the derivatives match central differences of the functions
+        let h = 1e-6;
+        for axis in 0..3 {
+            let (mut plus, mut minus) = (point, point);
+            plus[axis] += h;
+            minus[axis] -= h;
+            let n_plus = CubeSerendipityShapeFunctions::evaluate_shape_functions(&plus);
+            let n_minus = CubeSerendipityShapeFunctions::evaluate_shape_functions(&minus);
+            for i in 0..20 {
+                assert!(((n_plus[i] - n_minus[i]) / (2.0 * h) - jacobian[(i, axis)]).abs() < 1e-8);
+            }
+        }
+
+        // The centre is the centroid of the reference nodes, and linear elements weigh all nodes equally
+        for kind in [ShapeFunctionKind::Line2, ShapeFunctionKind::Square1, ShapeFunctionKind::CubeSerendipity] {
+            let nodes = kind.reference_nodes();
+            for (d, &c) in kind.center().iter().enumerate() {
+                assert!((nodes.iter().map(|node| node[d]).sum::<f64>() / nodes.len() as f64 - c).abs() < 1e-14);
+            }
+        }
+        assert!(TetrahedronShapeFunctions::<1>::evaluate_shape_functions(&TetrahedronShapeFunctions::<1>::center())
+            .iter()
+            .all(|&n| (n - 0.25).abs() < 1e-14));
+    }
+}
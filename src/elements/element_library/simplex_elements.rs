@@ -0,0 +1,375 @@
+//! Finite element shape functions for simplex element types.
+//!
+//! This module provides nodal-based shape functions written in barycentric coordinates on:
+//! - the reference triangle with vertices (0,0), (1,0) and (0,1), with area coordinates
+//!   L1 = 1 - x - y, L2 = x, L3 = y
+//! - the reference tetrahedron with vertices (0,0,0), (1,0,0), (0,1,0) and (0,0,1), with volume
+//!   coordinates L1 = 1 - x - y - z, L2 = x, L3 = y, L4 = z
+//!
+//! # Implementations
+//!
+//! ## 2D Triangle Elements
+//! - `TriangleShapeFunctions<ORDER>`: Lagrange shape functions for triangles
+//!   - Supported orders: 1 (linear T3, 3 nodes), 2 (quadratic T6, 6 nodes)
+//!
+//! ## 3D Tetrahedral Elements
+//! - `TetrahedronShapeFunctions<ORDER>`: Lagrange shape functions for tetrahedra
+//!   - Supported orders: 1 (linear Tet4, 4 nodes), 2 (quadratic Tet10, 10 nodes)
+//!
+//! Matching symmetric quadrature rules are `triangle_1_point`, `triangle_3_point`,
+//! `triangle_6_point`, `tetrahedron_1_point`, `tetrahedron_4_point` and `tetrahedron_5_point`
+//! in the quadrature module.
+//!
+//! # Examples
+//!
+//! ```
+//! use simplex_elements::TriangleShapeFunctions;
+//! use hypercube_elements::NodalBasedShapeFunctions;
+//!
+//! let coords = [0.25, 0.25];
+//! let n = TriangleShapeFunctions::<2>::evaluate_shape_functions(&coords);
+//! let jac = TriangleShapeFunctions::<2>::evaluate_jacobian_of_shape_functions(&coords);
+//! ```
+
+use ndarray::Array2;
+
+use crate::elements::element_library::hypercube_elements::NodalBasedShapeFunctions;
+
+// 2D Triangle elements
+pub struct TriangleShapeFunctions<const ORDER: u8>;
+
+impl<const ORDER: u8> NodalBasedShapeFunctions for TriangleShapeFunctions<ORDER> {
+    type Coordinates = [f64; 2];
+    const DIMENSION: u8 = 2;
+    const NUMBER_OF_NODES: u8 = (ORDER + 1) * (ORDER + 2) / 2;
+    const ORDERS: &'static [u8] = &[ORDER, ORDER];
+    const SIMPLEX: bool = true;
+
+    fn evaluate_shape_functions(coords: &[f64; 2]) -> Vec<f64> {
+        match ORDER {
+            1 => TriangleShapeFunctions::<1>::evaluate_shape_functions_impl(coords),
+            2 => TriangleShapeFunctions::<2>::evaluate_shape_functions_impl(coords),
+            _ => panic!("Unsupported order for triangle shape functions"),
+        }
+    }
+
+    fn evaluate_jacobian_of_shape_functions(coords: &[f64; 2]) -> Array2<f64> {
+        match ORDER {
+            1 => TriangleShapeFunctions::<1>::evaluate_jacobian_impl(coords),
+            2 => TriangleShapeFunctions::<2>::evaluate_jacobian_impl(coords),
+            _ => panic!("Unsupported order for triangle shape functions"),
+        }
+    }
+
+    fn center() -> [f64; 2] {
+        [1.0 / 3.0; 2]
+    }
+
+    fn reference_node_coordinates() -> Vec<Vec<f64>> {
+        let vertices = [[0.0, 0.0], [1.0, 0.0], [0.0, 1.0]];
+        match ORDER {
+            1 => simplex_nodes(&vertices, &[]),
+            2 => simplex_nodes(&vertices, &TriangleShapeFunctions::<2>::EDGES),
+            _ => panic!("Unsupported order for triangle shape functions"),
+        }
+    }
+}
+
+impl<const ORDER: u8> TriangleShapeFunctions<ORDER> {
+    /// Area coordinates (L1, L2, L3) of a point
+    fn barycentric(coords: &[f64; 2]) -> [f64; 3] {
+        [1.0 - coords[0] - coords[1], coords[0], coords[1]]
+    }
+
+    /// dL_i/dx and dL_i/dy of the area coordinates
+    const BARYCENTRIC_GRADIENTS: [[f64; 2]; 3] = [[-1.0, -1.0], [1.0, 0.0], [0.0, 1.0]];
+}
+
+impl TriangleShapeFunctions<1> {
+    /*
+    Node     x   y      N
+    0        0   0      L1
+    1        1   0      L2
+    2        0   1      L3
+    */
+    fn evaluate_shape_functions_impl(coords: &[f64; 2]) -> Vec<f64> {
+        Self::barycentric(coords).to_vec()
+    }
+
+    fn evaluate_jacobian_impl(_coords: &[f64; 2]) -> Array2<f64> {
+        Array2::from_shape_fn((3, 2), |(node, axis)| Self::BARYCENTRIC_GRADIENTS[node][axis])
+    }
+}
+
+impl TriangleShapeFunctions<2> {
+    /*
+    Node     x   y      N
+    0        0   0      L1 (2 L1 - 1)
+    1        1   0      L2 (2 L2 - 1)
+    2        0   1      L3 (2 L3 - 1)
+    3        0.5 0      4 L1 L2
+    4        0.5 0.5    4 L2 L3
+    5        0   0.5    4 L3 L1
+    */
+    const EDGES: [(usize, usize); 3] = [(0, 1), (1, 2), (2, 0)];
+
+    fn evaluate_shape_functions_impl(coords: &[f64; 2]) -> Vec<f64> {
+        let l = Self::barycentric(coords);
+
+        let corners = l.iter().map(|&li| li * (2.0 * li - 1.0));
+        let edges = Self::EDGES.iter().map(|&(i, j)| 4.0 * l[i] * l[j]);
+        corners.chain(edges).collect()
+    }
+
+    fn evaluate_jacobian_impl(coords: &[f64; 2]) -> Array2<f64> {
+        let l = Self::barycentric(coords);
+        let dl = Self::BARYCENTRIC_GRADIENTS;
+
+        let mut jacobian = Array2::zeros((6, 2));
+        for axis in 0..2 {
+            for corner in 0..3 {
+                jacobian[(corner, axis)] = (4.0 * l[corner] - 1.0) * dl[corner][axis];
+            }
+            for (edge, &(i, j)) in Self::EDGES.iter().enumerate() {
+                jacobian[(3 + edge, axis)] = 4.0 * (dl[i][axis] * l[j] + l[i] * dl[j][axis]);
+            }
+        }
+        jacobian
+    }
+}
+
+// 3D Tetrahedral elements
+pub struct TetrahedronShapeFunctions<const ORDER: u8>;
+
+impl<const ORDER: u8> NodalBasedShapeFunctions for TetrahedronShapeFunctions<ORDER> {
+    type Coordinates = [f64; 3];
+    const DIMENSION: u8 = 3;
+    const NUMBER_OF_NODES: u8 = (ORDER + 1) * (ORDER + 2) * (ORDER + 3) / 6;
+    const ORDERS: &'static [u8] = &[ORDER, ORDER, ORDER];
+    const SIMPLEX: bool = true;
+
+    fn evaluate_shape_functions(coords: &[f64; 3]) -> Vec<f64> {
+        match ORDER {
+            1 => TetrahedronShapeFunctions::<1>::evaluate_shape_functions_impl(coords),
+            2 => TetrahedronShapeFunctions::<2>::evaluate_shape_functions_impl(coords),
+            _ => panic!("Unsupported order for tetrahedron shape functions"),
+        }
+    }
+
+    fn evaluate_jacobian_of_shape_functions(coords: &[f64; 3]) -> Array2<f64> {
+        match ORDER {
+            1 => TetrahedronShapeFunctions::<1>::evaluate_jacobian_impl(coords),
+            2 => TetrahedronShapeFunctions::<2>::evaluate_jacobian_impl(coords),
+            _ => panic!("Unsupported order for tetrahedron shape functions"),
+        }
+    }
+
+    fn center() -> [f64; 3] {
+        [0.25; 3]
+    }
+
+    fn reference_node_coordinates() -> Vec<Vec<f64>> {
+        let vertices = [[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]];
+        match ORDER {
+            1 => simplex_nodes(&vertices, &[]),
+            2 => simplex_nodes(&vertices, &TetrahedronShapeFunctions::<2>::EDGES),
+            _ => panic!("Unsupported order for tetrahedron shape functions"),
+        }
+    }
+}
+
+/// Vertices followed by the midpoints of `edges`, the node order of the simplex elements
+fn simplex_nodes<const DIM: usize>(vertices: &[[f64; DIM]], edges: &[(usize, usize)]) -> Vec<Vec<f64>> {
+    let midpoints = edges
+        .iter()
+        .map(|&(i, j)| (0..DIM).map(|d| 0.5 * (vertices[i][d] + vertices[j][d])).collect());
+    vertices.iter().map(|vertex| vertex.to_vec()).chain(midpoints).collect()
+}
+
+impl<const ORDER: u8> TetrahedronShapeFunctions<ORDER> {
+    /// Volume coordinates (L1, L2, L3, L4) of a point
+    fn barycentric(coords: &[f64; 3]) -> [f64; 4] {
+        [1.0 - coords[0] - coords[1] - coords[2], coords[0], coords[1], coords[2]]
+    }
+
+    /// dL_i/dx, dL_i/dy and dL_i/dz of the volume coordinates
+    const BARYCENTRIC_GRADIENTS: [[f64; 3]; 4] = [[-1.0, -1.0, -1.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]];
+}
+
+impl TetrahedronShapeFunctions<1> {
+    /*
+    Node     x   y   z      N
+    0        0   0   0      L1
+    1        1   0   0      L2
+    2        0   1   0      L3
+    3        0   0   1      L4
+    */
+    fn evaluate_shape_functions_impl(coords: &[f64; 3]) -> Vec<f64> {
+        Self::barycentric(coords).to_vec()
+    }
+
+    fn evaluate_jacobian_impl(_coords: &[f64; 3]) -> Array2<f64> {
+        Array2::from_shape_fn((4, 3), |(node, axis)| Self::BARYCENTRIC_GRADIENTS[node][axis])
+    }
+}
+
+impl TetrahedronShapeFunctions<2> {
+    /*
+    Node     x   y   z      N
+    0-3      vertices       Li (2 Li - 1)
+    4        0.5 0   0      4 L1 L2
+    5        0.5 0.5 0      4 L2 L3
+    6        0   0.5 0      4 L3 L1
+    7        0   0   0.5    4 L1 L4
+    8        0.5 0   0.5    4 L2 L4
+    9        0   0.5 0.5    4 L3 L4
+    */
+    const EDGES: [(usize, usize); 6] = [(0, 1), (1, 2), (2, 0), (0, 3), (1, 3), (2, 3)];
+
+    fn evaluate_shape_functions_impl(coords: &[f64; 3]) -> Vec<f64> {
+        let l = Self::barycentric(coords);
+
+        let corners = l.iter().map(|&li| li * (2.0 * li - 1.0));
+        let edges = Self::EDGES.iter().map(|&(i, j)| 4.0 * l[i] * l[j]);
+        corners.chain(edges).collect()
+    }
+
+    fn evaluate_jacobian_impl(coords: &[f64; 3]) -> Array2<f64> {
+        let l = Self::barycentric(coords);
+        let dl = Self::BARYCENTRIC_GRADIENTS;
+
+        let mut jacobian = Array2::zeros((10, 3));
+        for axis in 0..3 {
+            for corner in 0..4 {
+                jacobian[(corner, axis)] = (4.0 * l[corner] - 1.0) * dl[corner][axis];
+            }
+            for (edge, &(i, j)) in Self::EDGES.iter().enumerate() {
+                jacobian[(4 + edge, axis)] = 4.0 * (dl[i][axis] * l[j] + l[i] * dl[j][axis]);
+            }
+        }
+        jacobian
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const T6_NODES: [[f64; 2]; 6] = [[0.0, 0.0], [1.0, 0.0], [0.0, 1.0], [0.5, 0.0], [0.5, 0.5], [0.0, 0.5]];
+    const SAMPLE_POINTS: [[f64; 2]; 4] = [[0.2, 0.3], [0.0, 0.0], [0.7, 0.1], [1.0 / 3.0, 1.0 / 3.0]];
+
+    const TET10_NODES: [[f64; 3]; 10] = [
+        [0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0],
+        [0.5, 0.0, 0.0], [0.5, 0.5, 0.0], [0.0, 0.5, 0.0], [0.0, 0.0, 0.5], [0.5, 0.0, 0.5], [0.0, 0.5, 0.5],
+    ];
+    const SAMPLE_POINTS_3D: [[f64; 3]; 4] = [[0.2, 0.3, 0.1], [0.0, 0.0, 0.0], [0.1, 0.1, 0.7], [0.25, 0.25, 0.25]];
+
+    fn check_jacobian_by_finite_differences<const DIM: usize, S: NodalBasedShapeFunctions<Coordinates = [f64; DIM]>>(point: [f64; DIM]) {
+        let h = 1e-6;
+        let jacobian = S::evaluate_jacobian_of_shape_functions(&point);
+        assert_eq!(jacobian.dim(), (S::NUMBER_OF_NODES as usize, DIM));
+
+        for axis in 0..DIM {
+            let (mut forward, mut backward) = (point, point);
+            forward[axis] += h;
+            backward[axis] -= h;
+            let n_forward = S::evaluate_shape_functions(&forward);
+            let n_backward = S::evaluate_shape_functions(&backward);
+            for node in 0..S::NUMBER_OF_NODES as usize {
+                let derivative = (n_forward[node] - n_backward[node]) / (2.0 * h);
+                assert!((jacobian[(node, axis)] - derivative).abs() < 1e-8);
+            }
+        }
+    }
+
+    #[test]
+    fn test_partition_of_unity() {
+        for point in SAMPLE_POINTS {
+            let sum_t3: f64 = TriangleShapeFunctions::<1>::evaluate_shape_functions(&point).iter().sum();
+            let sum_t6: f64 = TriangleShapeFunctions::<2>::evaluate_shape_functions(&point).iter().sum();
+            assert!((sum_t3 - 1.0).abs() < 1e-14);
+            assert!((sum_t6 - 1.0).abs() < 1e-14);
+
+            // Gradients of a partition of unity sum to zero
+            for jacobian in [
+                TriangleShapeFunctions::<1>::evaluate_jacobian_of_shape_functions(&point),
+                TriangleShapeFunctions::<2>::evaluate_jacobian_of_shape_functions(&point),
+            ] {
+                for column in jacobian.columns() {
+                    assert!(column.sum().abs() < 1e-14);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_nodal_interpolation() {
+        assert_eq!(TriangleShapeFunctions::<1>::NUMBER_OF_NODES, 3);
+        assert_eq!(TriangleShapeFunctions::<2>::NUMBER_OF_NODES, 6);
+
+        for (i, node) in T6_NODES.iter().enumerate() {
+            let n = TriangleShapeFunctions::<2>::evaluate_shape_functions(node);
+            for (j, value) in n.iter().enumerate() {
+                assert!((value - if i == j { 1.0 } else { 0.0 }).abs() < 1e-14);
+            }
+
+            if i < 3 {
+                let n = TriangleShapeFunctions::<1>::evaluate_shape_functions(node);
+                for (j, value) in n.iter().enumerate() {
+                    assert_eq!(*value, if i == j { 1.0 } else { 0.0 });
+                }
+            }
+        }
+
+        // T6 reproduces quadratic fields: u = x^2 + 3xy - y
+        let u = |p: &[f64; 2]| p[0] * p[0] + 3.0 * p[0] * p[1] - p[1];
+        for point in SAMPLE_POINTS {
+            let n = TriangleShapeFunctions::<2>::evaluate_shape_functions(&point);
+            let interpolated: f64 = n.iter().zip(T6_NODES.iter()).map(|(ni, node)| ni * u(node)).sum();
+            assert!((interpolated - u(&point)).abs() < 1e-14);
+        }
+    }
+
+    #[test]
+    fn test_jacobian_of_shape_functions() {
+        for point in SAMPLE_POINTS {
+            check_jacobian_by_finite_differences::<2, TriangleShapeFunctions<1>>(point);
+            check_jacobian_by_finite_differences::<2, TriangleShapeFunctions<2>>(point);
+        }
+        for point in SAMPLE_POINTS_3D {
+            check_jacobian_by_finite_differences::<3, TetrahedronShapeFunctions<1>>(point);
+            check_jacobian_by_finite_differences::<3, TetrahedronShapeFunctions<2>>(point);
+        }
+    }
+
+    #[test]
+    fn test_tetrahedron_kronecker_delta() {
+        assert_eq!(TetrahedronShapeFunctions::<1>::NUMBER_OF_NODES, 4);
+        assert_eq!(TetrahedronShapeFunctions::<2>::NUMBER_OF_NODES, 10);
+        let reference_nodes: Vec<Vec<f64>> = TET10_NODES.iter().map(|node| node.to_vec()).collect();
+        assert_eq!(TetrahedronShapeFunctions::<2>::reference_node_coordinates(), reference_nodes);
+        assert_eq!(TetrahedronShapeFunctions::<1>::reference_node_coordinates(), reference_nodes[..4]);
+        assert_eq!(TriangleShapeFunctions::<2>::reference_node_coordinates().concat(), T6_NODES.concat());
+
+        for (i, node) in TET10_NODES.iter().enumerate() {
+            let n = TetrahedronShapeFunctions::<2>::evaluate_shape_functions(node);
+            for (j, value) in n.iter().enumerate() {
+                assert!((value - if i == j { 1.0 } else { 0.0 }).abs() < 1e-14);
+            }
+
+            if i < 4 {
+                let n = TetrahedronShapeFunctions::<1>::evaluate_shape_functions(node);
+                for (j, value) in n.iter().enumerate() {
+                    assert_eq!(*value, if i == j { 1.0 } else { 0.0 });
+                }
+            }
+        }
+
+        for point in SAMPLE_POINTS_3D {
+            let sum_tet4: f64 = TetrahedronShapeFunctions::<1>::evaluate_shape_functions(&point).iter().sum();
+            let sum_tet10: f64 = TetrahedronShapeFunctions::<2>::evaluate_shape_functions(&point).iter().sum();
+            assert!((sum_tet4 - 1.0).abs() < 1e-14);
+            assert!((sum_tet10 - 1.0).abs() < 1e-14);
+        }
+    }
+}
@@ -8,7 +8,8 @@
 //!
 //! `PolynomialCoefficientsFixedLength<T, const LEN: usize>`
 //! 
-//! A generic container for fixed-length polynomial coefficients. It has methods: `.iter`, `.pow` and `.mul_polynomial`.
+//! A generic container for fixed-length polynomial coefficients. It has methods: `.iter`, `.evaluate`, `.add`, `.scale`, `.pow` and `.mul_polynomial`,
+//! and supports `p + q` and `p * factor`.
 //!
 //! `DeterminantAndAdjugateExpansions1Parameter<const SIZE: usize, const DEGREE: usize, const DET_LEN: usize, const ADJ_LEN: usize>`
 //! 
@@ -24,7 +25,7 @@
 //!        /// Returns struct with:
 //!        /// - Determinant coefficients [c0, c1, c2] for det(M) = c0 + c1μ + c2μ²
 //!        /// - Adjugate coefficients [c0, c1] for adj(M) = c0 + c1μ
-//!        fn new_from_matrix(a: &Matrix2<f64>, b: &Matrix2<f64>) -> Self
+//!        pub fn new_from_matrix(a: &Matrix2<f64>, b: &Matrix2<f64>) -> Self
 //!    }
 //!    ```
 //!
@@ -35,33 +36,67 @@
 //!        /// Returns struct with:
 //!        /// - Determinant coefficients [c0, c1, c2, c3] for det(M) = c0 + c1μ + c2μ² + c3μ³
 //!        /// - Adjugate coefficients [c0, c1, c2] for adj(M) = c0 + c1μ + c2μ²
-//!        fn new_from_matrix(a: &Matrix3<f64>, b: &Matrix3<f64>) -> Self
+//!        pub fn new_from_matrix(a: &Matrix3<f64>, b: &Matrix3<f64>) -> Self
 //!    }
 //!    ```
 //!
+//! 3. **For 4x4 matrices:**
+//!    ```rust
+//!    impl DeterminantAndAdjugateExpansions1Parameter<4, 1, 5, 4> {
+//!        /// Creates expansions for M(μ) = A + Bμ
+//!        /// Returns struct with:
+//!        /// - Determinant coefficients [c0, c1, c2, c3, c4] for det(M) = c0 + c1μ + c2μ² + c3μ³ + c4μ⁴
+//!        /// - Adjugate coefficients [c0, c1, c2, c3] for adj(M) = c0 + c1μ + c2μ² + c3μ³
+//!        pub fn new_from_matrix(a: &Matrix4<f64>, b: &Matrix4<f64>) -> Self
+//!    }
+//!    ```
+//!
+//! The coefficients are read with the `determinant()` and `adjugate()` accessors.
+//!
+//! `DeterminantExpansion1Parameter::<2, 1, 3>::new_from_matrix(a, b)` and
+//! `DeterminantExpansion1Parameter::<3, 1, 4>::new_from_matrix(a, b)` compute only the determinant
+//! coefficients, with the same formulas but without the adjugate matrix algebra.
+//!
+//! `determinant_expansion_faddeev::<N, LEN>(a, b)` computes the determinant coefficients for any
+//! size with the Faddeev–LeVerrier recurrence; it is used to validate the closed forms above.
+//!
+//! ### Two-Parameter Expansions
+//!
+//! `DeterminantAndAdjugateExpansions2Parameters` handles M(μ,ν) = A + Bμ + Cν for 2x2 and 3x3
+//! matrices. Coefficients are stored in `BivariatePolynomialCoefficientsFixedLength`, a 2D grid
+//! where entry [i][j] multiplies μ^i ν^j. Use `new_from_matrix(a, b, c)` and `evaluate(mu, nu)`,
+//! or read the coefficients with the `determinant()` and `adjugate()` accessors.
+//!
+//! ### Position Jacobians
+//!
+//! `determinant_and_adjugate_expansions(&jac)` returns det(J) and adj(J) of a 2x2 or 3x3
+//! `ndarray` Jacobian (the constant terms of the 1-parameter expansions), or a `JacobianError`.
+//!
 //! ### Type Aliases
 //! - `DeterminantExpansion1Parameter`: Polynomial coefficients for determinant expansion
 //! - `AdjugateExpansion1Parameter`: Polynomial coefficients for adjugate expansion
+//! - `DeterminantExpansion2Parameters`, `AdjugateExpansion2Parameters`: Bivariate counterparts
 //!
 //! ### Usage Example
 //! ```rust
 //! // For a 2x2 matrix M(μ) = A + Bμ
 //! let a = Matrix2::new(1.0, 2.0, 3.0, 4.0);
 //! let b = Matrix2::new(0.5, 0.5, 0.5, 0.5);
-//! let expansions = DeterminantAndAdjugateExpansions1Parameter::new_from_matrix(&a, &b);
+//! let expansions = DeterminantAndAdjugateExpansions1Parameter::<2, 1, 3, 2>::new_from_matrix(&a, &b);
 //!
 //! // Access determinant coefficients:
-//! let det_coeffs = expansions.determinant.iter();
+//! let det_coeffs = expansions.determinant().iter();
 //! // det(M(μ)) = det_coeffs[0] + det_coeffs[1]*μ + det_coeffs[2]*μ²
+//! let det_at_mu = expansions.determinant().evaluate(0.5);
 //!
 //! // Access adjugate coefficients:
-//! let adj_coeffs = expansions.adjugate.iter();
+//! let adj_coeffs = expansions.adjugate().iter();
 //! // adj(M(μ)) = adj_coeffs[0] + adj_coeffs[1]*μ
 //! ```
 //!
 //! ### Note
 //! The implementations are specialized for linear parameter dependence (DEGREE=1)
-//! and for 2x2 and 3x3 matrices. The length parameters (DET_LEN, ADJ_LEN) must
+//! and for 2x2, 3x3 and 4x4 matrices. The length parameters (DET_LEN, ADJ_LEN) must
 //! match the expected polynomial lengths for the given matrix size and degree.
 //! 
 //! 
@@ -69,7 +104,8 @@
 //!
 //! The `InverseDeterminant3x3` struct provides functionality for computing power series
 //! expansions of the inverse determinant (1/det(M(μ))) for 3x3 matrices with linear
-//! parameter dependence (M(μ) = A + Bμ).
+//! parameter dependence (M(μ) = A + Bμ). `InverseDeterminant2x2` provides the same methods
+//! for 2x2 matrices, taking the determinant expansion [c0, c1, c2] instead.
 //!
 //! ### Key Methods:
 //!
@@ -126,30 +162,30 @@
 //! ```
 //!
 //! ### Implementation Notes:
-//! - Uses a power series expansion of 1/(c0 + c1μ + c2μ² + c3μ³)
+//! - Uses a power series expansion of 1/(c0 + c1μ + c2μ² + c3μ³) (or 1/(c0 + c1μ + c2μ²) for 2x2)
 //! - Efficiently computes coefficients using multinomial theorem
 //! - Precomputes factorials for better performance with higher orders
 //! - Handles singular matrices (c0 = 0) by returning None
 //! - Numerically stable through use of `recip()` instead of direct division
 
-// Correct 2x2 matrix definition
-struct MatrixNxN<const SIZE: usize>([[f64; SIZE]; SIZE]);
+use nalgebra::SMatrix;
+use ndarray::Array2;
+
+use crate::elements::parametric_topology_element::position_jacobian::JacobianError;
+
+// Square matrix definition
+type MatrixNxN<const SIZE: usize> = SMatrix<f64, SIZE, SIZE>;
 type Matrix2x2 = MatrixNxN<2>;
 type Matrix3x3 = MatrixNxN<3>;
-
-impl Matrix3x3 {
-    fn identity() -> Self {
-        MatrixNxN([[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]])
-    }   
-}
+type Matrix4x4 = MatrixNxN<4>;
 
 // Function to compute the adjugate of a 2x2 matrix
 fn adjugate2x2(m: &Matrix2x2) -> Matrix2x2 {
-    let [[a, b], [c, d]] = m.0;
-    MatrixNxN([
-        [d, -b], 
-        [-c, a]
-    ])
+    let (a, b, c, d) = (m[(0, 0)], m[(0, 1)], m[(1, 0)], m[(1, 1)]);
+    Matrix2x2::new(
+        d, -b, 
+        -c, a
+    )
 }
 
 #[derive(Clone, Debug)]
@@ -161,7 +197,15 @@ impl<T, const LEN: usize> PolynomialCoefficientsFixedLength<T, LEN> {
     }
 }
 
-type DeterminantExpansion1Parameter<const SIZE: usize, const DEGREE: usize, const LEN: usize>
+impl<const SIZE: usize, const LEN: usize> PolynomialCoefficientsFixedLength<MatrixNxN<SIZE>, LEN> {
+
+    /// Evaluates the matrix-valued polynomial at μ using Horner's scheme
+    pub fn evaluate(&self, mu: f64) -> MatrixNxN<SIZE> {
+        self.0.iter().rev().fold(MatrixNxN::<SIZE>::zeros(), |acc, coeff| acc * mu + coeff)
+    }
+}
+
+pub type DeterminantExpansion1Parameter<const SIZE: usize, const DEGREE: usize, const LEN: usize>
     = PolynomialCoefficientsFixedLength<f64, LEN>; // LEN = SIZE * DEGREE + 1
 
 type AdjugateExpansion1Parameter<const SIZE: usize, const DEGREE: usize, const LEN: usize>
@@ -172,10 +216,38 @@ pub struct DeterminantAndAdjugateExpansions1Parameter<const SIZE: usize, const D
     adjugate: AdjugateExpansion1Parameter<SIZE, DEGREE, ADJ_LEN>,
 }
 
+impl<const SIZE: usize, const DEGREE: usize, const DET_LEN: usize, const ADJ_LEN: usize>
+    DeterminantAndAdjugateExpansions1Parameter<SIZE, DEGREE, DET_LEN, ADJ_LEN> {
+
+    /// Coefficients [c0, c1, ...] of det(M(μ)) = c0 + c1μ + ...
+    pub fn determinant(&self) -> &PolynomialCoefficientsFixedLength<f64, DET_LEN> {
+        &self.determinant
+    }
+
+    /// Matrix coefficients [C0, C1, ...] of adj(M(μ)) = C0 + C1μ + ...
+    pub fn adjugate(&self) -> &PolynomialCoefficientsFixedLength<MatrixNxN<SIZE>, ADJ_LEN> {
+        &self.adjugate
+    }
+}
+
 impl DeterminantAndAdjugateExpansions1Parameter<2, 1, 3, 2> {
 
     /// M(μ) = A + Bμ
-    fn new_from_matrix(a: &Matrix2x2, b: &Matrix2x2) -> Self {
+    ///
+    /// ```rust
+    /// use nalgebra::Matrix2;
+    /// use femrs::elements::parametric_topology_element::determinant_and_adjugate::DeterminantAndAdjugateExpansions1Parameter;
+    ///
+    /// let a = Matrix2::new(1.0, 2.0, 3.0, 4.0);
+    /// let b = Matrix2::new(0.5, 0.5, 0.5, 0.5);
+    /// let expansions = DeterminantAndAdjugateExpansions1Parameter::<2, 1, 3, 2>::new_from_matrix(&a, &b);
+    ///
+    /// // det(A + Bμ) = -2 + 0μ + 0μ²
+    /// let mu = 0.5;
+    /// assert!((expansions.determinant().evaluate(mu) - (a + b * mu).determinant()).abs() < 1e-12);
+    /// assert_eq!(expansions.adjugate().iter().count(), 2);
+    /// ```
+    pub fn new_from_matrix(a: &Matrix2x2, b: &Matrix2x2) -> Self {
 
         let c0_adj = adjugate2x2(a);
         let c1_adj = adjugate2x2(b);
@@ -191,10 +263,50 @@ impl DeterminantAndAdjugateExpansions1Parameter<2, 1, 3, 2> {
     }
 }
 
+impl DeterminantExpansion1Parameter<2, 1, 3> {
+
+    /// Determinant coefficients of M(μ) = A + Bμ, equal to the `determinant()` of
+    /// `DeterminantAndAdjugateExpansions1Parameter::<2, 1, 3, 2>::new_from_matrix(a, b)`
+    pub fn new_from_matrix(a: &Matrix2x2, b: &Matrix2x2) -> Self {
+        let c1_det: f64 = a.transpose().component_mul(&adjugate2x2(b)).sum();
+        PolynomialCoefficientsFixedLength([a.determinant(), c1_det, b.determinant()])
+    }
+}
+
+impl DeterminantExpansion1Parameter<3, 1, 4> {
+
+    /// Determinant coefficients of M(μ) = A + Bμ, equal to the `determinant()` of
+    /// `DeterminantAndAdjugateExpansions1Parameter::<3, 1, 4, 3>::new_from_matrix(a, b)`.
+    ///
+    /// Only A² and B² are formed as matrix products; the traces of the other products
+    /// are computed directly, as tr(XY) = Σ X ∘ Yᵀ.
+    pub fn new_from_matrix(a: &Matrix3x3, b: &Matrix3x3) -> Self {
+        let tr_a: f64 = a.trace();
+        let tr_b: f64 = b.trace();
+
+        let a_sq: Matrix3x3 = a * a;
+        let b_sq: Matrix3x3 = b * b;
+
+        let tr_ab: f64 = trace_of_product(a, b);
+        let tr_term_a: f64 = 0.5 * (a_sq.trace() - tr_a * tr_a);
+        let tr_term_b: f64 = 0.5 * (b_sq.trace() - tr_b * tr_b);
+
+        let c1_det: f64 = trace_of_product(&a_sq, b) - tr_term_a * tr_b - tr_a * tr_ab;
+        let c2_det: f64 = trace_of_product(&b_sq, a) - tr_term_b * tr_a - tr_b * tr_ab;
+
+        PolynomialCoefficientsFixedLength([a.determinant(), c1_det, c2_det, b.determinant()])
+    }
+}
+
+// tr(XY) without forming the product
+fn trace_of_product(x: &Matrix3x3, y: &Matrix3x3) -> f64 {
+    x.component_mul(&y.transpose()).sum()
+}
+
 impl DeterminantAndAdjugateExpansions1Parameter<3, 1, 4, 3> {
 
     /// M(μ) = A + Bμ
-    fn new_from_matrix(a: &Matrix3x3, b: &Matrix3x3) -> Self {
+    pub fn new_from_matrix(a: &Matrix3x3, b: &Matrix3x3) -> Self {
 
         // Common computations
         let i: Matrix3x3 = Matrix3x3::identity();
@@ -208,8 +320,8 @@ impl DeterminantAndAdjugateExpansions1Parameter<3, 1, 4, 3> {
 
         // Intermediate terms (reused for both det and adj)
         let tr_ab: f64 = ab.trace();
-        let term_a: Matrix3x3 = &a_sq - a * tr_a;
-        let term_b: Matrix3x3 = &b_sq - b * tr_b;
+        let term_a: Matrix3x3 = a_sq - a * tr_a;
+        let term_b: Matrix3x3 = b_sq - b * tr_b;
         let tr_term_a: f64 = 0.5 * term_a.trace(); // = (trA_sq - trA**2)/2
         let tr_term_b: f64 = 0.5 * term_b.trace(); // = (trB_sq - trB**2)/2
 
@@ -220,9 +332,9 @@ impl DeterminantAndAdjugateExpansions1Parameter<3, 1, 4, 3> {
         let c3_det: f64 = b.determinant();
 
         // Adjugate coefficients
-        let c0_adj: Matrix3x3 = &term_a - &i * tr_term_a;
-        let c1_adj: Matrix3x3 = &i * (tr_a * tr_b - tr_ab) - (a * tr_b + b * tr_a) + ab + b * a;
-        let c2_adj: Matrix3x3 = &term_b - &i * tr_term_b;
+        let c0_adj: Matrix3x3 = term_a - i * tr_term_a;
+        let c1_adj: Matrix3x3 = i * (tr_a * tr_b - tr_ab) - (a * tr_b + b * tr_a) + ab + b * a;
+        let c2_adj: Matrix3x3 = term_b - i * tr_term_b;
 
         DeterminantAndAdjugateExpansions1Parameter {
             determinant: PolynomialCoefficientsFixedLength([c0_det, c1_det, c2_det, c3_det]),
@@ -231,8 +343,434 @@ impl DeterminantAndAdjugateExpansions1Parameter<3, 1, 4, 3> {
     }
 }
 
+impl DeterminantAndAdjugateExpansions1Parameter<4, 1, 5, 4> {
+
+    /// M(μ) = A + Bμ
+    ///
+    /// Cayley-Hamilton for 4x4: adj(M) = e3·I - e2·M + e1·M² - M³, where the invariants
+    /// e1, e2, e3 of M are recovered from the power traces tr(M^k) via Newton's identities.
+    /// The determinant follows from M·adj(M) = det(M)·I.
+    pub fn new_from_matrix(a: &Matrix4x4, b: &Matrix4x4) -> Self {
+
+        // Common computations
+        let i: Matrix4x4 = Matrix4x4::identity();
+        let tr_a: f64 = a.trace();
+        let tr_b: f64 = b.trace();
+
+        // Matrix products
+        let a_sq: Matrix4x4 = a * a;
+        let b_sq: Matrix4x4 = b * b;
+        let ab: Matrix4x4 = a * b;
+        let ba: Matrix4x4 = b * a;
+
+        // Coefficients of M² and M³
+        let m2_1: Matrix4x4 = ab + ba;
+        let m3_0: Matrix4x4 = a_sq * a;
+        let m3_1: Matrix4x4 = a_sq * b + ab * a + b * a_sq;
+        let m3_2: Matrix4x4 = a * b_sq + ba * b + b_sq * a;
+        let m3_3: Matrix4x4 = b_sq * b;
+
+        // Power traces p2 = tr(M²) and p3 = tr(M³)
+        let p2: [f64; 3] = [a_sq.trace(), m2_1.trace(), b_sq.trace()];
+        let p3: [f64; 4] = [m3_0.trace(), m3_1.trace(), m3_2.trace(), m3_3.trace()];
+
+        // Invariants: 2·e2 = e1·p1 - p2 and 3·e3 = e2·p1 - e1·p2 + p3 (with e1 = p1 = tr(M))
+        let e2: [f64; 3] = [
+            0.5 * (tr_a * tr_a - p2[0]),
+            tr_a * tr_b - 0.5 * p2[1],
+            0.5 * (tr_b * tr_b - p2[2]),
+        ];
+        let e3: [f64; 4] = [
+            (e2[0] * tr_a - tr_a * p2[0] + p3[0]) / 3.0,
+            (e2[1] * tr_a + e2[0] * tr_b - tr_a * p2[1] - tr_b * p2[0] + p3[1]) / 3.0,
+            (e2[2] * tr_a + e2[1] * tr_b - tr_a * p2[2] - tr_b * p2[1] + p3[2]) / 3.0,
+            (e2[2] * tr_b - tr_b * p2[2] + p3[3]) / 3.0,
+        ];
+
+        // Adjugate coefficients
+        let c0_adj: Matrix4x4 = i * e3[0] - a * e2[0] + a_sq * tr_a - m3_0;
+        let c1_adj: Matrix4x4 = i * e3[1] - (a * e2[1] + b * e2[0]) + (m2_1 * tr_a + a_sq * tr_b) - m3_1;
+        let c2_adj: Matrix4x4 = i * e3[2] - (a * e2[2] + b * e2[1]) + (b_sq * tr_a + m2_1 * tr_b) - m3_2;
+        let c3_adj: Matrix4x4 = i * e3[3] - b * e2[2] + b_sq * tr_b - m3_3;
+
+        // Determinant coefficients: c_k·I = A·adj_k + B·adj_(k-1)
+        let c0_det: f64 = a.determinant();
+        let c1_det: f64 = 0.25 * (a * c1_adj + b * c0_adj).trace();
+        let c2_det: f64 = 0.25 * (a * c2_adj + b * c1_adj).trace();
+        let c3_det: f64 = 0.25 * (a * c3_adj + b * c2_adj).trace();
+        let c4_det: f64 = b.determinant();
+
+        DeterminantAndAdjugateExpansions1Parameter {
+            determinant: PolynomialCoefficientsFixedLength([c0_det, c1_det, c2_det, c3_det, c4_det]),
+            adjugate: PolynomialCoefficientsFixedLength([c0_adj, c1_adj, c2_adj, c3_adj]),
+        }
+    }
+}
+
+/// Coefficients [c0, ..., cN] of det(A + Bμ) for any size N, with the Faddeev–LeVerrier recurrence.
+///
+/// The pencil is shifted to C = A + Bμ0, with μ0 picked among 0, ±1, ±2, ... so that C is as far
+/// from singular as possible. Then det(A + Bμ) = det(C)·det(I + C⁻¹B·(μ - μ0)), whose coefficients
+/// are the (signed) characteristic polynomial coefficients of C⁻¹B, and the result is re-expanded
+/// around μ = 0. A singular pencil (det(A + Bμ) ≡ 0) gives all zeros.
+///
+/// This is slower than the closed-form `new_from_matrix` constructors and is mainly meant to
+/// validate them. `LEN` must be `N + 1`, which is checked at compile time.
+pub fn determinant_expansion_faddeev<const N: usize, const LEN: usize>(
+    a: &MatrixNxN<N>,
+    b: &MatrixNxN<N>,
+) -> PolynomialCoefficientsFixedLength<f64, LEN> {
+    const { assert!(LEN == N + 1, "LEN must be N + 1") };
+
+    // Shift with the best scaled determinant |det(C)| / |C|^N
+    let mut best: Option<(f64, f64, f64, MatrixNxN<N>)> = None; // (score, μ0, det(C), adj(C))
+    for candidate in 0..LEN {
+        let mu_0: f64 = if candidate.is_multiple_of(2) { -((candidate / 2) as f64) } else { (candidate / 2 + 1) as f64 };
+        let c: MatrixNxN<N> = a + b * mu_0;
+        let norm: f64 = c.norm();
+        if norm == 0.0 {
+            continue;
+        }
+
+        let (char_poly, m_n) = faddeev_leverrier(&c);
+        let sign: f64 = (-1.0_f64).powi(N as i32);
+        let det_c: f64 = sign * char_poly[0];
+        let score: f64 = det_c.abs() / norm.powi(N as i32);
+        if best.as_ref().is_none_or(|&(best_score, ..)| score > best_score) {
+            best = Some((score, mu_0, det_c, m_n * -sign));
+        }
+    }
+
+    let mut coefficients: [f64; LEN] = [0.0; LEN];
+    let Some((_, mu_0, det_c, adj_c)) = best.filter(|&(score, ..)| score > 0.0) else {
+        return PolynomialCoefficientsFixedLength(coefficients);
+    };
+
+    // det(I + Xs) = Σ e_k(X) s^k, with e_k = (-1)^k times the coefficient of λ^(N-k) in det(λI - X)
+    let x: MatrixNxN<N> = adj_c * b / det_c;
+    let (char_poly, _) = faddeev_leverrier(&x);
+    let shifted: Vec<f64> = (0..LEN)
+        .map(|k| det_c * (-1.0_f64).powi(k as i32) * char_poly[N - k])
+        .collect();
+
+    // (μ - μ0)^k = Σ_j binom(k, j) μ^j (-μ0)^(k-j)
+    for (k, &q_k) in shifted.iter().enumerate() {
+        let mut binomial: f64 = 1.0;
+        for j in (0..=k).rev() {
+            coefficients[j] += q_k * binomial * (-mu_0).powi((k - j) as i32);
+            binomial *= j as f64 / (k - j + 1) as f64;
+        }
+    }
+
+    PolynomialCoefficientsFixedLength(coefficients)
+}
+
+// Faddeev–LeVerrier: coefficients [p0, ..., pN] of det(λI - X) and the last iterate M_N,
+// with adj(X) = (-1)^(N-1) M_N
+fn faddeev_leverrier<const N: usize>(x: &MatrixNxN<N>) -> (Vec<f64>, MatrixNxN<N>) {
+    let mut char_poly: Vec<f64> = vec![0.0; N + 1];
+    char_poly[N] = 1.0;
+
+    let mut m: MatrixNxN<N> = MatrixNxN::<N>::zeros();
+    for k in 1..=N {
+        m = x * m + MatrixNxN::<N>::identity() * char_poly[N - k + 1];
+        char_poly[N - k] = -(x * m).trace() / k as f64;
+    }
+    (char_poly, m)
+}
+
+/// Bivariate polynomial coefficients: entry [i][j] multiplies μ^i ν^j.
+/// Only entries with i + j < LEN are used (total degree at most LEN - 1).
+#[derive(Clone, Debug)]
+pub struct BivariatePolynomialCoefficientsFixedLength<T, const LEN: usize> ([[T; LEN]; LEN]);
+
+impl<T, const LEN: usize> BivariatePolynomialCoefficientsFixedLength<T, LEN> {
+    pub fn iter(&self) -> std::slice::Iter<'_, [T; LEN]> {
+        self.0.iter()
+    }
+}
+
+impl<const LEN: usize> BivariatePolynomialCoefficientsFixedLength<f64, LEN> {
+
+    /// Evaluates the polynomial at (μ, ν) using Horner's scheme in both parameters
+    pub fn evaluate(&self, mu: f64, nu: f64) -> f64 {
+        self.0.iter().rev().fold(0.0, |acc, row| {
+            acc * mu + row.iter().rev().fold(0.0, |acc_nu, &coeff| acc_nu * nu + coeff)
+        })
+    }
+
+    /// Helper function for bivariate polynomial multiplication (terms of total degree >= LEN are truncated)
+    pub fn mul_polynomial(&self, b: &Self) -> Self {
+        let mut result = [[0.0; LEN]; LEN];
+
+        for (i1, row_1) in self.0.iter().enumerate() {
+            for (j1, &coeff_1) in row_1.iter().enumerate().take(LEN - i1) {
+                for (i2, row_2) in b.0.iter().enumerate().take(LEN - i1 - j1) {
+                    for (j2, &coeff_2) in row_2.iter().enumerate().take(LEN - i1 - j1 - i2) {
+                        result[i1 + i2][j1 + j2] += coeff_1 * coeff_2;
+                    }
+                }
+            }
+        }
+
+        BivariatePolynomialCoefficientsFixedLength(result)
+    }
+}
+
+impl<const SIZE: usize, const LEN: usize> BivariatePolynomialCoefficientsFixedLength<MatrixNxN<SIZE>, LEN> {
+
+    /// Evaluates the matrix-valued polynomial at (μ, ν) using Horner's scheme in both parameters
+    pub fn evaluate(&self, mu: f64, nu: f64) -> MatrixNxN<SIZE> {
+        self.0.iter().rev().fold(MatrixNxN::<SIZE>::zeros(), |acc, row| {
+            acc * mu + row.iter().rev().fold(MatrixNxN::<SIZE>::zeros(), |acc_nu, coeff| acc_nu * nu + coeff)
+        })
+    }
+}
+
+type DeterminantExpansion2Parameters<const SIZE: usize, const DEGREE: usize, const LEN: usize>
+    = BivariatePolynomialCoefficientsFixedLength<f64, LEN>; // LEN = SIZE * DEGREE + 1
+
+type AdjugateExpansion2Parameters<const SIZE: usize, const DEGREE: usize, const LEN: usize>
+    = BivariatePolynomialCoefficientsFixedLength<MatrixNxN<SIZE>, LEN>; // LEN = (SIZE - 1) * DEGREE + 1
+
+pub struct DeterminantAndAdjugateExpansions2Parameters<const SIZE: usize, const DEGREE: usize, const DET_LEN: usize, const ADJ_LEN: usize> {
+    determinant: DeterminantExpansion2Parameters<SIZE, DEGREE, DET_LEN>,
+    adjugate: AdjugateExpansion2Parameters<SIZE, DEGREE, ADJ_LEN>,
+}
+
+impl<const SIZE: usize, const DEGREE: usize, const DET_LEN: usize, const ADJ_LEN: usize>
+    DeterminantAndAdjugateExpansions2Parameters<SIZE, DEGREE, DET_LEN, ADJ_LEN> {
+
+    /// Coefficients of det(M(μ,ν)), entry [i][j] multiplies μ^i ν^j
+    pub fn determinant(&self) -> &BivariatePolynomialCoefficientsFixedLength<f64, DET_LEN> {
+        &self.determinant
+    }
+
+    /// Matrix coefficients of adj(M(μ,ν)), entry [i][j] multiplies μ^i ν^j
+    pub fn adjugate(&self) -> &BivariatePolynomialCoefficientsFixedLength<MatrixNxN<SIZE>, ADJ_LEN> {
+        &self.adjugate
+    }
+
+    /// Returns (det(M(μ,ν)), adj(M(μ,ν)))
+    pub fn evaluate(&self, mu: f64, nu: f64) -> (f64, MatrixNxN<SIZE>) {
+        (self.determinant.evaluate(mu, nu), self.adjugate.evaluate(mu, nu))
+    }
+
+    /// det(M)·I = M·adj(M), so each determinant coefficient is tr(Σ M_k·adj_(ij-k)) / SIZE,
+    /// with M = A + Bμ + Cν given as [A, B, C]
+    fn determinant_from_adjugate(
+        m: &[MatrixNxN<SIZE>; 3],
+        adjugate: &AdjugateExpansion2Parameters<SIZE, DEGREE, ADJ_LEN>
+    ) -> DeterminantExpansion2Parameters<SIZE, DEGREE, DET_LEN> {
+        let mut result = [[0.0; DET_LEN]; DET_LEN];
+        let [a, b, c] = m;
+
+        for (i, row) in adjugate.0.iter().enumerate() {
+            for (j, adj) in row.iter().enumerate().take(ADJ_LEN - i) {
+                result[i][j] += (a * adj).trace() / SIZE as f64;
+                result[i + 1][j] += (b * adj).trace() / SIZE as f64;
+                result[i][j + 1] += (c * adj).trace() / SIZE as f64;
+            }
+        }
+
+        BivariatePolynomialCoefficientsFixedLength(result)
+    }
+}
+
+impl DeterminantAndAdjugateExpansions2Parameters<2, 1, 3, 2> {
+
+    /// M(μ,ν) = A + Bμ + Cν
+    pub fn new_from_matrix(a: &Matrix2x2, b: &Matrix2x2, c: &Matrix2x2) -> Self {
+
+        // The 2x2 adjugate is linear
+        let zero = Matrix2x2::zeros();
+        let adjugate = BivariatePolynomialCoefficientsFixedLength([
+            [adjugate2x2(a), adjugate2x2(c)],
+            [adjugate2x2(b), zero],
+        ]);
+
+        let determinant = Self::determinant_from_adjugate(&[*a, *b, *c], &adjugate);
+
+        DeterminantAndAdjugateExpansions2Parameters { determinant, adjugate }
+    }
+}
+
+impl DeterminantAndAdjugateExpansions2Parameters<3, 1, 4, 3> {
+
+    /// M(μ,ν) = A + Bμ + Cν
+    pub fn new_from_matrix(a: &Matrix3x3, b: &Matrix3x3, c: &Matrix3x3) -> Self {
+
+        // The 3x3 adjugate is the quadratic form adj(X) = X² - tr(X)X + ½(tr(X)² - tr(X²))I,
+        // so the mixed coefficients are given by its polarization
+        let zero = Matrix3x3::zeros();
+        let adjugate = BivariatePolynomialCoefficientsFixedLength([
+            [adjugate3x3(a), adjugate3x3_polarized(a, c), adjugate3x3(c)],
+            [adjugate3x3_polarized(a, b), adjugate3x3_polarized(b, c), zero],
+            [adjugate3x3(b), zero, zero],
+        ]);
+
+        let determinant = Self::determinant_from_adjugate(&[*a, *b, *c], &adjugate);
+
+        DeterminantAndAdjugateExpansions2Parameters { determinant, adjugate }
+    }
+}
+
+// Function to compute the adjugate of a 3x3 matrix (Cayley-Hamilton)
+fn adjugate3x3(m: &Matrix3x3) -> Matrix3x3 {
+    adjugate3x3_polarized(m, m) * 0.5
+}
+
+// Polarization of the 3x3 adjugate: adj(X + Y) = adj(X) + adj(Y) + P(X, Y)
+fn adjugate3x3_polarized(x: &Matrix3x3, y: &Matrix3x3) -> Matrix3x3 {
+    let tr_x = x.trace();
+    let tr_y = y.trace();
+    let xy = x * y;
+    Matrix3x3::identity() * (tr_x * tr_y - xy.trace()) - (x * tr_y + y * tr_x) + xy + y * x
+}
+
+/// Determinant and adjugate of a 1x1, 2x2 or 3x3 position Jacobian, as used by `integrate_elements`.
+///
+/// The Jacobian does not depend on a parameter here (M(μ) = J + 0μ), so only the constant
+/// coefficients c0 of the 1-parameter expansions are returned. A 1x1 Jacobian [a] of a line
+/// element has determinant a and adjugate [1].
+pub fn determinant_and_adjugate_expansions(jac: &Array2<f64>) -> Result<(f64, Array2<f64>), JacobianError> {
+    let (rows, cols) = jac.dim();
+    if rows != cols {
+        return Err(JacobianError::NonSquare { rows, cols });
+    }
+
+    match rows {
+        1 => Ok((jac[[0, 0]], Array2::ones((1, 1)))),
+        2 => {
+            let a = Matrix2x2::from_fn(|i, j| jac[[i, j]]);
+            let expansions = DeterminantAndAdjugateExpansions1Parameter::<2, 1, 3, 2>::new_from_matrix(&a, &Matrix2x2::zeros());
+            let adjugate = expansions.adjugate.0[0];
+            Ok((expansions.determinant.0[0], Array2::from_shape_fn((2, 2), |(i, j)| adjugate[(i, j)])))
+        }
+        3 => {
+            let a = Matrix3x3::from_fn(|i, j| jac[[i, j]]);
+            let expansions = DeterminantAndAdjugateExpansions1Parameter::<3, 1, 4, 3>::new_from_matrix(&a, &Matrix3x3::zeros());
+            let adjugate = expansions.adjugate.0[0];
+            Ok((expansions.determinant.0[0], Array2::from_shape_fn((3, 3), |(i, j)| adjugate[(i, j)])))
+        }
+        dim => Err(JacobianError::UnsupportedDimension(dim)),
+    }
+}
+
+/// Determinant of a 1x1, 2x2 or 3x3 position Jacobian, for callers that do not need the adjugate
+/// returned by `determinant_and_adjugate_expansions`.
+pub fn determinant_expansion(jac: &Array2<f64>) -> Result<f64, JacobianError> {
+    let (rows, cols) = jac.dim();
+    if rows != cols {
+        return Err(JacobianError::NonSquare { rows, cols });
+    }
+
+    match rows {
+        1 => Ok(jac[[0, 0]]),
+        2 => {
+            let a = Matrix2x2::from_fn(|i, j| jac[[i, j]]);
+            Ok(DeterminantExpansion1Parameter::<2, 1, 3>::new_from_matrix(&a, &Matrix2x2::zeros()).0[0])
+        }
+        3 => {
+            let a = Matrix3x3::from_fn(|i, j| jac[[i, j]]);
+            Ok(DeterminantExpansion1Parameter::<3, 1, 4>::new_from_matrix(&a, &Matrix3x3::zeros()).0[0])
+        }
+        dim => Err(JacobianError::UnsupportedDimension(dim)),
+    }
+}
+
 pub struct PowerSeriesCoefficientsVec<T> (Vec<T>); // growable length
 
+struct InverseDeterminant2x2;
+
+impl InverseDeterminant2x2 {
+
+    pub fn polynomial_coefficient(
+        invdet0: f64,
+        h1: f64,
+        h2: f64,
+        order: u8,
+        factor_cache: &combinatorics::FactorialCache
+    ) -> Option<f64> {
+        let terms = combinatorics::expansion_terms_2(order);
+        let mut sum = 0.0f64;
+        
+        for term in terms {
+            let coeff = combinatorics::signed_multinomial_coefficient(term, factor_cache)? as f64;
+            let combinatorics::ExpansionTerm([a, b]) = term; // extract a, b from term
+            
+            sum += coeff * h1.powi(a as i32) * h2.powi(b as i32);
+        }
+        
+        Some(sum * invdet0)
+    }
+
+    /// Computes coefficients of power series up to specified maximum order (vec version)
+    pub fn power_series_coefficients_vec(
+        determinant_expansion: &DeterminantExpansion1Parameter<2,1,3>, // Assuming 2x2 matrix with degree 1
+        maximum_order: u8
+    ) -> Option<PowerSeriesCoefficientsVec<f64>> {
+        if determinant_expansion.0[0] == 0.0 {
+            return None; // Matrix is singular
+        }
+        
+        // Use of recip() instead of powi(-1) for better numerical properties
+        let invdet0 = determinant_expansion.0[0].recip();
+        let h1 = determinant_expansion.0[1] * invdet0;
+        let h2 = determinant_expansion.0[2] * invdet0;
+        
+        // Precompute factorials up to maximum_order for efficiency
+        let factor_cache = combinatorics::FactorialCache::new(maximum_order as usize);
+        
+        let mut coefficients: Vec<f64> = Vec::with_capacity((maximum_order + 1) as usize);
+        coefficients.push(invdet0); // Order 0 coefficient is always invdet0
+        
+        for order in 1..=maximum_order {
+            coefficients.push(
+                Self::polynomial_coefficient(invdet0, h1, h2, order, &factor_cache)?
+            );
+        }
+        
+        Some(PowerSeriesCoefficientsVec(coefficients))
+    }
+
+    /// Computes polynomial coefficients if the length is known at compile time
+    pub fn polynomial_coefficients_fixed_length<const LEN: usize>(
+        determinant_expansion: &DeterminantExpansion1Parameter<2,1,3> // Assuming 2x2 matrix with degree 1
+    ) -> Option<PolynomialCoefficientsFixedLength<f64, LEN>> {
+        if LEN == 0 {
+            return None;
+        }
+
+        if determinant_expansion.0[0] == 0.0 {
+            return None; // Matrix is singular
+        }
+        
+        // Use of recip() instead of powi(-1) for better numerical properties
+        let invdet0 = determinant_expansion.0[0].recip();
+        if LEN == 1 {
+            return Some(PolynomialCoefficientsFixedLength([invdet0; LEN]));
+        }
+
+        let h1 = determinant_expansion.0[1] * invdet0;
+        let h2 = determinant_expansion.0[2] * invdet0;
+
+        let maximum_order: usize = LEN - 1;
+        
+        // Precompute factorials up to maximum_order for efficiency
+        let factor_cache = combinatorics::FactorialCache::new(maximum_order);
+        
+        let mut coefficients: [f64; LEN] = [0.0; LEN]; // Order 0 coefficient is always invdet0
+        coefficients[0] = invdet0;
+        
+        for (order, coefficient) in coefficients.iter_mut().enumerate().skip(1) {
+            *coefficient = Self::polynomial_coefficient(invdet0, h1, h2, order as u8, &factor_cache)?
+        }
+        
+        Some(PolynomialCoefficientsFixedLength(coefficients))
+    }
+}
+
 struct InverseDeterminant3x3;
 
 impl InverseDeterminant3x3 {
@@ -250,7 +788,7 @@ impl InverseDeterminant3x3 {
         
         for term in terms {
             let coeff = combinatorics::signed_multinomial_coefficient(term, factor_cache)? as f64;
-            let combinatorics::ExpansionTerm(a, b, c) = term; // extract a, b, c from term
+            let combinatorics::ExpansionTerm([a, b, c]) = term; // extract a, b, c from term
             
             sum += coeff * h1.powi(a as i32) * h2.powi(b as i32) * h3.powi(c as i32);
         }
@@ -318,8 +856,8 @@ impl InverseDeterminant3x3 {
         let mut coefficients: [f64; LEN] = [0.0; LEN]; // Order 0 coefficient is always invdet0
         coefficients[0] = invdet0;
         
-        for order in 1..=maximum_order {
-            coefficients[order] = Self::polynomial_coefficient(invdet0, h1, h2, h3, order as u8, &factor_cache)?
+        for (order, coefficient) in coefficients.iter_mut().enumerate().skip(1) {
+            *coefficient = Self::polynomial_coefficient(invdet0, h1, h2, h3, order as u8, &factor_cache)?
         }
         
         Some(PolynomialCoefficientsFixedLength(coefficients))
@@ -359,36 +897,44 @@ mod combinatorics {
         }
     }
     
-    /// Represents a term in the expansion (a, b, c exponents)
+    /// Represents a term in the expansion (exponents of h1, h2, ..., hN)
     #[derive(Debug, Copy, Clone)]
-    pub struct ExpansionTerm(pub u8, pub u8, pub u8);
+    pub struct ExpansionTerm<const N: usize>(pub [u8; N]);
     
     /// Calculates multinomial coefficient with sign based on parity
-    pub fn signed_multinomial_coefficient(
-        term: ExpansionTerm,
+    pub fn signed_multinomial_coefficient<const N: usize>(
+        term: ExpansionTerm<N>,
         factorials: &FactorialCache
     ) -> Option<i64> {
-        let ExpansionTerm(a, b, c) = term;
-        let total: u8 = a + b + c;
+        let ExpansionTerm(exponents) = term;
+        let total: u8 = exponents.iter().sum();
         
-        let a_fact: u64 = factorials.get(a as usize)?;
-        let b_fact: u64 = factorials.get(b as usize)?;
-        let c_fact: u64 = factorials.get(c as usize)?;
+        let mut denominator: u64 = 1;
+        for &exponent in exponents.iter() {
+            denominator = denominator.checked_mul(factorials.get(exponent as usize)?)?;
+        }
         let total_fact: u64 = factorials.get(total as usize)?;
         
-        let denominator: u64 = a_fact.checked_mul(b_fact)?.checked_mul(c_fact)?;
         let raw_coeff: i64 = total_fact.checked_div(denominator)? as i64;
         
         Some(if total & 1 == 1 { -raw_coeff } else { raw_coeff })
     }
     
+    /// Finds all pairs (a, b) such that a + 2b = order
+    pub fn expansion_terms_2(order: u8) -> impl Iterator<Item = ExpansionTerm<2>> {
+        (0..=order / 2).map(move |b: u8| {
+            let a: u8 = order - 2 * b;
+            ExpansionTerm([a, b])
+        })
+    }
+    
     /// Finds all triples (a, b, c) such that a + 2b + 3c = order
-    pub fn expansion_terms(order: u8) -> impl Iterator<Item = ExpansionTerm> {
+    pub fn expansion_terms(order: u8) -> impl Iterator<Item = ExpansionTerm<3>> {
         (0..=order / 3).flat_map(move |c: u8| {
             let remaining_after_c: u8 = order - 3 * c;
             (0..=remaining_after_c / 2).map(move |b: u8| {
                 let a: u8 = order - 2 * b - 3 * c;
-                ExpansionTerm(a, b, c)
+                ExpansionTerm([a, b, c])
             })
         })
     }
@@ -396,7 +942,29 @@ mod combinatorics {
 
 impl<const LEN: usize> PolynomialCoefficientsFixedLength<f64, LEN> {
 
-    /// Helper function for polynomial multiplication
+    /// Evaluates the polynomial at μ using Horner's scheme
+    pub fn evaluate(&self, mu: f64) -> f64 {
+        self.0.iter().rev().fold(0.0, |acc, &coeff| acc * mu + coeff)
+    }
+
+    /// Coefficientwise polynomial addition
+    pub fn add(&self, other: &Self) -> Self {
+        let mut result = self.0;
+        for (coeff, &other_coeff) in result.iter_mut().zip(other.0.iter()) {
+            *coeff += other_coeff;
+        }
+        PolynomialCoefficientsFixedLength(result)
+    }
+
+    /// Multiplies every coefficient by a scalar factor
+    pub fn scale(&self, factor: f64) -> Self {
+        PolynomialCoefficientsFixedLength(self.0.map(|coeff| coeff * factor))
+    }
+
+    /// Helper function for polynomial multiplication.
+    ///
+    /// Terms of degree >= LEN are dropped on purpose, since expansions are only needed up to a
+    /// fixed order. Use `resize` beforehand to keep higher-order terms of products and powers.
     pub fn mul_polynomial(&self, b: &Self) -> Self {
         let mut result = [0.0; LEN];
         
@@ -411,6 +979,15 @@ impl<const LEN: usize> PolynomialCoefficientsFixedLength<f64, LEN> {
         PolynomialCoefficientsFixedLength(result)
     }
 
+    /// Copies the first min(LEN, NEW_LEN) coefficients into a polynomial of length NEW_LEN,
+    /// zero-filling the remaining ones
+    pub fn resize<const NEW_LEN: usize>(&self) -> PolynomialCoefficientsFixedLength<f64, NEW_LEN> {
+        let mut result = [0.0; NEW_LEN];
+        let len = LEN.min(NEW_LEN);
+        result[..len].copy_from_slice(&self.0[..len]);
+        PolynomialCoefficientsFixedLength(result)
+    }
+
     pub fn pow(&self, power: u8) -> Self {
         // Initialize output array with zeros
         let mut result: PolynomialCoefficientsFixedLength<f64, LEN> = PolynomialCoefficientsFixedLength([0.0; LEN]);
@@ -445,8 +1022,25 @@ impl<const LEN: usize> PolynomialCoefficientsFixedLength<f64, LEN> {
     }
 }
 
-#[cfg(not(test))]
+impl<const LEN: usize> std::ops::Add for PolynomialCoefficientsFixedLength<f64, LEN> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        PolynomialCoefficientsFixedLength::add(&self, &other)
+    }
+}
+
+impl<const LEN: usize> std::ops::Mul<f64> for PolynomialCoefficientsFixedLength<f64, LEN> {
+    type Output = Self;
+
+    fn mul(self, factor: f64) -> Self {
+        self.scale(factor)
+    }
+}
+
+#[cfg(test)]
 mod tests {
+    use super::*;
 
     #[test]
     fn test_polynomial_pow() {
@@ -479,4 +1073,398 @@ mod tests {
         println!("{:?}", result.0);
         assert_eq!(result.0, [1.0, 8.0, 24.0, 32.0]);
     }
+
+    #[test]
+    fn test_polynomial_resize() {
+
+        // (1 + 2x)^4 = 1 + 8x + 24x^2 + 32x^3 + 16x^4 only fits from LEN = 5 on
+        let coeffs: PolynomialCoefficientsFixedLength<f64, 4> = PolynomialCoefficientsFixedLength([1.0, 2.0, 0.0, 0.0]);
+        assert_eq!(coeffs.pow(4).0, [1.0, 8.0, 24.0, 32.0]);
+
+        let extended: PolynomialCoefficientsFixedLength<f64, 6> = coeffs.resize();
+        assert_eq!(extended.0, [1.0, 2.0, 0.0, 0.0, 0.0, 0.0]);
+        assert_eq!(extended.pow(4).0, [1.0, 8.0, 24.0, 32.0, 16.0, 0.0]);
+
+        // Shrinking truncates
+        let truncated: PolynomialCoefficientsFixedLength<f64, 2> = extended.pow(4).resize();
+        assert_eq!(truncated.0, [1.0, 8.0]);
+    }
+
+    #[test]
+    fn test_polynomial_add_and_scale() {
+
+        // (1 + 2x) + (3 + x) = 4 + 3x
+        let p: PolynomialCoefficientsFixedLength<f64, 3> = PolynomialCoefficientsFixedLength([1.0, 2.0, 0.0]);
+        let q: PolynomialCoefficientsFixedLength<f64, 3> = PolynomialCoefficientsFixedLength([3.0, 1.0, 0.0]);
+        assert_eq!(p.add(&q).0, [4.0, 3.0, 0.0]);
+        assert_eq!((p.clone() + q.clone()).0, [4.0, 3.0, 0.0]);
+
+        // Scaling
+        assert_eq!(p.scale(2.0).0, [2.0, 4.0, 0.0]);
+        assert_eq!((p.clone() * 2.0).0, [2.0, 4.0, 0.0]);
+
+        // Scaling distributes over mul_polynomial: (2p) * q = 2 (p * q)
+        let lhs = p.scale(2.0).mul_polynomial(&q);
+        let rhs = p.mul_polynomial(&q).scale(2.0);
+        assert_eq!(lhs.0, rhs.0);
+        assert_eq!(lhs.0, [6.0, 14.0, 4.0]);
+    }
+
+    #[test]
+    fn test_evaluate_2x2() {
+
+        let a = Matrix2x2::new(1.0, 2.0, 3.0, 4.0);
+        let b = Matrix2x2::new(0.5, -0.3, 0.2, 0.7);
+
+        let expansions = DeterminantAndAdjugateExpansions1Parameter::<2, 1, 3, 2>::new_from_matrix(&a, &b);
+
+        for mu in [-1.5, 0.0, 0.3, 2.0] {
+            let m = a + b * mu;
+            let expected_det = m.determinant();
+            let expected_adj = m.try_inverse().unwrap() * expected_det;
+
+            assert!((expansions.determinant().evaluate(mu) - expected_det).abs() < 1e-12);
+            assert!((expansions.adjugate().evaluate(mu) - expected_adj).abs().max() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_evaluate_3x3() {
+
+        let a = Matrix3x3::new(
+            2.0, 0.5, 0.1,
+            0.3, 3.0, 0.4,
+            0.2, 0.6, 1.5,
+        );
+        let b = Matrix3x3::new(
+            0.4, -0.1, 0.2,
+            0.3, 0.5, -0.2,
+            -0.1, 0.2, 0.6,
+        );
+
+        let expansions = DeterminantAndAdjugateExpansions1Parameter::<3, 1, 4, 3>::new_from_matrix(&a, &b);
+
+        for mu in [-1.5, 0.0, 0.3, 2.0] {
+            let m = a + b * mu;
+            let expected_det = m.determinant();
+            let expected_adj = m.try_inverse().unwrap() * expected_det;
+
+            assert!((expansions.determinant.evaluate(mu) - expected_det).abs() < 1e-12);
+            assert!((expansions.adjugate.evaluate(mu) - expected_adj).abs().max() < 1e-12);
+        }
+    }
+
+    /// Adjugate from the cofactors, independent of the Cayley-Hamilton form and of invertibility
+    fn cofactor_adjugate3x3(m: &Matrix3x3) -> Matrix3x3 {
+        Matrix3x3::from_fn(|i, j| {
+            // adj(M)_ij is the (j, i) cofactor
+            let rows: Vec<usize> = (0..3).filter(|&r| r != j).collect();
+            let cols: Vec<usize> = (0..3).filter(|&c| c != i).collect();
+            let minor = m[(rows[0], cols[0])] * m[(rows[1], cols[1])] - m[(rows[0], cols[1])] * m[(rows[1], cols[0])];
+            if (i + j) % 2 == 0 { minor } else { -minor }
+        })
+    }
+
+    #[test]
+    fn test_expansion_3x3_matches_random_matrices() {
+        use rand::{rng, Rng};
+
+        let mut rng = rng();
+        for _ in 0..200 {
+            let a = Matrix3x3::from_fn(|_, _| rng.random_range(-1.0..1.0));
+            let b = Matrix3x3::from_fn(|_, _| rng.random_range(-1.0..1.0));
+            let expansions = DeterminantAndAdjugateExpansions1Parameter::<3, 1, 4, 3>::new_from_matrix(&a, &b);
+
+            for _ in 0..10 {
+                let mu: f64 = rng.random_range(-2.0..2.0);
+                let m = a + b * mu;
+
+                let det_error = (expansions.determinant().evaluate(mu) - m.determinant()).abs();
+                assert!(det_error < 1e-9, "det mismatch {} for a = {}, b = {}, mu = {}", det_error, a, b, mu);
+
+                let adj_error = (expansions.adjugate().evaluate(mu) - cofactor_adjugate3x3(&m)).abs().max();
+                assert!(adj_error < 1e-9, "adjugate mismatch {} for a = {}, b = {}, mu = {}", adj_error, a, b, mu);
+            }
+        }
+    }
+
+    #[test]
+    fn test_faddeev_matches_closed_form() {
+        use rand::{rng, Rng};
+
+        let mut rng = rng();
+        for _ in 0..50 {
+            let a2 = Matrix2x2::from_fn(|_, _| rng.random_range(-1.0..1.0));
+            let b2 = Matrix2x2::from_fn(|_, _| rng.random_range(-1.0..1.0));
+            let closed_form = DeterminantAndAdjugateExpansions1Parameter::<2, 1, 3, 2>::new_from_matrix(&a2, &b2);
+            let generic: PolynomialCoefficientsFixedLength<f64, 3> = determinant_expansion_faddeev(&a2, &b2);
+            for (c, g) in closed_form.determinant().iter().zip(generic.iter()) {
+                assert!((c - g).abs() < 1e-9);
+            }
+
+            let a3 = Matrix3x3::from_fn(|_, _| rng.random_range(-1.0..1.0));
+            let b3 = Matrix3x3::from_fn(|_, _| rng.random_range(-1.0..1.0));
+            let closed_form = DeterminantAndAdjugateExpansions1Parameter::<3, 1, 4, 3>::new_from_matrix(&a3, &b3);
+            let generic: PolynomialCoefficientsFixedLength<f64, 4> = determinant_expansion_faddeev(&a3, &b3);
+            for (c, g) in closed_form.determinant().iter().zip(generic.iter()) {
+                assert!((c - g).abs() < 1e-9);
+            }
+
+            let a4 = Matrix4x4::from_fn(|_, _| rng.random_range(-1.0..1.0));
+            let b4 = Matrix4x4::from_fn(|_, _| rng.random_range(-1.0..1.0));
+            let closed_form = DeterminantAndAdjugateExpansions1Parameter::<4, 1, 5, 4>::new_from_matrix(&a4, &b4);
+            let generic: PolynomialCoefficientsFixedLength<f64, 5> = determinant_expansion_faddeev(&a4, &b4);
+            for (c, g) in closed_form.determinant().iter().zip(generic.iter()) {
+                assert!((c - g).abs() < 1e-9);
+            }
+        }
+    }
+
+    #[test]
+    fn test_new_from_matrix_doc_example() {
+        // Same matrices as the example on new_from_matrix, through the public accessors only
+        let a = Matrix2x2::new(1.0, 2.0, 3.0, 4.0);
+        let b = Matrix2x2::new(0.5, 0.5, 0.5, 0.5);
+        let expansions = DeterminantAndAdjugateExpansions1Parameter::<2, 1, 3, 2>::new_from_matrix(&a, &b);
+
+        // det(A + Bμ) = -2 + 0μ + 0μ²
+        let faddeev: PolynomialCoefficientsFixedLength<f64, 3> = determinant_expansion_faddeev(&a, &b);
+        for (c, (g, expected)) in expansions.determinant().iter().zip(faddeev.iter().zip([-2.0, 0.0, 0.0])) {
+            assert!((c - g).abs() < 1e-12);
+            assert!((c - expected).abs() < 1e-12);
+        }
+        let mu = 0.5;
+        assert!((expansions.determinant().evaluate(mu) - (a + b * mu).determinant()).abs() < 1e-12);
+        assert_eq!(expansions.adjugate().iter().count(), 2);
+
+        let a = Matrix3x3::new(2.0, 0.5, 0.1, 0.3, 3.0, 0.4, 0.2, 0.6, 1.5);
+        let b = Matrix3x3::new(0.4, -0.1, 0.2, 0.3, 0.5, -0.2, -0.1, 0.2, 0.6);
+        let expansions = DeterminantAndAdjugateExpansions1Parameter::<3, 1, 4, 3>::new_from_matrix(&a, &b);
+        let faddeev: PolynomialCoefficientsFixedLength<f64, 4> = determinant_expansion_faddeev(&a, &b);
+        for (c, g) in expansions.determinant().iter().zip(faddeev.iter()) {
+            assert!((c - g).abs() < 1e-12);
+        }
+        assert!((expansions.adjugate().evaluate(mu) - cofactor_adjugate3x3(&(a + b * mu))).abs().max() < 1e-12);
+        assert_eq!(expansions.adjugate().iter().count(), 3);
+    }
+
+    #[test]
+    fn test_determinant_only_matches_full_expansion() {
+        use rand::{rng, Rng};
+
+        let mut rng = rng();
+        for _ in 0..100 {
+            let a2 = Matrix2x2::from_fn(|_, _| rng.random_range(-1.0..1.0));
+            let b2 = Matrix2x2::from_fn(|_, _| rng.random_range(-1.0..1.0));
+            let full = DeterminantAndAdjugateExpansions1Parameter::<2, 1, 3, 2>::new_from_matrix(&a2, &b2);
+            let determinant_only = DeterminantExpansion1Parameter::<2, 1, 3>::new_from_matrix(&a2, &b2);
+            for (d, f) in determinant_only.iter().zip(full.determinant().iter()) {
+                assert!((d - f).abs() < 1e-14);
+            }
+
+            let a3 = Matrix3x3::from_fn(|_, _| rng.random_range(-1.0..1.0));
+            let b3 = Matrix3x3::from_fn(|_, _| rng.random_range(-1.0..1.0));
+            let full = DeterminantAndAdjugateExpansions1Parameter::<3, 1, 4, 3>::new_from_matrix(&a3, &b3);
+            let determinant_only = DeterminantExpansion1Parameter::<3, 1, 4>::new_from_matrix(&a3, &b3);
+            for (d, f) in determinant_only.iter().zip(full.determinant().iter()) {
+                assert!((d - f).abs() < 1e-12);
+            }
+        }
+
+        let jac_3 = ndarray::array![[2.0, 0.5, 0.0], [0.1, 1.5, 0.3], [0.0, 0.2, 1.0]];
+        assert_eq!(determinant_expansion(&jac_3).unwrap(), determinant_and_adjugate_expansions(&jac_3).unwrap().0);
+        assert!(matches!(determinant_expansion(&Array2::zeros((4, 4))), Err(JacobianError::UnsupportedDimension(4))));
+
+        let jac_1 = ndarray::array![[0.25]];
+        assert_eq!(determinant_and_adjugate_expansions(&jac_1).unwrap(), (0.25, ndarray::array![[1.0]]));
+        assert_eq!(determinant_expansion(&jac_1).unwrap(), 0.25);
+    }
+
+    #[test]
+    fn test_faddeev_singular_matrices() {
+        // Both A and B singular: det(A + Bμ) = μ
+        let a = Matrix2x2::new(1.0, 0.0, 0.0, 0.0);
+        let b = Matrix2x2::new(0.0, 0.0, 0.0, 1.0);
+        let generic: PolynomialCoefficientsFixedLength<f64, 3> = determinant_expansion_faddeev(&a, &b);
+        for (g, expected) in generic.iter().zip([0.0, 1.0, 0.0]) {
+            assert!((g - expected).abs() < 1e-12);
+        }
+
+        // Singular pencil
+        let generic: PolynomialCoefficientsFixedLength<f64, 3> = determinant_expansion_faddeev(&a, &a);
+        assert_eq!(generic.0, [0.0; 3]);
+    }
+
+    #[test]
+    fn test_bivariate_mul_polynomial() {
+
+        // (1 + μ)(1 + ν) = 1 + μ + ν + μν
+        let p: BivariatePolynomialCoefficientsFixedLength<f64, 3> =
+            BivariatePolynomialCoefficientsFixedLength([[1.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 0.0, 0.0]]);
+        let q: BivariatePolynomialCoefficientsFixedLength<f64, 3> =
+            BivariatePolynomialCoefficientsFixedLength([[1.0, 1.0, 0.0], [0.0, 0.0, 0.0], [0.0, 0.0, 0.0]]);
+
+        let result = p.mul_polynomial(&q);
+        assert_eq!(result.0, [[1.0, 1.0, 0.0], [1.0, 1.0, 0.0], [0.0, 0.0, 0.0]]);
+        assert_eq!(result.evaluate(2.0, 3.0), 12.0);
+    }
+
+    #[test]
+    fn test_two_parameter_expansion_2x2() {
+
+        let a = Matrix2x2::new(1.0, 2.0, 3.0, 4.0);
+        let b = Matrix2x2::new(0.5, -0.3, 0.2, 0.7);
+        let c = Matrix2x2::new(-0.4, 0.1, 0.6, 0.3);
+
+        let expansions = DeterminantAndAdjugateExpansions2Parameters::<2, 1, 3, 2>::new_from_matrix(&a, &b, &c);
+
+        for (mu, nu) in [(0.0, 0.0), (0.5, -1.0), (-1.2, 0.7), (2.0, 1.5)] {
+            let m = a + b * mu + c * nu;
+            let expected_det = m.determinant();
+            let expected_adj = m.try_inverse().unwrap() * expected_det;
+
+            let (det, adj) = expansions.evaluate(mu, nu);
+            assert!((det - expected_det).abs() < 1e-12, "det mismatch at ({}, {})", mu, nu);
+            assert!((adj - expected_adj).abs().max() < 1e-12, "adjugate mismatch at ({}, {})", mu, nu);
+        }
+    }
+
+    #[test]
+    fn test_two_parameter_expansion_3x3() {
+
+        let a = Matrix3x3::new(
+            2.0, 0.5, 0.1,
+            0.3, 3.0, 0.4,
+            0.2, 0.6, 1.5,
+        );
+        let b = Matrix3x3::new(
+            0.4, -0.1, 0.2,
+            0.3, 0.5, -0.2,
+            -0.1, 0.2, 0.6,
+        );
+        let c = Matrix3x3::new(
+            -0.2, 0.3, 0.0,
+            0.1, 0.4, 0.5,
+            0.6, -0.3, 0.2,
+        );
+
+        let expansions = DeterminantAndAdjugateExpansions2Parameters::<3, 1, 4, 3>::new_from_matrix(&a, &b, &c);
+
+        for (mu, nu) in [(0.0, 0.0), (0.5, -1.0), (-1.2, 0.7), (2.0, 1.5)] {
+            let m = a + b * mu + c * nu;
+            let expected_det = m.determinant();
+            let expected_adj = m.try_inverse().unwrap() * expected_det;
+
+            let (det, adj) = expansions.evaluate(mu, nu);
+            assert!((det - expected_det).abs() < 1e-12, "det mismatch at ({}, {})", mu, nu);
+            assert!((adj - expected_adj).abs().max() < 1e-12, "adjugate mismatch at ({}, {})", mu, nu);
+        }
+    }
+
+    #[test]
+    fn test_two_parameter_accessors() {
+        // Through the public constructors and accessors only
+        let a = Matrix2x2::new(1.0, 2.0, 3.0, 4.0);
+        let b = Matrix2x2::new(0.5, -0.3, 0.2, 0.7);
+        let c = Matrix2x2::new(-0.4, 0.1, 0.6, 0.3);
+        let expansions = DeterminantAndAdjugateExpansions2Parameters::<2, 1, 3, 2>::new_from_matrix(&a, &b, &c);
+
+        // The pure μ² and ν² coefficients of det(A + Bμ + Cν) are det(B) and det(C)
+        let det: Vec<[f64; 3]> = expansions.determinant().iter().copied().collect();
+        assert!((det[0][0] - a.determinant()).abs() < 1e-12);
+        assert!((det[2][0] - b.determinant()).abs() < 1e-12);
+        assert!((det[0][2] - c.determinant()).abs() < 1e-12);
+        assert!((expansions.determinant().evaluate(0.5, -1.0) - (a + b * 0.5 - c).determinant()).abs() < 1e-12);
+        assert_eq!(expansions.adjugate().iter().count(), 2);
+
+        let a = Matrix3x3::new(2.0, 0.5, 0.1, 0.3, 3.0, 0.4, 0.2, 0.6, 1.5);
+        let b = Matrix3x3::new(0.4, -0.1, 0.2, 0.3, 0.5, -0.2, -0.1, 0.2, 0.6);
+        let c = Matrix3x3::new(-0.2, 0.3, 0.0, 0.1, 0.4, 0.5, 0.6, -0.3, 0.2);
+        let expansions = DeterminantAndAdjugateExpansions2Parameters::<3, 1, 4, 3>::new_from_matrix(&a, &b, &c);
+
+        let (mu, nu) = (-1.2, 0.7);
+        let m = a + b * mu + c * nu;
+        assert!((expansions.determinant().evaluate(mu, nu) - m.determinant()).abs() < 1e-12);
+        assert!((expansions.adjugate().evaluate(mu, nu) - cofactor_adjugate3x3(&m)).abs().max() < 1e-12);
+        assert!((expansions.adjugate().iter().next().unwrap()[0] - cofactor_adjugate3x3(&a)).abs().max() < 1e-12);
+        assert_eq!(expansions.adjugate().iter().count(), 3);
+    }
+
+    #[test]
+    fn test_determinant_and_adjugate_4x4() {
+
+        let a = Matrix4x4::new(
+            4.0, 1.0, 0.5, 0.0,
+            1.0, 3.0, 0.2, 0.7,
+            0.3, 0.1, 5.0, 1.2,
+            0.0, 0.9, 1.1, 2.0,
+        );
+        let b = Matrix4x4::new(
+            0.5, -0.2, 0.1, 0.3,
+            0.4, 1.0, -0.6, 0.2,
+            -0.3, 0.8, 0.2, 0.5,
+            0.1, 0.0, 0.7, -0.4,
+        );
+
+        let expansions = DeterminantAndAdjugateExpansions1Parameter::<4, 1, 5, 4>::new_from_matrix(&a, &b);
+
+        for mu in [-1.3, -0.5, 0.0, 0.25, 0.8, 2.0] {
+            let m = a + b * mu;
+
+            let det: f64 = expansions.determinant.evaluate(mu);
+            let adj: Matrix4x4 = expansions.adjugate.evaluate(mu);
+
+            let expected_det = m.determinant();
+            let expected_adj = m.try_inverse().unwrap() * expected_det;
+
+            assert!((det - expected_det).abs() < 1e-10, "det mismatch at mu={}: {} vs {}", mu, det, expected_det);
+            assert!((adj - expected_adj).abs().max() < 1e-10, "adjugate mismatch at mu={}", mu);
+        }
+    }
+
+    #[test]
+    fn test_inverse_determinant_2x2() {
+
+        let (c0, c1, c2) = (2.0, 0.6, 0.3);
+        let det_coeffs: DeterminantExpansion1Parameter<2, 1, 3> = PolynomialCoefficientsFixedLength([c0, c1, c2]);
+
+        let series = InverseDeterminant2x2::power_series_coefficients_vec(&det_coeffs, 15).unwrap();
+        let fixed = InverseDeterminant2x2::polynomial_coefficients_fixed_length::<16>(&det_coeffs).unwrap();
+
+        assert_eq!(series.0.len(), 16);
+        for (a, b) in series.0.iter().zip(fixed.iter()) {
+            assert!((a - b).abs() < 1e-14);
+        }
+
+        for mu in [-0.1, 0.0, 0.05, 0.1] {
+            let approx: f64 = fixed.evaluate(mu);
+            let expected: f64 = 1.0 / (c0 + c1 * mu + c2 * mu * mu);
+            assert!((approx - expected).abs() < 1e-12, "mismatch at mu={}: {} vs {}", mu, approx, expected);
+        }
+
+        // Singular matrix
+        let singular: DeterminantExpansion1Parameter<2, 1, 3> = PolynomialCoefficientsFixedLength([0.0, 1.0, 1.0]);
+        assert!(InverseDeterminant2x2::power_series_coefficients_vec(&singular, 3).is_none());
+        assert!(InverseDeterminant2x2::polynomial_coefficients_fixed_length::<4>(&singular).is_none());
+    }
+    #[test]
+    fn test_determinant_and_adjugate_expansions_ndarray() {
+        let jac_2 = ndarray::array![[2.0, 1.0], [0.5, 3.0]];
+        let (det, adj) = determinant_and_adjugate_expansions(&jac_2).unwrap();
+        assert!((det - 5.5).abs() < 1e-14);
+        assert_eq!(adj, ndarray::array![[3.0, -1.0], [-0.5, 2.0]]);
+
+        let jac_3 = ndarray::array![[2.0, 0.5, 0.0], [0.1, 1.5, 0.3], [0.0, 0.2, 1.0]];
+        let (det, adj) = determinant_and_adjugate_expansions(&jac_3).unwrap();
+        let identity = jac_3.dot(&adj) / det;
+        for i in 0..3 {
+            for j in 0..3 {
+                assert!((identity[[i, j]] - if i == j { 1.0 } else { 0.0 }).abs() < 1e-14);
+            }
+        }
+        assert!((det - (2.0 * (1.5 - 0.06) - 0.5 * 0.1)).abs() < 1e-14);
+
+        assert!(matches!(determinant_and_adjugate_expansions(&Array2::zeros((2, 3))), Err(JacobianError::NonSquare { rows: 2, cols: 3 })));
+        assert!(matches!(determinant_and_adjugate_expansions(&Array2::zeros((4, 4))), Err(JacobianError::UnsupportedDimension(4))));
+    }
 }
\ No newline at end of file
@@ -0,0 +1,220 @@
+//! # Linear Elastic Element Stiffness
+//!
+//! Element stiffness matrix of isotropic linear elasticity, K = ∫ Bᵀ D B dV, integrated with a
+//! Gauss-Legendre rule that is exact for undistorted elements.
+//!
+//! Strains use Voigt notation with engineering shear strains:
+//! - 2D: [εxx, εyy, γxy], plane strain
+//! - 3D: [εxx, εyy, εzz, γyz, γxz, γxy]
+//!
+//! Degrees of freedom are ordered node by node, (u_x, u_y, u_z) of node 0 first, which matches
+//! the `dimension x dimension` blocks of `assembly::scatter_element_matrix`.
+
+use ndarray::{array, Array2};
+
+use crate::elements::element_library::hypercube_elements::ShapeFunctionKind;
+use crate::elements::parametric_topology_element::position_jacobian::{
+    compute_position_jacobian, jacobian_determinant_and_inverse, JacobianError,
+};
+use crate::elements::quadrature::quadrature_rules::QuadratureRuleDyn;
+
+/// Dense (n_nodes * dim, n_nodes * dim) stiffness matrix of one isotropic linear elastic element.
+///
+/// `coords` has shape (dim, total_nodes) with dim = `kind.dimension()`, and `element` holds the
+/// global node ids of the element in the local order of `kind`. Returns
+/// `ElasticityError::UnsupportedElement` for line elements, and `ElasticityError::Jacobian` if the
+/// position Jacobian is singular at some quadrature point.
+///
+/// # Panics
+/// Panics if the coordinate dimension differs from `kind.dimension()`.
+pub fn element_stiffness_elasticity(
+    coords: &Array2<f64>,
+    element: &[u32],
+    kind: ShapeFunctionKind,
+    young: f64,
+    poisson: f64,
+) -> Result<Array2<f64>, ElasticityError> {
+    let dim: usize = kind.dimension() as usize;
+    if dim < 2 {
+        return Err(ElasticityError::UnsupportedElement(kind));
+    }
+    let n_dofs: usize = element.len() * dim;
+    let num_points: usize = match kind {
+        ShapeFunctionKind::Square1 | ShapeFunctionKind::Cube1 => 2,
+        _ => 3,
+    };
+    let rule = QuadratureRuleDyn::gauss_legendre(num_points, dim);
+    let constitutive: Array2<f64> = constitutive_matrix(dim, young, poisson);
+
+    let mut stiffness: Array2<f64> = Array2::zeros((n_dofs, n_dofs));
+    for (point, &weight) in rule.iter() {
+        let jacobian_shape_functions = kind.evaluate_jacobian_of_shape_functions(point);
+        let position_jacobian = compute_position_jacobian(coords, element, &jacobian_shape_functions);
+        let (determinant, inverse) = jacobian_determinant_and_inverse(&position_jacobian)?;
+
+        // Rows are the physical gradients ∇N_i = dN_i/dξ J⁻¹
+        let gradients: Array2<f64> = jacobian_shape_functions.dot(&inverse);
+        let strain_displacement: Array2<f64> = strain_displacement_matrix(&gradients);
+
+        stiffness += &(strain_displacement.t().dot(&constitutive).dot(&strain_displacement) * (determinant * weight));
+    }
+
+    Ok(stiffness)
+}
+
+/// Errors of `element_stiffness_elasticity`
+#[derive(Debug, Clone, PartialEq)]
+pub enum ElasticityError {
+    /// The element type has no linear elastic stiffness
+    UnsupportedElement(ShapeFunctionKind),
+    /// The position Jacobian is unsupported or singular
+    Jacobian(JacobianError),
+}
+
+impl std::fmt::Display for ElasticityError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ElasticityError::UnsupportedElement(kind) => {
+                write!(f, "Linear elasticity is not supported for elements of type {:?}", kind)
+            }
+            ElasticityError::Jacobian(err) => write!(f, "Jacobian error: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for ElasticityError {}
+
+impl From<JacobianError> for ElasticityError {
+    fn from(err: JacobianError) -> Self {
+        ElasticityError::Jacobian(err)
+    }
+}
+
+/// Isotropic constitutive matrix D in Voigt notation (plane strain in 2D)
+fn constitutive_matrix(dim: usize, young: f64, poisson: f64) -> Array2<f64> {
+    let lambda: f64 = young * poisson / ((1.0 + poisson) * (1.0 - 2.0 * poisson));
+    let mu: f64 = young / (2.0 * (1.0 + poisson));
+    let diagonal: f64 = lambda + 2.0 * mu;
+
+    match dim {
+        2 => array![
+            [diagonal, lambda, 0.0],
+            [lambda, diagonal, 0.0],
+            [0.0, 0.0, mu],
+        ],
+        _ => {
+            let mut d = Array2::zeros((6, 6));
+            for i in 0..3 {
+                for j in 0..3 {
+                    d[[i, j]] = if i == j { diagonal } else { lambda };
+                }
+                d[[i + 3, i + 3]] = mu;
+            }
+            d
+        }
+    }
+}
+
+/// Strain-displacement matrix B (n_strains, n_nodes * dim) from the physical gradients (n_nodes, dim)
+fn strain_displacement_matrix(gradients: &Array2<f64>) -> Array2<f64> {
+    let (n_nodes, dim) = gradients.dim();
+    let n_strains: usize = dim * (dim + 1) / 2;
+    let mut b: Array2<f64> = Array2::zeros((n_strains, n_nodes * dim));
+
+    // Shear strains pair the axes (1, 2), (0, 2), (0, 1) in 3D and (0, 1) in 2D
+    let shear_pairs: &[(usize, usize)] = if dim == 2 { &[(0, 1)] } else { &[(1, 2), (0, 2), (0, 1)] };
+
+    for (i, gradient) in gradients.rows().into_iter().enumerate() {
+        let column = i * dim;
+        for d in 0..dim {
+            b[[d, column + d]] = gradient[d];
+        }
+        for (s, &(p, q)) in shear_pairs.iter().enumerate() {
+            b[[dim + s, column + p]] = gradient[q];
+            b[[dim + s, column + q]] = gradient[p];
+        }
+    }
+    b
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Nodal displacements u = ε x of a uniform strain ε, ordered node by node
+    fn uniform_strain_displacements(coords: &Array2<f64>, strain: &Array2<f64>) -> Vec<f64> {
+        coords.columns().into_iter().flat_map(|x| strain.dot(&x).to_vec()).collect()
+    }
+
+    #[test]
+    fn test_square_matches_analytic_stiffness() {
+        // Unit square, plane strain with ν = 0: diagonal entries (λ + 2μ)/3 + μ/3 = E/2
+        let coords = array![[0.0, 1.0, 0.0, 1.0], [0.0, 0.0, 1.0, 1.0]];
+        let stiffness = element_stiffness_elasticity(&coords, &[0, 1, 2, 3], ShapeFunctionKind::Square1, 12.0, 0.0).unwrap();
+        assert_eq!(stiffness.dim(), (8, 8));
+        for i in 0..8 {
+            assert!((stiffness[[i, i]] - 6.0).abs() < 1e-12);
+        }
+        // u_x of node 0 and u_y of node 3 (opposite corners): -(λ + μ)/4 = -E/8
+        assert!((stiffness[[0, 7]] + 1.5).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_hexahedron_uniform_strain_energy() {
+        let (young, poisson) = (200.0, 0.3);
+        let lambda = young * poisson / ((1.0 + poisson) * (1.0 - 2.0 * poisson));
+        let mu = young / (2.0 * (1.0 + poisson));
+
+        for kind in [ShapeFunctionKind::Cube1, ShapeFunctionKind::Cube2, ShapeFunctionKind::CubeSerendipity] {
+            let nodes = kind.reference_nodes();
+            let coords = Array2::from_shape_fn((3, nodes.len()), |(d, i)| nodes[i][d] * [2.0, 1.0, 0.5][d]);
+            let element: Vec<u32> = (0..nodes.len() as u32).collect();
+            let stiffness = element_stiffness_elasticity(&coords, &element, kind, young, poisson).unwrap();
+
+            // Symmetric
+            for ((i, j), &k) in stiffness.indexed_iter() {
+                assert!((k - stiffness[[j, i]]).abs() < 1e-9);
+            }
+
+            // Strain energy of a uniform strain: uᵀ K u = (λ tr(ε)² + 2μ ε:ε) V
+            let strain = array![[1e-3, 2e-4, -5e-4], [2e-4, -3e-4, 1e-4], [-5e-4, 1e-4, 4e-4]];
+            let u = ndarray::Array1::from(uniform_strain_displacements(&coords, &strain));
+            let trace: f64 = strain.diag().sum();
+            let volume = 2.0 * 1.0 * 0.5;
+            let expected = (lambda * trace * trace + 2.0 * mu * strain.iter().map(|e| e * e).sum::<f64>()) * volume;
+            assert!((u.dot(&stiffness.dot(&u)) - expected).abs() < 1e-12);
+
+            // Rigid rotation about z carries no force
+            let rotation = array![[0.0, -1.0, 0.0], [1.0, 0.0, 0.0], [0.0, 0.0, 0.0]];
+            let u = ndarray::Array1::from(uniform_strain_displacements(&coords, &rotation));
+            assert!(stiffness.dot(&u).iter().all(|f| f.abs() < 1e-9));
+        }
+    }
+
+    #[test]
+    fn test_quadrilateral_patch_and_singular_element() {
+        // Distorted quadrilateral: the bilinear element represents a uniform strain exactly
+        let coords = array![[0.0, 2.0, 0.2, 1.7], [0.0, 0.1, 1.0, 1.4]];
+        let (young, poisson) = (1.0, 0.25);
+        let stiffness = element_stiffness_elasticity(&coords, &[0, 1, 2, 3], ShapeFunctionKind::Square1, young, poisson).unwrap();
+
+        // Uniform strain εxx = 1: energy = (λ + 2μ) * area
+        let strain = array![[1.0, 0.0], [0.0, 0.0]];
+        let u = ndarray::Array1::from(uniform_strain_displacements(&coords, &strain));
+        let area: f64 = 0.5 * ((2.0 * 1.4 - 0.1 * 1.7) + (1.7 * 1.0 - 1.4 * 0.2));
+        let lambda = young * poisson / ((1.0 + poisson) * (1.0 - 2.0 * poisson));
+        let mu = young / (2.0 * (1.0 + poisson));
+        assert!((u.dot(&stiffness.dot(&u)) - (lambda + 2.0 * mu) * area).abs() < 1e-12);
+
+        let collapsed = array![[0.0, 1.0, 0.0, 1.0], [0.0, 0.0, 0.0, 0.0]];
+        let result = element_stiffness_elasticity(&collapsed, &[0, 1, 2, 3], ShapeFunctionKind::Square1, young, poisson);
+        assert!(matches!(result, Err(ElasticityError::Jacobian(JacobianError::Singular { .. }))));
+    }
+
+    #[test]
+    fn test_unsupported_elements() {
+        let coords = array![[0.0, 1.0]];
+        let result = element_stiffness_elasticity(&coords, &[0, 1], ShapeFunctionKind::Line1, 1.0, 0.25);
+        assert_eq!(result, Err(ElasticityError::UnsupportedElement(ShapeFunctionKind::Line1)));
+    }
+}
@@ -1,21 +1,157 @@
 use ndarray::Array2;
 
-use crate::elements::quadrature::quadrature_rules::QuadratureRule;
-use crate::elements::parametric_topology_element::position_jacobian::compute_position_jacobian;
+use crate::elements::quadrature::quadrature_rules::{QuadratureError, QuadratureFamily, QuadratureRule, QuadratureRuleDyn};
+use crate::elements::parametric_topology_element::position_jacobian::{compute_position_jacobian, JacobianError};
 use crate::elements::element_library::hypercube_elements::NodalBasedShapeFunctions;
 use crate::elements::parametric_topology_element::determinant_and_adjugate::determinant_and_adjugate_expansions;
+use crate::elements::parametric_topology_element::mass_matrices::increment_consistent_mass_matrix;
+use crate::elements::parametric_topology_element::stiffness_matrices::increment_linear_stiffness_matrix;
 
-fn integrate_elements<const DIM: usize, const LEN: usize, Element>(
+/// Dense (n_nodes x n_nodes) scalar blocks of one element, stored in row-major order
+#[derive(Debug, Clone, PartialEq)]
+pub struct ElementMatrices {
+    pub mass: Vec<f64>,
+    pub stiffness: Vec<f64>,
+}
+
+/// Choice of the tensor-product quadrature rule used by `integrate_elements_with_config`.
+///
+/// By default the rule is full Gauss-Legendre integration: order + 1 points per direction for an
+/// element of the given polynomial order, as `QuadratureFamily::points_for_order(order)`.
+/// `points_override` selects another number of points per direction, e.g. reduced integration
+/// with one point less to avoid shear or volumetric locking. Selective integration is obtained by
+/// integrating the terms with different configurations.
+///
+/// Tensor-product rules only cover hypercube elements. Simplices are integrated with
+/// `integrate_elements` and a triangle or tetrahedron rule instead.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct IntegrationConfig {
+    pub rule_family: QuadratureFamily,
+    pub points_override: Option<usize>,
+}
+
+impl IntegrationConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn rule_family(mut self, rule_family: QuadratureFamily) -> Self {
+        self.rule_family = rule_family;
+        self
+    }
+
+    pub fn points_override(mut self, points_per_direction: usize) -> Self {
+        self.points_override = Some(points_per_direction);
+        self
+    }
+
+    /// Number of points per direction for an element of the given polynomial order
+    pub fn points_per_direction(&self, order: usize) -> usize {
+        self.points_override.unwrap_or_else(|| self.rule_family.points_for_order(order))
+    }
+
+    /// Tensor-product rule on the reference element of `Element`, with the number of points
+    /// of each direction chosen from the order of `Element` along that axis (`Element::ORDERS`).
+    /// Returns `QuadratureError::SimplexElement` for triangles and tetrahedra.
+    pub fn rule<Element: NodalBasedShapeFunctions>(&self) -> Result<QuadratureRuleDyn, QuadratureError> {
+        if Element::SIMPLEX {
+            return Err(QuadratureError::SimplexElement { dim: Element::DIMENSION as usize });
+        }
+
+        let rules_1d: Vec<Vec<(f64, f64)>> = Element::ORDERS
+            .iter()
+            .map(|&order| self.rule_family.rule_1d(self.points_per_direction(order as usize)))
+            .collect();
+        let rules_1d: Vec<&[(f64, f64)]> = rules_1d.iter().map(Vec::as_slice).collect();
+        Ok(QuadratureRuleDyn::tensor_product_mixed(&rules_1d))
+    }
+}
+
+/// Errors of `integrate_elements_with_config`
+#[derive(Debug)]
+pub enum IntegrationError {
+    /// No quadrature rule for the element type
+    Quadrature(QuadratureError),
+    /// A position Jacobian is unsupported or singular
+    Jacobian(JacobianError),
+}
+
+impl std::fmt::Display for IntegrationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            IntegrationError::Quadrature(err) => write!(f, "Quadrature error: {}", err),
+            IntegrationError::Jacobian(err) => write!(f, "Jacobian error: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for IntegrationError {}
+
+impl From<QuadratureError> for IntegrationError {
+    fn from(err: QuadratureError) -> Self {
+        IntegrationError::Quadrature(err)
+    }
+}
+
+impl From<JacobianError> for IntegrationError {
+    fn from(err: JacobianError) -> Self {
+        IntegrationError::Jacobian(err)
+    }
+}
+
+/// Integrates the mass and linear stiffness matrices of each element with the given quadrature rule.
+///
+/// `all_nodal_coords` has shape (DIM, total_nodes). Returns one `ElementMatrices` per element,
+/// in the same order, or a `JacobianError` if a position Jacobian is unsupported or singular.
+pub fn integrate_elements<const DIM: usize, const LEN: usize, Element>(
     elements: Vec<&Element>,
     quadrature_rule: &QuadratureRule<DIM, LEN>,
     all_nodal_coords: &Array2<f64>,
-) where
+) -> Result<Vec<ElementMatrices>, JacobianError>
+where
+    Element: NodalBasedShapeFunctions<Coordinates = [f64; DIM]>,
+{
+    let points: Vec<([f64; DIM], f64)> = quadrature_rule.iter().collect();
+    integrate_at_points(elements, &points, all_nodal_coords)
+}
+
+/// Same as `integrate_elements`, with the quadrature rule chosen by `config`
+/// (full Gauss-Legendre integration for `IntegrationConfig::default()`).
+/// Fails for simplex elements, which have no tensor-product rule.
+pub fn integrate_elements_with_config<const DIM: usize, Element>(
+    elements: Vec<&Element>,
+    config: &IntegrationConfig,
+    all_nodal_coords: &Array2<f64>,
+) -> Result<Vec<ElementMatrices>, IntegrationError>
+where
     Element: NodalBasedShapeFunctions<Coordinates = [f64; DIM]>,
 {
+    let rule: QuadratureRuleDyn = config.rule::<Element>()?;
+    let points: Vec<([f64; DIM], f64)> = rule
+        .iter()
+        .map(|(point, &weight)| (std::array::from_fn(|d| point[d]), weight))
+        .collect();
+    Ok(integrate_at_points(elements, &points, all_nodal_coords)?)
+}
+
+fn integrate_at_points<const DIM: usize, Element>(
+    elements: Vec<&Element>,
+    points: &[([f64; DIM], f64)],
+    all_nodal_coords: &Array2<f64>,
+) -> Result<Vec<ElementMatrices>, JacobianError>
+where
+    Element: NodalBasedShapeFunctions<Coordinates = [f64; DIM]>,
+{
+    let n_nodes: usize = Element::NUMBER_OF_NODES as usize;
+    let mut element_matrices: Vec<ElementMatrices> = Vec::with_capacity(elements.len());
+
     for element in elements {
-        // Iterate over points and weights in the quadrature rule 
-        for (point, weight) in quadrature_rule.iter() {
-            let jacobian_shape_functions: Array2<f64> = Element::evaluate_jacobian_of_shape_functions(point);
+        let mut mass: Vec<f64> = vec![0.0; n_nodes * n_nodes];
+        let mut stiffness: Vec<f64> = vec![0.0; n_nodes * n_nodes];
+
+        // Iterate over points and weights in the quadrature rule
+        for &(point, weight) in points {
+            let jacobian_shape_functions: Array2<f64> = Element::evaluate_jacobian_of_shape_functions(&point);
 
             let position_jacobian = compute_position_jacobian(
                 all_nodal_coords,
@@ -23,21 +159,240 @@ fn integrate_elements<const DIM: usize, const LEN: usize, Element>(
                 &jacobian_shape_functions,
             );
 
-            let (determinant, adjugate) = determinant_and_adjugate_expansions(&position_jacobian);
-            
-            increment_mass_matrix(
-                Element::evaluate_shape_functions(point),
-                element.node_ids(),
+            let (determinant, adjugate) = determinant_and_adjugate_expansions(&position_jacobian)?;
+            if determinant == 0.0 {
+                return Err(JacobianError::Singular { determinant });
+            }
+
+            increment_consistent_mass_matrix(
+                &mut mass,
+                &Element::evaluate_shape_functions(&point),
                 determinant * weight,
             );
 
-            increment_stiffness_matrices_all_force_orders(
+            increment_linear_stiffness_matrix(
+                &mut stiffness,
                 &jacobian_shape_functions,
-                element.node_ids(),
                 &adjugate,
                 determinant,
-                *weight,
+                weight,
             );
         }
+
+        element_matrices.push(ElementMatrices { mass, stiffness });
+    }
+
+    Ok(element_matrices)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elements::element_library::hypercube_elements::{LineShapeFunctions, ShapeFunctionKind};
+    use crate::elements::element_library::simplex_elements::{TetrahedronShapeFunctions, TriangleShapeFunctions};
+    use crate::elements::quadrature::quadrature_rules::{line_2_point, line_3_point, triangle_3_point, QuadratureRuleDyn};
+
+    struct Hexahedron {
+        node_ids: Vec<u32>,
+    }
+
+    impl NodalBasedShapeFunctions for Hexahedron {
+        type Coordinates = [f64; 3];
+        const DIMENSION: u8 = 3;
+        const NUMBER_OF_NODES: u8 = 8;
+        const ORDERS: &'static [u8] = &[1, 1, 1];
+
+        fn evaluate_shape_functions(coords: &[f64; 3]) -> Vec<f64> {
+            ShapeFunctionKind::Cube1.evaluate_shape_functions(coords)
+        }
+
+        fn evaluate_jacobian_of_shape_functions(coords: &[f64; 3]) -> Array2<f64> {
+            ShapeFunctionKind::Cube1.evaluate_jacobian_of_shape_functions(coords)
+        }
+
+        fn center() -> [f64; 3] {
+            [0.5; 3]
+        }
+
+        fn node_ids(&self) -> &[u32] {
+            &self.node_ids
+        }
+    }
+
+    struct Bar<const ORDER: u8> {
+        node_ids: Vec<u32>,
+    }
+
+    impl<const ORDER: u8> NodalBasedShapeFunctions for Bar<ORDER> {
+        type Coordinates = [f64; 1];
+        const DIMENSION: u8 = 1;
+        const NUMBER_OF_NODES: u8 = ORDER + 1;
+        const ORDERS: &'static [u8] = &[ORDER];
+
+        fn evaluate_shape_functions(coords: &[f64; 1]) -> Vec<f64> {
+            LineShapeFunctions::<ORDER>::evaluate_shape_functions(coords)
+        }
+
+        fn evaluate_jacobian_of_shape_functions(coords: &[f64; 1]) -> Array2<f64> {
+            LineShapeFunctions::<ORDER>::evaluate_jacobian_of_shape_functions(coords)
+        }
+
+        fn center() -> [f64; 1] {
+            LineShapeFunctions::<ORDER>::center()
+        }
+
+        fn node_ids(&self) -> &[u32] {
+            &self.node_ids
+        }
+    }
+
+    struct Triangle {
+        node_ids: Vec<u32>,
+    }
+
+    impl NodalBasedShapeFunctions for Triangle {
+        type Coordinates = [f64; 2];
+        const DIMENSION: u8 = 2;
+        const NUMBER_OF_NODES: u8 = 3;
+        const ORDERS: &'static [u8] = &[1, 1];
+        const SIMPLEX: bool = true;
+
+        fn evaluate_shape_functions(coords: &[f64; 2]) -> Vec<f64> {
+            TriangleShapeFunctions::<1>::evaluate_shape_functions(coords)
+        }
+
+        fn evaluate_jacobian_of_shape_functions(coords: &[f64; 2]) -> Array2<f64> {
+            TriangleShapeFunctions::<1>::evaluate_jacobian_of_shape_functions(coords)
+        }
+
+        fn center() -> [f64; 2] {
+            TriangleShapeFunctions::<1>::center()
+        }
+
+        fn node_ids(&self) -> &[u32] {
+            &self.node_ids
+        }
+    }
+
+    fn gauss_rule_3d() -> QuadratureRule<3, 8> {
+        let rule = QuadratureRuleDyn::gauss_legendre(2, 3);
+        QuadratureRule {
+            points: std::array::from_fn(|q| [rule.points[q][0], rule.points[q][1], rule.points[q][2]]),
+            weights: std::array::from_fn(|q| rule.weights[q]),
+        }
+    }
+
+    /// Reference hexahedron nodes scaled by (sx, sy, sz), as a (3, 8) coordinate array
+    fn box_coordinates(scale: [f64; 3]) -> Array2<f64> {
+        let nodes = ShapeFunctionKind::Cube1.reference_nodes();
+        Array2::from_shape_fn((3, 8), |(d, i)| nodes[i][d] * scale[d])
+    }
+
+    #[test]
+    fn test_integrate_single_hexahedron() {
+        let element = Hexahedron { node_ids: (0..8).collect() };
+        let rule = gauss_rule_3d();
+
+        // Unit cube: trilinear Laplace stiffness has K_ii = 1/3
+        let results = integrate_elements(vec![&element], &rule, &box_coordinates([1.0, 1.0, 1.0])).unwrap();
+        assert_eq!(results.len(), 1);
+        let ElementMatrices { mass, stiffness } = &results[0];
+        assert!((mass.iter().sum::<f64>() - 1.0).abs() < 1e-12);
+        assert!((mass[0] - 1.0 / 27.0).abs() < 1e-12);
+        for i in 0..8 {
+            assert!((stiffness[i * 8 + i] - 1.0 / 3.0).abs() < 1e-12);
+        }
+
+        // Stretched box: mass sums to the volume, constant fields have zero energy
+        let results = integrate_elements(vec![&element], &rule, &box_coordinates([2.0, 1.0, 0.5])).unwrap();
+        let ElementMatrices { mass, stiffness } = &results[0];
+        assert!((mass.iter().sum::<f64>() - 1.0).abs() < 1e-12);
+        for i in 0..8 {
+            let row_sum: f64 = stiffness[i * 8..(i + 1) * 8].iter().sum();
+            assert!(row_sum.abs() < 1e-12);
+            for j in 0..8 {
+                assert!((stiffness[i * 8 + j] - stiffness[j * 8 + i]).abs() < 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn test_reduced_integration_uses_fewer_points() {
+        let full = IntegrationConfig::new();
+        let reduced = IntegrationConfig::new().points_override(1);
+        assert_eq!(full.rule::<Hexahedron>().unwrap().len(), 8);
+        assert_eq!(reduced.rule::<Hexahedron>().unwrap().len(), 1);
+        assert_eq!(IntegrationConfig::new().rule_family(QuadratureFamily::GaussLobatto).rule::<Hexahedron>().unwrap().len(), 27);
+
+        // Default config matches the explicit 2x2x2 Gauss rule
+        let element = Hexahedron { node_ids: (0..8).collect() };
+        let coords = box_coordinates([2.0, 1.0, 0.5]);
+        let from_rule = integrate_elements(vec![&element], &gauss_rule_3d(), &coords).unwrap();
+        let from_config = integrate_elements_with_config(vec![&element], &full, &coords).unwrap();
+        for (a, b) in from_rule[0].stiffness.iter().zip(&from_config[0].stiffness) {
+            assert!((a - b).abs() < 1e-12);
+        }
+
+        // One point at the centre: the volume is still exact, each mass entry is 1/64
+        let results = integrate_elements_with_config(vec![&element], &reduced, &coords).unwrap();
+        assert!((results[0].mass.iter().sum::<f64>() - 1.0).abs() < 1e-12);
+        assert!(results[0].mass.iter().all(|m| (m - 1.0 / 64.0).abs() < 1e-12));
+    }
+
+    #[test]
+    fn test_integrate_bar() {
+        // The shape functions of the line element can be integrated directly
+        let _ = integrate_elements::<1, 2, LineShapeFunctions<1>>;
+
+        // Bar of length L = 2.5 between x = 1 and x = 3.5
+        let length = 2.5;
+        let coords = ndarray::array![[1.0, 1.0 + length]];
+        let element = Bar::<1> { node_ids: vec![0, 1] };
+
+        let results = integrate_elements(vec![&element], &line_2_point(), &coords).unwrap();
+        let ElementMatrices { mass, stiffness } = &results[0];
+
+        // Consistent mass (L/6) [[2, 1], [1, 2]], stiffness (1/L) [[1, -1], [-1, 1]]
+        let expected_mass = [2.0, 1.0, 1.0, 2.0].map(|m| m * length / 6.0);
+        let expected_stiffness = [1.0, -1.0, -1.0, 1.0].map(|k| k / length);
+        for (m, expected) in mass.iter().zip(expected_mass) {
+            assert!((m - expected).abs() < 1e-12);
+        }
+        for (k, expected) in stiffness.iter().zip(expected_stiffness) {
+            assert!((k - expected).abs() < 1e-12);
+        }
+
+        // Quadratic bar: the mass sums to the length, the config rule matches the 3-point statics
+        let element = Bar::<2> { node_ids: vec![0, 2, 1] };
+        let coords = ndarray::array![[0.0, length, 0.5 * length]];
+        let from_rule = integrate_elements(vec![&element], &line_3_point(), &coords).unwrap();
+        let from_config = integrate_elements_with_config(vec![&element], &IntegrationConfig::new(), &coords).unwrap();
+        assert!((from_rule[0].mass.iter().sum::<f64>() - length).abs() < 1e-12);
+        for (a, b) in from_rule[0].mass.iter().zip(&from_config[0].mass) {
+            assert!((a - b).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_config_rejects_simplices() {
+        let config = IntegrationConfig::new();
+        assert!(matches!(config.rule::<TriangleShapeFunctions<2>>(), Err(QuadratureError::SimplexElement { dim: 2 })));
+        assert!(matches!(config.rule::<TetrahedronShapeFunctions<1>>(), Err(QuadratureError::SimplexElement { dim: 3 })));
+
+        // Right triangle with legs 2 and 1: a square rule would report a mass of 2 instead of the area 1
+        let element = Triangle { node_ids: vec![0, 1, 2] };
+        let coords = ndarray::array![[0.0, 2.0, 0.0], [0.0, 0.0, 1.0]];
+        let result = integrate_elements_with_config(vec![&element], &config, &coords);
+        assert!(matches!(result, Err(IntegrationError::Quadrature(QuadratureError::SimplexElement { dim: 2 }))));
+
+        let results = integrate_elements(vec![&element], &triangle_3_point(), &coords).unwrap();
+        assert!((results[0].mass.iter().sum::<f64>() - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_integrate_degenerate_hexahedron() {
+        let element = Hexahedron { node_ids: (0..8).collect() };
+        let result = integrate_elements(vec![&element], &gauss_rule_3d(), &box_coordinates([1.0, 1.0, 0.0]));
+        assert!(matches!(result, Err(JacobianError::Singular { .. })));
     }
-}
\ No newline at end of file
+}
@@ -1,34 +1,169 @@
-use ndarray::Array2;
+//! # Elemental Mass Matrices
+//!
+//! Quadrature-point increments of the elemental mass matrix
+//! M_ij = ∫ N_i N_j dV ≈ ∑_q N_i(ξ_q) N_j(ξ_q) w_q det J(ξ_q)
+//!
+//! Both functions accumulate into a dense (n_nodes x n_nodes) scalar block stored in row-major
+//! order, i.e. `mass_data[i * n_nodes + j]` holds M_ij. The same scalar block applies to every
+//! displacement component.
+//!
+//! - `increment_consistent_mass_matrix`: adds the full outer product N_i N_j w det J
+//! - `increment_lumped_mass_matrix`: row-sum lumping, adds N_i (∑_j N_j) w det J to the diagonal only
+//!
+//! `project_gauss_to_nodes` uses the consistent mass matrix for the L2 projection of quadrature-point
+//! values (e.g. stresses) onto the nodal basis of an element.
+//!
+//! ### Panics
+//! Both increment functions panic if `mass_data.len()` is not the square of `shape_functions.len()`.
 
-fn increment_elemental_mass_matrix(mass_matrix_data: &mut Vec<Vec<Vec<f64>>>, shape_functions: Vec<f64>, node_ids: &[u8], weight_times_determinant: &f64) {
-    // Implementation for incrementing the mass matrix
-    let data = shape_functions * shape_functions * weight_times_determinant;
+use nalgebra::{DMatrix, DVector};
+
+/// Add N_i * N_j * weight_times_det to `mass_data[i * n_nodes + j]` for all node pairs
+pub fn increment_consistent_mass_matrix(mass_data: &mut [f64], shape_functions: &[f64], weight_times_det: f64) {
+    let n_nodes: usize = shape_functions.len();
+    assert_eq!(mass_data.len(), n_nodes * n_nodes, "Mass block length {} does not match {} nodes", mass_data.len(), n_nodes);
+
+    for (mass_row, &n_i) in mass_data.chunks_exact_mut(n_nodes).zip(shape_functions) {
+        let scaled_n_i: f64 = n_i * weight_times_det;
+        for (m_ij, &n_j) in mass_row.iter_mut().zip(shape_functions) {
+            *m_ij += scaled_n_i * n_j;
+        }
+    }
+}
+
+/// Add the row sums N_i * (∑_j N_j) * weight_times_det to the diagonal of `mass_data`
+pub fn increment_lumped_mass_matrix(mass_data: &mut [f64], shape_functions: &[f64], weight_times_det: f64) {
+    let n_nodes: usize = shape_functions.len();
+    assert_eq!(mass_data.len(), n_nodes * n_nodes, "Mass block length {} does not match {} nodes", mass_data.len(), n_nodes);
+
+    let sum_weighted: f64 = shape_functions.iter().sum::<f64>() * weight_times_det;
+
+    for (i, &n_i) in shape_functions.iter().enumerate() {
+        mass_data[i * n_nodes + i] += n_i * sum_weighted;
+    }
+}
+
+/// L2 projection of quadrature-point values onto the nodal basis of one element.
+///
+/// Solves M x = b with M_ij = ∑_q N_i N_j w_q det J_q and b_i = ∑_q N_i v_q w_q det J_q, where
+/// `shape_fns_at_points[q]` holds N_i(ξ_q), `weights_det[q]` holds w_q det J_q and `gauss_values[q]`
+/// holds v_q. Returns `None` if M is singular, e.g. with fewer quadrature points than needed to
+/// integrate N_i N_j.
+///
+/// ### Panics
+/// Panics if the three slices have different lengths or the shape function vectors differ in length.
+pub fn project_gauss_to_nodes(shape_fns_at_points: &[Vec<f64>], weights_det: &[f64], gauss_values: &[f64]) -> Option<Vec<f64>> {
+    assert_eq!(shape_fns_at_points.len(), weights_det.len(), "Expected one weight per quadrature point");
+    assert_eq!(shape_fns_at_points.len(), gauss_values.len(), "Expected one value per quadrature point");
+    let n_nodes: usize = shape_fns_at_points.first().map_or(0, Vec::len);
+
+    let mut mass_data: Vec<f64> = vec![0.0; n_nodes * n_nodes];
+    let mut load: Vec<f64> = vec![0.0; n_nodes];
+    for ((shape_functions, &weight_times_det), &value) in shape_fns_at_points.iter().zip(weights_det).zip(gauss_values) {
+        increment_consistent_mass_matrix(&mut mass_data, shape_functions, weight_times_det);
+        for (b_i, &n_i) in load.iter_mut().zip(shape_functions) {
+            *b_i += n_i * value * weight_times_det;
+        }
+    }
+
+    let mass: DMatrix<f64> = DMatrix::from_row_slice(n_nodes, n_nodes, &mass_data);
+    mass.lu()
+        .solve(&DVector::from_vec(load))
+        .map(|nodal_values| nodal_values.data.into())
 }
 
-fn increment_elemental_mass_matrix(
-    mass_matrix_data: &mut Vec<Vec<Vec<f64>>>,
-    shape_functions: &Vec<f64>,
-    node_ids: &[u8],
-    weight_times_determinant: f64,
-) {
-    let n_nodes = shape_functions.len();
-    let block_size = mass_matrix_data[0].len(); // Assuming all blocks are same size
-    
-    for i in 0..n_nodes {
-        for j in 0..n_nodes {
-            let block_index = node_ids[i * n_nodes + j];
-            let scalar_value = shape_functions[i] * shape_functions[j] * weight_times_determinant;
-            
-            // Add to all elements of the block (for diagonal mass matrix)
-            for row in 0..block_size {
-                for col in 0..block_size {
-                    if row == col {
-                        mass_matrix_data[block_index][row][col] += scalar_value;
-                    }
-                    // For off-diagonal elements, you might want different behavior
-                    // mass_matrix_data[block_index][row][col] += some_other_value;
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elements::element_library::hypercube_elements::ShapeFunctionKind;
+    use crate::elements::quadrature::quadrature_rules::QuadratureRuleDyn;
+
+    fn integrate_mass(kind: ShapeFunctionKind, lumped: bool) -> Vec<f64> {
+        let n_nodes: usize = kind.number_of_nodes() as usize;
+        let rule = QuadratureRuleDyn::gauss_legendre(3, kind.dimension() as usize);
+        let mut mass_data: Vec<f64> = vec![0.0; n_nodes * n_nodes];
+
+        // Unit reference element: det J = 1
+        for (point, &weight) in rule.iter() {
+            let shape_functions: Vec<f64> = kind.evaluate_shape_functions(point);
+            if lumped {
+                increment_lumped_mass_matrix(&mut mass_data, &shape_functions, weight);
+            } else {
+                increment_consistent_mass_matrix(&mut mass_data, &shape_functions, weight);
+            }
+        }
+        mass_data
+    }
+
+    #[test]
+    fn test_consistent_mass_integrates_to_volume() {
+        for kind in [ShapeFunctionKind::Line1, ShapeFunctionKind::Square1, ShapeFunctionKind::Cube1, ShapeFunctionKind::Cube2] {
+            let n_nodes: usize = kind.number_of_nodes() as usize;
+            let mass_data: Vec<f64> = integrate_mass(kind, false);
+
+            assert!((mass_data.iter().sum::<f64>() - 1.0).abs() < 1e-12);
+            for i in 0..n_nodes {
+                for j in 0..n_nodes {
+                    assert!((mass_data[i * n_nodes + j] - mass_data[j * n_nodes + i]).abs() < 1e-15);
+                }
+            }
+        }
+
+        // Linear bar: M = [[1/3, 1/6], [1/6, 1/3]]
+        let mass_data: Vec<f64> = integrate_mass(ShapeFunctionKind::Line1, false);
+        for (value, expected) in mass_data.iter().zip([1.0 / 3.0, 1.0 / 6.0, 1.0 / 6.0, 1.0 / 3.0]) {
+            assert!((value - expected).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_lumped_mass_preserves_total_mass() {
+        for kind in [ShapeFunctionKind::Line1, ShapeFunctionKind::Square1, ShapeFunctionKind::Cube1] {
+            let n_nodes: usize = kind.number_of_nodes() as usize;
+            let consistent: Vec<f64> = integrate_mass(kind, false);
+            let lumped: Vec<f64> = integrate_mass(kind, true);
+
+            assert!((lumped.iter().sum::<f64>() - consistent.iter().sum::<f64>()).abs() < 1e-12);
+            for i in 0..n_nodes {
+                let row_sum: f64 = consistent[i * n_nodes..(i + 1) * n_nodes].iter().sum();
+                assert!((lumped[i * n_nodes + i] - row_sum).abs() < 1e-12);
+                for j in (0..n_nodes).filter(|&j| j != i) {
+                    assert_eq!(lumped[i * n_nodes + j], 0.0);
                 }
             }
         }
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_project_gauss_to_nodes() {
+        let kind = ShapeFunctionKind::Cube1;
+        let nodes = kind.reference_nodes();
+        let rule = QuadratureRuleDyn::gauss_legendre(2, 3);
+
+        // Box [0,2]x[0,1]x[0,1]: det J = 2
+        let shape_fns: Vec<Vec<f64>> = rule.points.iter().map(|point| kind.evaluate_shape_functions(point)).collect();
+        let weights_det: Vec<f64> = rule.weights.iter().map(|w| 2.0 * w).collect();
+
+        // A constant field is recovered at every node
+        let projected = project_gauss_to_nodes(&shape_fns, &weights_det, &vec![3.5; rule.len()]).unwrap();
+        assert!(projected.iter().all(|value| (value - 3.5).abs() < 1e-12));
+
+        // So is any field of the nodal basis, here 1 + x at the physical points x = 2ξ
+        let values: Vec<f64> = rule.points.iter().map(|point| 1.0 + 2.0 * point[0]).collect();
+        let projected = project_gauss_to_nodes(&shape_fns, &weights_det, &values).unwrap();
+        for (value, node) in projected.iter().zip(&nodes) {
+            assert!((value - (1.0 + 2.0 * node[0])).abs() < 1e-12);
+        }
+
+        // One point cannot integrate the 8x8 mass matrix
+        let centre = vec![kind.evaluate_shape_functions(&[0.5, 0.5, 0.5])];
+        assert!(project_gauss_to_nodes(&centre, &[1.0], &[1.0]).is_none());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_mass_block_length_mismatch() {
+        let mut mass_data: Vec<f64> = vec![0.0; 3];
+        increment_consistent_mass_matrix(&mut mass_data, &[0.5, 0.5], 1.0);
+    }
+}
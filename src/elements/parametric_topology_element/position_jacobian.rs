@@ -10,7 +10,10 @@
 //! - Derivatives of shape functions
 //!
 //! Convenience wrappers `compute_position_jacobian_2d` and `compute_position_jacobian_3d` are provided
-//! for common 2D and 3D cases respectively.
+//! for common 2D and 3D cases respectively. `compute_position_jacobians_batch` evaluates the Jacobian
+//! at several points of one element (e.g. all quadrature points) while gathering its coordinates once.
+//! `compute_position_jacobian_scaled` applies the chain-rule factor when the shape functions and the
+//! quadrature rule use different reference domains, e.g. [-1, 1] elements with the [0, 1] rules.
 //!
 //! ### Theory
 //! The Jacobian matrix J is computed as:
@@ -31,16 +34,29 @@
 //! let jacobian = compute_position_jacobian_3d(&all_coords, &element_nodes, &shape_derivs);
 //! ```
 //!
+//! `jacobian_determinant_and_inverse` returns det(J) and J⁻¹ for 2x2 and 3x3 Jacobians, or a
+//! `JacobianError` for non-square, unsupported or singular matrices. `physical_gradient` uses J⁻¹
+//! to map the gradient of an interpolated field from parametric to physical coordinates.
+//!
 //! ### Panics
 //! The functions will panic if:
 //! - The shape function matrix dimensions don't match the number of element nodes
 //! - The shape function column count doesn't match the spatial dimension
+//! - An element node id is not a column of the nodal coordinates
+//!
+//! These checks are left to ndarray on the hot path. `try_compute_position_jacobian` performs them
+//! up front and returns a `JacobianError` instead, e.g. for meshes that have not been validated.
 //!
 //! ### Performance
-//! The implementation uses nalgebra's efficient matrix operations and avoids unnecessary allocations.
-//! For repeated computations, consider reusing matrix allocations where possible.
+//! The implementation uses ndarray's matrix multiplication (gemm) and avoids unnecessary allocations.
+//! For repeated computations, `compute_position_jacobian_into` reuses caller-provided buffers.
+
 
 
+use ndarray::Array2;
+use ndarray::linalg::general_mat_mul;
+
+use crate::elements::element_library::hypercube_elements::ReferenceDomain;
 
 /// Computes the Jacobian matrix for finite element analysis.
 ///
@@ -51,16 +67,82 @@
 ///
 /// # Panics
 /// Panics if dimensions are incompatible
+pub fn compute_position_jacobian(
+    all_nodal_coords: &Array2<f64>,
+    element_node_ids: &[u32],
+    jacobian_shape_functions: &Array2<f64>,
+) -> Array2<f64> {
+    let mut element_coords = Array2::zeros((0, 0));
+    let mut jacobian = Array2::zeros((0, 0));
+    compute_position_jacobian_into(
+        all_nodal_coords,
+        element_node_ids,
+        jacobian_shape_functions,
+        &mut element_coords,
+        &mut jacobian,
+    );
+    jacobian
+}
 
-use ndarray::Array2;
+/// Computes the Jacobian matrix like `compute_position_jacobian`, returning an error instead of
+/// panicking when the shapes of the inputs are incompatible or a node id is out of range.
+pub fn try_compute_position_jacobian(
+    all_nodal_coords: &Array2<f64>,
+    element_node_ids: &[u32],
+    jacobian_shape_functions: &Array2<f64>,
+) -> Result<Array2<f64>, JacobianError> {
+    let (dim, num_nodes) = all_nodal_coords.dim();
+    let (rows, cols) = jacobian_shape_functions.dim();
 
-pub fn compute_position_jacobian(
+    if cols != dim {
+        return Err(JacobianError::DimensionMismatch { expected: dim, actual: cols });
+    }
+    if rows != element_node_ids.len() {
+        return Err(JacobianError::NodeCountMismatch { expected: element_node_ids.len(), actual: rows });
+    }
+    if let Some(&node) = element_node_ids.iter().find(|&&node| node as usize >= num_nodes) {
+        return Err(JacobianError::NodeOutOfRange { node, num_nodes });
+    }
+
+    Ok(compute_position_jacobian(all_nodal_coords, element_node_ids, jacobian_shape_functions))
+}
+
+/// Jacobian dx/dη with respect to the coordinates η of a quadrature rule on `quadrature_domain`,
+/// from the derivatives dN/dξ of shape functions defined on `shape_domain`.
+///
+/// Along every axis ξ = `shape_domain.map_from(quadrature_domain, η)`, so by the chain rule
+/// dx/dη = dx/dξ · |shape_domain| / |quadrature_domain|, and det(J) scales by that ratio to the
+/// power DIM: 2^DIM for [-1, 1] shape functions integrated with a [0, 1] rule. The shape function
+/// derivatives must be evaluated at the mapped point ξ.
+///
+/// # Panics
+/// Panics under the same conditions as `compute_position_jacobian`
+pub fn compute_position_jacobian_scaled(
     all_nodal_coords: &Array2<f64>,
     element_node_ids: &[u32],
     jacobian_shape_functions: &Array2<f64>,
+    shape_domain: ReferenceDomain,
+    quadrature_domain: ReferenceDomain,
 ) -> Array2<f64> {
+    compute_position_jacobian(all_nodal_coords, element_node_ids, jacobian_shape_functions)
+        * shape_domain.scale_from(quadrature_domain)
+}
+
+/// Computes the Jacobian matrix into `out`, reusing caller-provided buffers.
+///
+/// `element_coords_scratch` and `out` are only reallocated when their shapes differ from
+/// (dim, n_nodes) and (dim, dim), so repeated calls for elements of the same type do not allocate.
+///
+/// # Panics
+/// Panics if dimensions are incompatible
+pub fn compute_position_jacobian_into(
+    all_nodal_coords: &Array2<f64>,
+    element_node_ids: &[u32],
+    jacobian_shape_functions: &Array2<f64>,
+    element_coords_scratch: &mut Array2<f64>,
+    out: &mut Array2<f64>,
+) {
     let dim = all_nodal_coords.shape()[0];
-    let n_nodes = element_node_ids.len();
 
     assert_eq!(
         jacobian_shape_functions.shape()[1],
@@ -68,15 +150,162 @@ pub fn compute_position_jacobian(
         "Shape function columns must match spatial dimension"
     );
 
-    // Build element coordinates matrix by selecting columns from all_nodal_coords
-    let mut element_coords = Array2::zeros((dim, n_nodes));
+    if out.dim() != (dim, dim) {
+        *out = Array2::zeros((dim, dim));
+    }
+
+    gather_element_coords_into(all_nodal_coords, element_node_ids, element_coords_scratch);
+
+    // Matrix multiplication: element_coords (dim, n_nodes) × jacobian_shape_functions (n_nodes, dim)
+    general_mat_mul(1.0, element_coords_scratch, jacobian_shape_functions, 0.0, out);
+}
+
+/// Computes the Jacobian matrix at several points of the same element.
+///
+/// The element coordinates matrix is gathered once and multiplied by the shape function
+/// derivatives of every point, e.g. all points of a quadrature rule.
+///
+/// # Panics
+/// Panics if dimensions are incompatible
+pub fn compute_position_jacobians_batch(
+    all_nodal_coords: &Array2<f64>,
+    element_node_ids: &[u32],
+    jacobians_at_points: &[Array2<f64>],
+) -> Vec<Array2<f64>> {
+    let dim = all_nodal_coords.shape()[0];
+
+    let mut element_coords = Array2::zeros((0, 0));
+    gather_element_coords_into(all_nodal_coords, element_node_ids, &mut element_coords);
+
+    jacobians_at_points
+        .iter()
+        .map(|jacobian_shape_functions| {
+            assert_eq!(
+                jacobian_shape_functions.shape()[1],
+                dim,
+                "Shape function columns must match spatial dimension"
+            );
+            element_coords.dot(jacobian_shape_functions)
+        })
+        .collect()
+}
+
+/// Builds the element coordinates matrix (dim, n_nodes) by selecting columns from all_nodal_coords
+fn gather_element_coords_into(
+    all_nodal_coords: &Array2<f64>,
+    element_node_ids: &[u32],
+    element_coords: &mut Array2<f64>,
+) {
+    let dim = all_nodal_coords.shape()[0];
+    let n_nodes = element_node_ids.len();
+
+    if element_coords.dim() != (dim, n_nodes) {
+        *element_coords = Array2::zeros((dim, n_nodes));
+    }
+
     for (col, &node_id) in element_node_ids.iter().enumerate() {
         let node_col = all_nodal_coords.column(node_id as usize);
         element_coords.column_mut(col).assign(&node_col);
     }
+}
 
-    // Matrix multiplication: element_coords (dim, n_nodes) × jacobian_shape_functions (n_nodes, dim)
-    element_coords.dot(jacobian_shape_functions)
+/// Errors when computing or inverting a position Jacobian
+#[derive(Debug, Clone, PartialEq)]
+pub enum JacobianError {
+    /// The Jacobian is not a square matrix
+    NonSquare { rows: usize, cols: usize },
+    /// Only 2x2 and 3x3 Jacobians are supported
+    UnsupportedDimension(usize),
+    /// The determinant is (numerically) zero
+    Singular { determinant: f64 },
+    /// The shape function derivatives do not have one column per spatial dimension
+    DimensionMismatch { expected: usize, actual: usize },
+    /// The shape function derivatives do not have one row per element node
+    NodeCountMismatch { expected: usize, actual: usize },
+    /// An element node id is not a column of the nodal coordinates
+    NodeOutOfRange { node: u32, num_nodes: usize },
+}
+
+impl std::fmt::Display for JacobianError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            JacobianError::NonSquare { rows, cols } => write!(f, "Jacobian is not square: {}x{}", rows, cols),
+            JacobianError::UnsupportedDimension(dim) => write!(f, "Unsupported Jacobian dimension {}", dim),
+            JacobianError::Singular { determinant } => write!(f, "Singular Jacobian with determinant {}", determinant),
+            JacobianError::DimensionMismatch { expected, actual } => {
+                write!(f, "Expected {} shape function derivative columns, got {}", expected, actual)
+            }
+            JacobianError::NodeCountMismatch { expected, actual } => {
+                write!(f, "Expected {} shape function derivative rows, got {}", expected, actual)
+            }
+            JacobianError::NodeOutOfRange { node, num_nodes } => {
+                write!(f, "Node {} is out of range for {} nodes", node, num_nodes)
+            }
+        }
+    }
+}
+
+impl std::error::Error for JacobianError {}
+
+/// Relative tolerance below which a Jacobian is considered singular
+const SINGULARITY_TOLERANCE: f64 = 1e-12;
+
+/// Computes det(J) and J⁻¹ for 2x2 and 3x3 Jacobians using the closed-form adjugate.
+///
+/// J is considered singular if |det(J)| <= 1e-12 * max|J_ij|^dim.
+pub fn jacobian_determinant_and_inverse(jac: &Array2<f64>) -> Result<(f64, Array2<f64>), JacobianError> {
+    let (rows, cols) = jac.dim();
+    if rows != cols {
+        return Err(JacobianError::NonSquare { rows, cols });
+    }
+
+    let adjugate = match rows {
+        2 => adjugate_2x2(jac),
+        3 => adjugate_3x3(jac),
+        dim => return Err(JacobianError::UnsupportedDimension(dim)),
+    };
+
+    // Expansion along the first row: det(J) = sum_j J_0j adj(J)_j0
+    let determinant: f64 = (0..cols).map(|j| jac[[0, j]] * adjugate[[j, 0]]).sum();
+
+    let scale = jac.iter().fold(0.0_f64, |acc, &x| acc.max(x.abs()));
+    if determinant.abs() <= SINGULARITY_TOLERANCE * scale.powi(rows as i32) {
+        return Err(JacobianError::Singular { determinant });
+    }
+
+    Ok((determinant, adjugate / determinant))
+}
+
+/// Gradient ∇u in physical coordinates of the field interpolated from `nodal_values`.
+///
+/// The parametric gradient ∂u/∂ξ = ∑_i u_i ∂N_i/∂ξ is mapped with ∂ξ/∂x = J⁻¹, so that
+/// ∂u/∂x_d = ∑_k ∂u/∂ξ_k (J⁻¹)_kd.
+///
+/// # Panics
+/// Panics if `nodal_values` does not have one value per row of `jac_shape_fns`, or if
+/// `jac_inverse` does not match the number of its columns.
+pub fn physical_gradient(nodal_values: &[f64], jac_shape_fns: &Array2<f64>, jac_inverse: &Array2<f64>) -> Vec<f64> {
+    assert_eq!(nodal_values.len(), jac_shape_fns.nrows(), "Expected one nodal value per shape function");
+    let parametric_gradient = ndarray::ArrayView1::from(nodal_values).dot(jac_shape_fns);
+    parametric_gradient.dot(jac_inverse).to_vec()
+}
+
+fn adjugate_2x2(m: &Array2<f64>) -> Array2<f64> {
+    let (a, b, c, d) = (m[[0, 0]], m[[0, 1]], m[[1, 0]], m[[1, 1]]);
+    ndarray::array![
+        [d, -b],
+        [-c, a],
+    ]
+}
+
+fn adjugate_3x3(m: &Array2<f64>) -> Array2<f64> {
+    // adj(M)_ij = cofactor C_ji
+    let cofactor = |r0: usize, r1: usize, c0: usize, c1: usize| m[[r0, c0]] * m[[r1, c1]] - m[[r0, c1]] * m[[r1, c0]];
+    ndarray::array![
+        [cofactor(1, 2, 1, 2), -cofactor(0, 2, 1, 2), cofactor(0, 1, 1, 2)],
+        [-cofactor(1, 2, 0, 2), cofactor(0, 2, 0, 2), -cofactor(0, 1, 0, 2)],
+        [cofactor(1, 2, 0, 1), -cofactor(0, 2, 0, 1), cofactor(0, 1, 0, 1)],
+    ]
 }
 
 /// Convenience wrapper for 3D case
@@ -103,6 +332,80 @@ pub fn compute_position_jacobian_2d(
 mod tests {
     use super::*;
     use ndarray::array;
+    use crate::elements::element_library::hypercube_elements::{NodalBasedShapeFunctions, ShapeFunctionKind};
+    use crate::elements::quadrature::quadrature_rules::QuadratureRuleDyn;
+
+    /// Bilinear quadrilateral on [-1, 1]², nodes in the tensor-product order of `Square1`
+    struct BiUnitSquare;
+
+    impl NodalBasedShapeFunctions for BiUnitSquare {
+        type Coordinates = [f64; 2];
+        const DIMENSION: u8 = 2;
+        const NUMBER_OF_NODES: u8 = 4;
+        const ORDERS: &'static [u8] = &[1, 1];
+
+        fn evaluate_shape_functions(&[x, y]: &[f64; 2]) -> Vec<f64> {
+            vec![(1.0 - x) * (1.0 - y) / 4.0, (1.0 + x) * (1.0 - y) / 4.0, (1.0 - x) * (1.0 + y) / 4.0, (1.0 + x) * (1.0 + y) / 4.0]
+        }
+
+        fn evaluate_jacobian_of_shape_functions(&[x, y]: &[f64; 2]) -> Array2<f64> {
+            array![
+                [-(1.0 - y) / 4.0, -(1.0 - x) / 4.0],
+                [(1.0 - y) / 4.0, -(1.0 + x) / 4.0],
+                [-(1.0 + y) / 4.0, (1.0 - x) / 4.0],
+                [(1.0 + y) / 4.0, (1.0 + x) / 4.0],
+            ]
+        }
+
+        fn center() -> [f64; 2] {
+            [0.0; 2]
+        }
+
+        fn reference_domain() -> ReferenceDomain {
+            ReferenceDomain::MinusOneToOne
+        }
+    }
+
+    #[test]
+    fn test_scaled_jacobian_across_reference_domains() {
+        // Distorted quadrilateral of area 2.45, integrated with the [0, 1] Gauss rule
+        let coords = array![[0.0, 2.0, 0.2, 1.7], [0.0, 0.1, 1.0, 1.4]];
+        let area: f64 = 0.5 * ((2.0 * 1.4 - 0.1 * 1.7) + (1.7 * 1.0 - 1.4 * 0.2));
+        let element = [0, 1, 2, 3];
+        let rule = QuadratureRuleDyn::gauss_legendre(2, 2);
+        let quadrature_domain = ReferenceDomain::ZeroToOne;
+
+        let mut unit_square_area = 0.0;
+        let mut bi_unit_area = 0.0;
+        let mut unscaled_bi_unit_area = 0.0;
+        for (point, &weight) in rule.iter() {
+            let jacobian = compute_position_jacobian(&coords, &element, &ShapeFunctionKind::Square1.evaluate_jacobian_of_shape_functions(point));
+            unit_square_area += jacobian_determinant_and_inverse(&jacobian).unwrap().0 * weight;
+
+            let shape_domain = BiUnitSquare::reference_domain();
+            let xi: [f64; 2] = std::array::from_fn(|d| shape_domain.map_from(quadrature_domain, point[d]));
+            let jacobian_shape_functions = BiUnitSquare::evaluate_jacobian_of_shape_functions(&xi);
+            let scaled = compute_position_jacobian_scaled(&coords, &element, &jacobian_shape_functions, shape_domain, quadrature_domain);
+            bi_unit_area += jacobian_determinant_and_inverse(&scaled).unwrap().0 * weight;
+
+            let unscaled = compute_position_jacobian(&coords, &element, &jacobian_shape_functions);
+            unscaled_bi_unit_area += jacobian_determinant_and_inverse(&unscaled).unwrap().0 * weight;
+        }
+
+        assert!((unit_square_area - area).abs() < 1e-12);
+        assert!((bi_unit_area - area).abs() < 1e-12);
+        // Without the chain-rule factor the area is off by 2^DIM
+        assert!((unscaled_bi_unit_area - area / 4.0).abs() < 1e-12);
+
+        // Same domain: no scaling
+        let jacobian_shape_functions = ShapeFunctionKind::Square1.evaluate_jacobian_of_shape_functions(&[0.3, 0.6]);
+        assert_eq!(
+            compute_position_jacobian_scaled(&coords, &element, &jacobian_shape_functions, quadrature_domain, quadrature_domain),
+            compute_position_jacobian(&coords, &element, &jacobian_shape_functions)
+        );
+        assert_eq!(ReferenceDomain::MinusOneToOne.map_from(ReferenceDomain::ZeroToOne, 0.75), 0.5);
+        assert_eq!(ReferenceDomain::ZeroToOne.map_from(ReferenceDomain::MinusOneToOne, -1.0), 0.0);
+    }
 
     #[test]
     fn test_compute_position_jacobian_2d() {
@@ -274,4 +577,185 @@ mod tests {
             &jacobian_shape_functions,
         );
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_try_compute_position_jacobian() {
+        let all_nodal_coords = array![
+            [0.0, 1.0, 1.0, 0.0],
+            [0.0, 0.0, 1.0, 1.0],
+        ];
+        let jacobian_shape_functions = array![
+            [-0.5, -0.5],
+            [0.5, -0.5],
+            [0.5, 0.5],
+            [-0.5, 0.5],
+        ];
+
+        assert_eq!(
+            try_compute_position_jacobian(&all_nodal_coords, &[0, 1, 2, 3], &jacobian_shape_functions),
+            Ok(compute_position_jacobian(&all_nodal_coords, &[0, 1, 2, 3], &jacobian_shape_functions))
+        );
+
+        let three_columns = Array2::zeros((4, 3));
+        assert_eq!(
+            try_compute_position_jacobian(&all_nodal_coords, &[0, 1, 2, 3], &three_columns),
+            Err(JacobianError::DimensionMismatch { expected: 2, actual: 3 })
+        );
+        assert_eq!(
+            try_compute_position_jacobian(&all_nodal_coords, &[0, 1, 2], &jacobian_shape_functions),
+            Err(JacobianError::NodeCountMismatch { expected: 3, actual: 4 })
+        );
+        assert_eq!(
+            try_compute_position_jacobian(&all_nodal_coords, &[0, 1, 2, 4], &jacobian_shape_functions),
+            Err(JacobianError::NodeOutOfRange { node: 4, num_nodes: 4 })
+        );
+    }
+
+    #[test]
+    fn test_compute_position_jacobian_into_matches_allocating() {
+        let all_nodal_coords = array![
+            [0.0, 2.0, 2.5, 0.2, 4.0],
+            [0.0, 0.1, 1.5, 1.0, 3.0],
+        ];
+        let jacobian_shape_functions = array![
+            [-0.3, -0.2],
+            [0.3, -0.3],
+            [0.2, 0.3],
+            [-0.2, 0.2],
+        ];
+
+        let mut element_coords = Array2::zeros((0, 0));
+        let mut out = Array2::zeros((0, 0));
+
+        // Reuse the same buffers for several elements
+        for element_node_ids in [[0, 1, 2, 3], [1, 4, 2, 3]] {
+            let expected = compute_position_jacobian(&all_nodal_coords, &element_node_ids, &jacobian_shape_functions);
+
+            compute_position_jacobian_into(
+                &all_nodal_coords,
+                &element_node_ids,
+                &jacobian_shape_functions,
+                &mut element_coords,
+                &mut out,
+            );
+
+            assert_eq!(out, expected);
+            assert_eq!(element_coords.shape(), [2, 4]);
+        }
+    }
+
+    #[test]
+    fn test_compute_position_jacobians_batch() {
+        let all_nodal_coords = array![
+            [0.0, 2.0, 2.5, 0.2],
+            [0.0, 0.1, 1.5, 1.0],
+        ];
+        let element_node_ids = [0, 1, 2, 3];
+
+        // Bilinear shape function derivatives on [0,1]² at a few points
+        let derivatives_at = |x: f64, y: f64| array![
+            [-(1.0 - y), -(1.0 - x)],
+            [1.0 - y, -x],
+            [y, x],
+            [-y, 1.0 - x],
+        ];
+        let jacobians_at_points = vec![
+            derivatives_at(0.2, 0.3),
+            derivatives_at(0.5, 0.5),
+            derivatives_at(0.9, 0.1),
+        ];
+
+        let batch = compute_position_jacobians_batch(&all_nodal_coords, &element_node_ids, &jacobians_at_points);
+
+        assert_eq!(batch.len(), jacobians_at_points.len());
+        for (jacobian, jacobian_shape_functions) in batch.iter().zip(jacobians_at_points.iter()) {
+            let expected = compute_position_jacobian(&all_nodal_coords, &element_node_ids, jacobian_shape_functions);
+            assert_eq!(jacobian, &expected);
+        }
+    }
+
+    #[test]
+    fn test_jacobian_determinant_and_inverse_unit_square() {
+        // Unit square at center (same data as test_compute_position_jacobian_2d)
+        let jacobian = array![
+            [0.5, 0.0],
+            [0.0, 0.5],
+        ];
+
+        let (determinant, inverse) = jacobian_determinant_and_inverse(&jacobian).unwrap();
+        assert!((determinant - 0.25).abs() < 1e-10);
+        assert_eq!(inverse, array![[2.0, 0.0], [0.0, 2.0]]);
+    }
+
+    #[test]
+    fn test_jacobian_determinant_and_inverse_3x3() {
+        let jacobian = array![
+            [2.0, 0.5, 0.1],
+            [0.3, 1.5, 0.4],
+            [0.2, 0.6, 1.2],
+        ];
+
+        let (determinant, inverse) = jacobian_determinant_and_inverse(&jacobian).unwrap();
+
+        let expected_det = 2.0 * (1.5 * 1.2 - 0.4 * 0.6) - 0.5 * (0.3 * 1.2 - 0.4 * 0.2) + 0.1 * (0.3 * 0.6 - 1.5 * 0.2);
+        assert!((determinant - expected_det).abs() < 1e-12);
+
+        let identity = jacobian.dot(&inverse);
+        for i in 0..3 {
+            for j in 0..3 {
+                let expected = if i == j { 1.0 } else { 0.0 };
+                assert!((identity[[i, j]] - expected).abs() < 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn test_physical_gradient_of_linear_field() {
+        use crate::elements::element_library::hypercube_elements::ShapeFunctionKind;
+
+        // Box [0,2]x[0,1]x[0,0.5] and u = 1 + 3x - y + 4z
+        let kind = ShapeFunctionKind::Cube1;
+        let nodes = kind.reference_nodes();
+        let all_nodal_coords = Array2::from_shape_fn((3, 8), |(d, i)| nodes[i][d] * [2.0, 1.0, 0.5][d]);
+        let nodal_values: Vec<f64> = all_nodal_coords
+            .columns()
+            .into_iter()
+            .map(|x| 1.0 + 3.0 * x[0] - x[1] + 4.0 * x[2])
+            .collect();
+        let element_node_ids: Vec<u32> = (0..8).collect();
+
+        for point in [[0.5, 0.5, 0.5], [0.1, 0.7, 0.3], [1.0, 0.0, 0.9]] {
+            let jacobian_shape_functions = kind.evaluate_jacobian_of_shape_functions(&point);
+            let jacobian = compute_position_jacobian(&all_nodal_coords, &element_node_ids, &jacobian_shape_functions);
+            let (_, inverse) = jacobian_determinant_and_inverse(&jacobian).unwrap();
+
+            let gradient = physical_gradient(&nodal_values, &jacobian_shape_functions, &inverse);
+            for (g, expected) in gradient.iter().zip([3.0, -1.0, 4.0]) {
+                assert!((g - expected).abs() < 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn test_jacobian_determinant_and_inverse_errors() {
+        // Degenerate element: all nodes on a line
+        let all_nodal_coords = array![
+            [0.0, 1.0, 2.0, 3.0],
+            [0.0, 1.0, 2.0, 3.0],
+        ];
+        let jacobian_shape_functions = array![
+            [-0.25, -0.25],
+            [0.25, -0.25],
+            [0.25, 0.25],
+            [-0.25, 0.25],
+        ];
+        let jacobian = compute_position_jacobian(&all_nodal_coords, &[0, 1, 2, 3], &jacobian_shape_functions);
+        assert!(matches!(jacobian_determinant_and_inverse(&jacobian), Err(JacobianError::Singular { .. })));
+
+        let non_square = array![[1.0, 0.0, 0.0], [0.0, 1.0, 0.0]];
+        assert_eq!(jacobian_determinant_and_inverse(&non_square), Err(JacobianError::NonSquare { rows: 2, cols: 3 }));
+
+        let one_by_one = array![[1.0]];
+        assert_eq!(jacobian_determinant_and_inverse(&one_by_one), Err(JacobianError::UnsupportedDimension(1)));
+    }
+}
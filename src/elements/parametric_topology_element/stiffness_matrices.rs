@@ -0,0 +1,36 @@
+//! # Elemental Stiffness Matrices
+//!
+//! Quadrature-point increments of the linear (force order 1) elemental stiffness matrix
+//! K_ij = ∫ ∇N_i · ∇N_j dV ≈ ∑_q (dN_i/dξ adj(J)) · (dN_j/dξ adj(J)) w_q / det J
+//!
+//! The gradients are written with the adjugate instead of the inverse, ∇N = dN/dξ adj(J) / det J,
+//! which is the form used by the parametric expansions. As for the mass matrix, the increment is
+//! accumulated into a dense (n_nodes x n_nodes) scalar block stored in row-major order.
+//!
+//! ### Panics
+//! Panics if `stiffness_data.len()` is not the square of the number of rows of
+//! `jacobian_shape_functions`, or if the adjugate does not match the spatial dimension.
+
+use ndarray::Array2;
+
+/// Add (dN/dξ adj(J)) (dN/dξ adj(J))ᵀ * weight / determinant to `stiffness_data`
+pub fn increment_linear_stiffness_matrix(
+    stiffness_data: &mut [f64],
+    jacobian_shape_functions: &Array2<f64>,
+    adjugate: &Array2<f64>,
+    determinant: f64,
+    weight: f64,
+) {
+    let n_nodes: usize = jacobian_shape_functions.nrows();
+    assert_eq!(stiffness_data.len(), n_nodes * n_nodes, "Stiffness block length {} does not match {} nodes", stiffness_data.len(), n_nodes);
+
+    // Rows are dN_i/dξ adj(J) = det J * ∇N_i
+    let scaled_gradients: Array2<f64> = jacobian_shape_functions.dot(adjugate);
+    let factor: f64 = weight / determinant;
+
+    for (stiffness_row, gradient_i) in stiffness_data.chunks_exact_mut(n_nodes).zip(scaled_gradients.rows()) {
+        for (k_ij, gradient_j) in stiffness_row.iter_mut().zip(scaled_gradients.rows()) {
+            *k_ij += factor * gradient_i.dot(&gradient_j);
+        }
+    }
+}
@@ -23,6 +23,9 @@
 //! - 3D (Cube):
 //!   - Linear (1st order, 8 points)
 //!   - Quadratic (2nd order, 27 points)
+//! - 4D (Tesseract, e.g. space-time):
+//!   - Linear (1st order, 16 points)
+//!   - Quadratic (2nd order, 81 points)
 //!
 //! ## Implementation Details
 //! - Uses `nalgebra` for matrix/vector operations
@@ -40,6 +43,15 @@
 //!
 //! Note: Higher-order rules can be added by extending the `create_1d_rule` function
 //! and adding corresponding type aliases and initialization methods.
+//! Rules of any order are also available at runtime through `gauss_legendre_1d` and
+//! `QuadratureRuleDyn`, which build Gauss-Legendre points with Newton's iteration.
+//! `gauss_lobatto_1d` provides Gauss-Lobatto points (including the endpoints), and
+//! `rule_for_family` selects between both families through `QuadratureFamily`.
+//! `integrates_exactly` checks the degree of exactness of a rule against monomials on ```[0,1]^DIM```.
+//! `anisotropic_cube_rule` builds the matching rule for hexahedra with a different order per axis.
+//! Symmetric rules on the reference triangle are available through `triangle_1_point`,
+//! `triangle_3_point` and `triangle_6_point`, and on the reference tetrahedron through
+//! `tetrahedron_1_point`, `tetrahedron_4_point` and `tetrahedron_5_point`.
 
 use std::iter::{IntoIterator, Zip};
 use std::slice::Iter;
@@ -49,7 +61,8 @@ use once_cell::sync::Lazy;
 #[derive(Debug)]
 pub enum QuadratureError {
     UnsupportedRule { dim: usize, order: usize },
-    DimensionMismatch { expected: usize, actual: usize }
+    DimensionMismatch { expected: usize, actual: usize },
+    SimplexElement { dim: usize },
 }
 
 impl std::fmt::Display for QuadratureError {
@@ -59,6 +72,8 @@ impl std::fmt::Display for QuadratureError {
                 write!(f, "Unsupported quadrature rule: dimension {} with order {}", dim, order),
             QuadratureError::DimensionMismatch { expected, actual } =>
                 write!(f, "Dimension mismatch: expected is {} but actual is {}", expected, actual),
+            QuadratureError::SimplexElement { dim } =>
+                write!(f, "No tensor-product rule for a {}D simplex, use a triangle or tetrahedron rule", dim),
         }
     }
 }
@@ -74,8 +89,10 @@ pub struct QuadratureRule<const DIM: usize, const LEN: usize> {
 }
 
 impl<const DIM: usize, const LEN: usize> QuadratureRule<DIM, LEN> {
-    pub fn iter(&self) -> Zip<Iter<'_, [f64; DIM]>, Iter<'_, f64>> {
-        self.points.iter().zip(self.weights.iter())
+    /// Iterates over (point, weight) pairs without cloning the rule.
+    /// Points and weights are `Copy`, so each item is a small owned copy.
+    pub fn iter(&self) -> impl Iterator<Item = ([f64; DIM], f64)> + '_ {
+        self.points.iter().copied().zip(self.weights.iter().copied())
     }
 }
 
@@ -92,6 +109,61 @@ impl<const DIM: usize, const LEN: usize> IntoIterator for QuadratureRule<DIM, LE
     }
 }
 
+/// Integrates f over the reference element: sum of weight * f(point)
+pub fn integrate<const DIM: usize, const LEN: usize, F: Fn([f64; DIM]) -> f64>(
+    rule: &QuadratureRule<DIM, LEN>,
+    f: F,
+) -> f64 {
+    rule.iter().map(|(point, weight)| weight * f(point)).sum()
+}
+
+/// Integrates a vector-valued f over the reference element into `out` (overwritten).
+/// Panics if f returns a vector whose length differs from `out.len()`.
+pub fn integrate_into<const DIM: usize, const LEN: usize, F: Fn([f64; DIM]) -> Vec<f64>>(
+    rule: &QuadratureRule<DIM, LEN>,
+    f: F,
+    out: &mut [f64],
+) {
+    out.fill(0.0);
+
+    for (point, weight) in rule.iter() {
+        let values = f(point);
+        assert_eq!(values.len(), out.len(), "Integrand length {} does not match output length {}", values.len(), out.len());
+
+        for (o, value) in out.iter_mut().zip(values) {
+            *o += weight * value;
+        }
+    }
+}
+
+/// Tolerance of `integrates_exactly` on the integral of every monomial
+const EXACTNESS_TOLERANCE: f64 = 1e-12;
+
+/// Returns true if the rule integrates every monomial x_1^e_1 ... x_DIM^e_DIM of total degree
+/// e_1 + ... + e_DIM <= `degree` over ```[0,1]^DIM``` within 1e-12 of the analytic value
+/// ∏ 1/(e_i + 1). An n-point Gauss-Legendre tensor rule passes up to degree 2n-1.
+pub fn integrates_exactly<const DIM: usize, const LEN: usize>(rule: &QuadratureRule<DIM, LEN>, degree: usize) -> bool {
+    let mut exponents = [0_usize; DIM];
+    loop {
+        if exponents.iter().sum::<usize>() <= degree {
+            let exact: f64 = exponents.iter().map(|&e| 1.0 / (e + 1) as f64).product();
+            let integral = integrate(rule, |point| {
+                point.iter().zip(&exponents).map(|(x, &e)| x.powi(e as i32)).product()
+            });
+            if (integral - exact).abs() > EXACTNESS_TOLERANCE {
+                return false;
+            }
+        }
+
+        // Next exponent tuple in 0..=degree along every axis, the first axis fastest
+        let Some(axis) = exponents.iter().position(|&e| e < degree) else {
+            return true;
+        };
+        exponents[axis] += 1;
+        exponents[..axis].fill(0);
+    }
+}
+
 // Precompute all quadrature rules at compile time or first use
 static LINEAR_1D: Lazy<QuadratureRule<1, 2>> = Lazy::new(|| create_linear_1d_rule());
 static QUADRATIC_1D: Lazy<QuadratureRule<1, 3>> = Lazy::new(|| create_quadratic_1d_rule());
@@ -112,6 +184,14 @@ static QUADRATIC_3D: Lazy<QuadratureRule<3, 27>> = Lazy::new(
     || create_3d_from_1d::<3, 27>(&QUADRATIC_1D).unwrap()
 );
 
+static LINEAR_4D: Lazy<QuadratureRule<4, 16>> = Lazy::new(
+    || create_4d_from_1d::<2, 16>(&LINEAR_1D).unwrap()
+);
+
+static QUADRATIC_4D: Lazy<QuadratureRule<4, 81>> = Lazy::new(
+    || create_4d_from_1d::<3, 81>(&QUADRATIC_1D).unwrap()
+);
+
 fn create_linear_1d_rule() -> QuadratureRule<1, 2> {
     let aux = 1.0 / (3.0_f64).sqrt();
     let points = [[-aux], [aux]];
@@ -142,8 +222,8 @@ fn create_2d_from_1d<const IN_LEN: usize, const OUT_LEN: usize>(
         return Err(QuadratureError::DimensionMismatch { expected: OUT_LEN, actual: actual_dimension })
     }
     
-    for (i, (x, wx)) in rule_1d.clone().into_iter().enumerate() {
-        for (j, (y, wy)) in rule_1d.clone().into_iter().enumerate() {
+    for (i, (x, wx)) in rule_1d.iter().enumerate() {
+        for (j, (y, wy)) in rule_1d.iter().enumerate() {
             let idx = i * IN_LEN + j;
             points[idx] = [x[0], y[0]];
             weights[idx] = wx * wy;
@@ -164,9 +244,9 @@ fn create_3d_from_1d<const IN_LEN: usize, const OUT_LEN: usize>(
         return Err(QuadratureError::DimensionMismatch { expected: OUT_LEN, actual: actual_dimension })
     }
     
-    for (i, (x, wx)) in rule_1d.clone().into_iter().enumerate() {
-        for (j, (y, wy)) in rule_1d.clone().into_iter().enumerate() {
-            for (k, (z, wz)) in rule_1d.clone().into_iter().enumerate() {
+    for (i, (x, wx)) in rule_1d.iter().enumerate() {
+        for (j, (y, wy)) in rule_1d.iter().enumerate() {
+            for (k, (z, wz)) in rule_1d.iter().enumerate() {
                 let idx = (i * IN_LEN + j) * IN_LEN + k;
                 points[idx] = [x[0], y[0], z[0]];
                 weights[idx] = wx * wy * wz;
@@ -177,6 +257,298 @@ fn create_3d_from_1d<const IN_LEN: usize, const OUT_LEN: usize>(
     Ok(QuadratureRule { points, weights })
 }
 
+fn create_4d_from_1d<const IN_LEN: usize, const OUT_LEN: usize>(
+    rule_1d: &QuadratureRule<1, IN_LEN>,
+) -> Result<QuadratureRule<4, OUT_LEN>, QuadratureError> {
+    let mut points = [[0.0, 0.0, 0.0, 0.0]; OUT_LEN];
+    let mut weights = [0.0; OUT_LEN];
+
+    let actual_dimension: usize = IN_LEN.pow(4);
+    if actual_dimension != OUT_LEN {
+        return Err(QuadratureError::DimensionMismatch { expected: OUT_LEN, actual: actual_dimension })
+    }
+
+    for (i, (x, wx)) in rule_1d.iter().enumerate() {
+        for (j, (y, wy)) in rule_1d.iter().enumerate() {
+            for (k, (z, wz)) in rule_1d.iter().enumerate() {
+                for (l, (t, wt)) in rule_1d.iter().enumerate() {
+                    let idx = ((i * IN_LEN + j) * IN_LEN + k) * IN_LEN + l;
+                    points[idx] = [x[0], y[0], z[0], t[0]];
+                    weights[idx] = wx * wy * wz * wt;
+                }
+            }
+        }
+    }
+
+    Ok(QuadratureRule { points, weights })
+}
+
+/// 2-point Gauss-Legendre rule on the reference line [0, 1], exact for polynomials of degree 3
+pub fn line_2_point() -> QuadratureRule<1, 2> {
+    LINEAR_1D.clone()
+}
+
+/// 3-point Gauss-Legendre rule on the reference line [0, 1], exact for polynomials of degree 5
+pub fn line_3_point() -> QuadratureRule<1, 3> {
+    QUADRATIC_1D.clone()
+}
+
+/// 1-point rule on the reference triangle (0,0), (1,0), (0,1), exact for polynomials of degree 1
+pub fn triangle_1_point() -> QuadratureRule<2, 1> {
+    QuadratureRule { points: [[1.0 / 3.0, 1.0 / 3.0]], weights: [0.5] }
+}
+
+/// 3-point rule on the reference triangle, exact for polynomials of degree 2
+pub fn triangle_3_point() -> QuadratureRule<2, 3> {
+    let (a, b) = (1.0 / 6.0, 2.0 / 3.0);
+    QuadratureRule {
+        points: [[a, a], [b, a], [a, b]],
+        weights: [1.0 / 6.0; 3],
+    }
+}
+
+/// 6-point rule on the reference triangle (Dunavant), exact for polynomials of degree 4
+pub fn triangle_6_point() -> QuadratureRule<2, 6> {
+    let (a, wa) = (0.445948490915965, 0.223381589678011 / 2.0);
+    let (b, wb) = (0.091576213509771, 0.109951743655322 / 2.0);
+    QuadratureRule {
+        points: [[a, a], [1.0 - 2.0 * a, a], [a, 1.0 - 2.0 * a], [b, b], [1.0 - 2.0 * b, b], [b, 1.0 - 2.0 * b]],
+        weights: [wa, wa, wa, wb, wb, wb],
+    }
+}
+
+/// 1-point rule on the reference tetrahedron (0,0,0), (1,0,0), (0,1,0), (0,0,1),
+/// exact for polynomials of degree 1
+pub fn tetrahedron_1_point() -> QuadratureRule<3, 1> {
+    QuadratureRule { points: [[0.25, 0.25, 0.25]], weights: [1.0 / 6.0] }
+}
+
+/// 4-point rule on the reference tetrahedron, exact for polynomials of degree 2
+pub fn tetrahedron_4_point() -> QuadratureRule<3, 4> {
+    let a = (5.0 - 5.0_f64.sqrt()) / 20.0;
+    let b = 1.0 - 3.0 * a;
+    QuadratureRule {
+        points: [[a, a, a], [b, a, a], [a, b, a], [a, a, b]],
+        weights: [1.0 / 24.0; 4],
+    }
+}
+
+/// 5-point rule on the reference tetrahedron, exact for polynomials of degree 3.
+/// Note that the centroid weight is negative.
+pub fn tetrahedron_5_point() -> QuadratureRule<3, 5> {
+    let (a, b) = (1.0 / 6.0, 0.5);
+    QuadratureRule {
+        points: [[0.25, 0.25, 0.25], [a, a, a], [b, a, a], [a, b, a], [a, a, b]],
+        weights: [-2.0 / 15.0, 3.0 / 40.0, 3.0 / 40.0, 3.0 / 40.0, 3.0 / 40.0],
+    }
+}
+
+/// Quadrature rule with sizes only known at runtime
+#[derive(Debug, Clone, PartialEq)]
+pub struct QuadratureRuleDyn {
+    pub points: Vec<Vec<f64>>,  // Each point has DIM coordinates
+    pub weights: Vec<f64>,
+}
+
+impl QuadratureRuleDyn {
+    /// Gauss-Legendre rule on ```[0,1]^dim``` with `num_points` points per direction
+    pub fn gauss_legendre(num_points: usize, dim: usize) -> Self {
+        Self::tensor_product(&gauss_legendre_1d(num_points), dim)
+    }
+
+    /// Tensor product of a 1D rule, with the same point ordering as `create_2d_from_1d` and
+    /// `create_3d_from_1d` (the last coordinate varies fastest)
+    pub fn tensor_product(rule_1d: &[(f64, f64)], dim: usize) -> Self {
+        Self::tensor_product_mixed(&vec![rule_1d; dim])
+    }
+
+    /// Tensor product of one 1D rule per direction, possibly with different numbers of points,
+    /// with the same point ordering as `tensor_product`
+    pub fn tensor_product_mixed(rules_1d: &[&[(f64, f64)]]) -> Self {
+        let mut points: Vec<Vec<f64>> = vec![Vec::with_capacity(rules_1d.len())];
+        let mut weights: Vec<f64> = vec![1.0];
+
+        for &rule_1d in rules_1d {
+            let mut next_points = Vec::with_capacity(points.len() * rule_1d.len());
+            let mut next_weights = Vec::with_capacity(weights.len() * rule_1d.len());
+
+            for (point, weight) in points.iter().zip(weights.iter()) {
+                for &(x, wx) in rule_1d {
+                    let mut next_point = point.clone();
+                    next_point.push(x);
+                    next_points.push(next_point);
+                    next_weights.push(weight * wx);
+                }
+            }
+
+            points = next_points;
+            weights = next_weights;
+        }
+
+        QuadratureRuleDyn { points, weights }
+    }
+
+    pub fn len(&self) -> usize {
+        self.weights.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.weights.is_empty()
+    }
+
+    pub fn iter(&self) -> Zip<Iter<'_, Vec<f64>>, Iter<'_, f64>> {
+        self.points.iter().zip(self.weights.iter())
+    }
+}
+
+/// Family of 1D rules used to build tensor-product rules
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum QuadratureFamily {
+    /// Interior points, n points integrate polynomials up to degree 2n-1 exactly
+    #[default]
+    GaussLegendre,
+    /// Includes the endpoints 0 and 1, n points integrate polynomials up to degree 2n-3 exactly
+    GaussLobatto,
+}
+
+impl QuadratureFamily {
+    /// Number of points per direction needed to integrate degree 2*order+1 exactly
+    pub fn points_for_order(&self, order: usize) -> usize {
+        match self {
+            QuadratureFamily::GaussLegendre => order + 1,
+            QuadratureFamily::GaussLobatto => order + 2,
+        }
+    }
+
+    /// Points and weights on ```[0,1]```
+    pub fn rule_1d(&self, num_points: usize) -> Vec<(f64, f64)> {
+        match self {
+            QuadratureFamily::GaussLegendre => gauss_legendre_1d(num_points),
+            QuadratureFamily::GaussLobatto => gauss_lobatto_1d(num_points),
+        }
+    }
+}
+
+/// Gauss-Legendre tensor-product rule on ```[0,1]^dim``` with (order+1) points per direction,
+/// which integrates polynomials of degree 2*order+1 in each coordinate exactly
+pub fn rule_for(dim: usize, order: usize) -> Result<QuadratureRuleDyn, QuadratureError> {
+    rule_for_family(dim, order, QuadratureFamily::GaussLegendre)
+}
+
+/// Tensor-product rule of the given family on ```[0,1]^dim``` which integrates polynomials
+/// of degree 2*order+1 in each coordinate exactly
+pub fn rule_for_family(dim: usize, order: usize, family: QuadratureFamily) -> Result<QuadratureRuleDyn, QuadratureError> {
+    if !(1..=3).contains(&dim) {
+        return Err(QuadratureError::UnsupportedRule { dim, order });
+    }
+
+    let rule_1d = family.rule_1d(family.points_for_order(order));
+    Ok(QuadratureRuleDyn::tensor_product(&rule_1d, dim))
+}
+
+/// Gauss-Legendre rule on ```[0,1]^3``` for a hexahedron of orders (ox, oy, oz), such as
+/// `CubeShapeFunctions<OX, OY, OZ>`, with (order+1) points in each direction.
+/// Integrates polynomials of degree 2*ox+1 in x, 2*oy+1 in y and 2*oz+1 in z exactly.
+pub fn anisotropic_cube_rule(ox: usize, oy: usize, oz: usize) -> QuadratureRuleDyn {
+    let rules_1d: Vec<Vec<(f64, f64)>> = [ox, oy, oz].iter().map(|&order| gauss_legendre_1d(order + 1)).collect();
+    let rules_1d: Vec<&[(f64, f64)]> = rules_1d.iter().map(Vec::as_slice).collect();
+    QuadratureRuleDyn::tensor_product_mixed(&rules_1d)
+}
+
+/// Gauss-Lobatto-Legendre points and weights on ```[0,1]```, in ascending order.
+/// The endpoints 0 and 1 are always included, so `num_points` must be at least 2.
+/// A rule of n points integrates polynomials up to degree 2n-3 exactly.
+/// The interior points are the roots of P_(n-1)', found on ```[-1,1]``` by Newton's iteration
+/// and then mapped to ```[0,1]``` following `transform_to_01`.
+pub fn gauss_lobatto_1d(num_points: usize) -> Vec<(f64, f64)> {
+    assert!(num_points >= 2, "Gauss-Lobatto rules need at least 2 points, got {}", num_points);
+
+    const TOLERANCE: f64 = 1e-15;
+    const MAX_ITERATIONS: usize = 100;
+
+    let m = num_points - 1; // degree of the Legendre polynomial
+    let m_f64 = m as f64;
+    let weight_of = |x: f64| {
+        let (p_m, _) = legendre_with_derivative(m, x);
+        2.0 / (m_f64 * (m_f64 + 1.0) * p_m * p_m)
+    };
+
+    let interior = (1..m).rev().map(|i| {
+        // Initial guess (Chebyshev-Gauss-Lobatto), roots are found in descending order
+        let mut x = (std::f64::consts::PI * i as f64 / m_f64).cos();
+
+        for _ in 0..MAX_ITERATIONS {
+            // Legendre equation: (1-x²)P'' = 2xP' - m(m+1)P
+            let (p_m, dp_m) = legendre_with_derivative(m, x);
+            let d2p_m = (2.0 * x * dp_m - m_f64 * (m_f64 + 1.0) * p_m) / (1.0 - x * x);
+            let step = dp_m / d2p_m;
+            x -= step;
+            if step.abs() < TOLERANCE {
+                break;
+            }
+        }
+
+        x
+    });
+
+    std::iter::once(-1.0)
+        .chain(interior)
+        .chain(std::iter::once(1.0))
+        .map(|x| ((x + 1.0) / 2.0, weight_of(x) / 2.0))
+        .collect()
+}
+
+/// Gauss-Legendre points and weights on ```[0,1]```, in ascending order.
+/// The roots of the Legendre polynomial are found on ```[-1,1]``` by Newton's iteration
+/// and then mapped to ```[0,1]``` following `transform_to_01`.
+pub fn gauss_legendre_1d(num_points: usize) -> Vec<(f64, f64)> {
+    const TOLERANCE: f64 = 1e-15;
+    const MAX_ITERATIONS: usize = 100;
+
+    let n = num_points as f64;
+
+    (0..num_points)
+        .rev()
+        .map(|i| {
+            // Initial guess (Tricomi), roots are found in descending order
+            let mut x = (std::f64::consts::PI * (i as f64 + 0.75) / (n + 0.5)).cos();
+
+            for _ in 0..MAX_ITERATIONS {
+                let (p_n, dp_n) = legendre_with_derivative(num_points, x);
+                let step = p_n / dp_n;
+                x -= step;
+                if step.abs() < TOLERANCE {
+                    break;
+                }
+            }
+
+            let (_, derivative) = legendre_with_derivative(num_points, x);
+            let weight = 2.0 / ((1.0 - x * x) * derivative * derivative);
+            ((x + 1.0) / 2.0, weight / 2.0)
+        })
+        .collect()
+}
+
+/// Evaluates P_n(x) and P_n'(x) with the three-term recurrence
+fn legendre_with_derivative(n: usize, x: f64) -> (f64, f64) {
+    let mut p_prev = 1.0;
+    let mut p = x;
+
+    if n == 0 {
+        return (1.0, 0.0);
+    }
+
+    for k in 2..=n {
+        let k = k as f64;
+        let p_next = ((2.0 * k - 1.0) * x * p - (k - 1.0) * p_prev) / k;
+        p_prev = p;
+        p = p_next;
+    }
+
+    let derivative = n as f64 * (x * p - p_prev) / (x * x - 1.0);
+    (p, derivative)
+}
+
 /// Transform points and weights from ```[-1,1]``` to ```[0,1]``` quadrature
 fn transform_to_01<const DIM: usize, const LEN: usize>(
     points: [[f64; DIM]; LEN],
@@ -187,8 +559,9 @@ fn transform_to_01<const DIM: usize, const LEN: usize>(
     (transformed_points, transformed_weights)
 }
 
-#[cfg(not(test))]
+#[cfg(test)]
 mod tests {
+    use super::*;
 
     #[test]
     fn test_linear_1d_rule() {
@@ -280,6 +653,28 @@ mod tests {
         println!("QUADRATIC 3D\npoints={:?}, \nweights={:?}, sum={}\n", rule.points, rule.weights, rule.weights.iter().sum::<f64>());
     }
 
+    #[test]
+    fn test_4d_from_1d() {
+        let rule = LINEAR_4D.clone();
+        assert_eq!(rule.points.len(), 16);
+        for point in rule.points {
+            assert!(point.iter().all(|&x| (0.0..=1.0).contains(&x)));
+        }
+        // Weights sum to 1 (volume of the unit tesseract)
+        assert!((rule.weights.iter().sum::<f64>() - 1.0).abs() < 1e-14);
+
+        // Same ordering as the dynamic tensor product, last coordinate fastest
+        let rule = QUADRATIC_4D.clone();
+        let dyn_rule = QuadratureRuleDyn::tensor_product(&gauss_legendre_1d(3), 4);
+        assert_eq!(dyn_rule.len(), 81);
+        for ((point, weight), (dyn_point, dyn_weight)) in rule.iter().zip(dyn_rule.iter()) {
+            for (x, y) in point.iter().zip(dyn_point) {
+                assert!((x - y).abs() < 1e-14);
+            }
+            assert!((weight - dyn_weight).abs() < 1e-14);
+        }
+    }
+
     #[test]
     fn test_dimension_mismatch_error() {
         // Test that trying to create a 2D rule with wrong output length fails
@@ -289,6 +684,9 @@ mod tests {
         // Test that trying to create a 3D rule with wrong output length fails
         let result = create_3d_from_1d::<2, 7>(&LINEAR_1D);
         assert!(matches!(result, Err(QuadratureError::DimensionMismatch { expected: 7, actual: 8 })));
+
+        let result = create_4d_from_1d::<3, 27>(&QUADRATIC_1D);
+        assert!(matches!(result, Err(QuadratureError::DimensionMismatch { expected: 27, actual: 81 })));
     }
 
     #[test]
@@ -304,9 +702,19 @@ mod tests {
         println!("test_into_iterator -> sum={}\n", sum);
     }
 
+    #[test]
+    fn test_iter_matches_into_iter() {
+        let rule = QUADRATIC_2D.clone();
+        let borrowed: Vec<([f64; 2], f64)> = rule.iter().collect();
+        let owned: Vec<([f64; 2], f64)> = rule.into_iter().collect();
+
+        assert_eq!(borrowed.len(), 9);
+        assert_eq!(borrowed, owned);
+    }
+
     #[test]
     fn test_transform_to_01() {
-        let points = [SVector::from([-1.0]), SVector::from([1.0])];
+        let points = [[-1.0], [1.0]];
         let weights = [1.0, 1.0];
         
         let (transformed_points, transformed_weights) = transform_to_01(points, weights);
@@ -319,61 +727,277 @@ mod tests {
 
     #[test]
     fn test_quadrature_accuracy() {
-        // Test that the quadrature rules can integrate polynomials exactly
-        
+        // Test that the quadrature rules integrate polynomials exactly
+
         // Linear 1D rule should integrate linear functions exactly
-        let linear_rule_1d = LINEAR_1D.clone();
-        let mut integral = 0.0;
-        for (point, weight) in linear_rule_1d {
-            integral += weight * (2.0 * point[0] + 3.0); // Integral of 2x + 3 on [0,1] is 4
-        }
-        println!("linear_rule_1d: expected = 4.0, actual integral = {}", integral);
-        //assert_relative_eq!(integral, 4.0, epsilon = 1e-10);
-        
+        let integral = integrate(&LINEAR_1D, |x| 2.0 * x[0] + 3.0); // Integral of 2x + 3 on [0,1] is 4
+        assert!((integral - 4.0).abs() < 1e-12);
+
         // Linear 2D rule should integrate bilinear functions exactly
-        let linear_rule_2d = LINEAR_2D.clone();
-        let mut integral = 0.0;
-        for (point, weight) in linear_rule_2d {
-            integral += weight * (point[0] * point[1]); // Integral of xy on [0,1]² is 0.25
-        }
-        println!("linear_rule_2d: expected = 0.25, actual integral = {}", integral);
+        let integral = integrate(&LINEAR_2D, |x| x[0] * x[1]); // Integral of xy on [0,1]² is 0.25
+        assert!((integral - 0.25).abs() < 1e-12);
 
         // Linear 3D rule should integrate trilinear functions exactly
-        let linear_rule_3d = LINEAR_3D.clone();
-        let mut integral = 0.0;
-        for (point, weight) in linear_rule_3d {
-            integral += weight * ((4.0 + point[0]) * point[1] * point[2]); // Integral of (4+x)yz on [0,1]² is 1.125
-        }
-        println!("linear_rule_3d: expected = 1.125, actual integral = {}", integral);
+        let integral = integrate(&LINEAR_3D, |x| (4.0 + x[0]) * x[1] * x[2]); // Integral of (4+x)yz on [0,1]³ is 1.125
+        assert!((integral - 1.125).abs() < 1e-12);
 
         // Quadratic 1D rule should integrate quadratic functions exactly
-        let quad_rule_1d = LINEAR_1D.clone();
-        let mut integral = 0.0;
-        for (point, weight) in quad_rule_1d {
-            integral += weight * (3.0 * point[0] * point[0] + 2.0 * point[0] + 1.0); // Integral of 3x² + 2x + 1 on [0,1] is 3
-        }
-        println!("quad_rule_1d: expected = 3.0, actual integral = {}", integral);
-        //assert_relative_eq!(integral, 3.0, epsilon = 1e-10);
+        let integral = integrate(&QUADRATIC_1D, |x| 3.0 * x[0] * x[0] + 2.0 * x[0] + 1.0); // Integral of 3x² + 2x + 1 on [0,1] is 3
+        assert!((integral - 3.0).abs() < 1e-12);
 
         // Quadratic 2D rule should integrate biquadratic functions exactly
-        let quad_rule_2d = LINEAR_2D.clone();
-        let mut integral = 0.0;
-        for (point, weight) in quad_rule_2d {
-            integral += weight * 
-            (3.0 * point[0] * point[0] + 2.0 * point[0] + 1.0) *
-            (6.0 * point[1] * point[1] - 2.0 * point[1] + 1.0); // Integral of (3x² + 2x + 1)(3y² - 2y + 1) on [0,1]² is 6
-        }
-        println!("quad_rule_2d: expected = 6.0, actual integral = {}", integral);
+        let integral = integrate(&QUADRATIC_2D, |x| {
+            (3.0 * x[0] * x[0] + 2.0 * x[0] + 1.0) * (6.0 * x[1] * x[1] - 2.0 * x[1] + 1.0)
+        }); // Integral of (3x² + 2x + 1)(6y² - 2y + 1) on [0,1]² is 6
+        assert!((integral - 6.0).abs() < 1e-12);
 
         // Quadratic 3D rule should integrate triquadratic functions exactly
-        let quad_rule_3d = LINEAR_3D.clone();
-        let mut integral = 0.0;
-        for (point, weight) in quad_rule_3d {
-            integral += weight * 
-            (3.0 * point[0] * point[0] + 2.0 * point[0] + 1.0) *
-            (2.0 * 3.0 * point[1] * point[1] - 2.0 * point[1] + 2.0) *
-            (-2.0 * 3.0 * point[2] * point[2] + 2.0 * point[2] - 1.0); // Integral on [0,1]² is -18
-        }
-        println!("quad_rule_3d: expected = -18.0, actual integral = {}", integral);
+        let integral = integrate(&QUADRATIC_3D, |x| {
+            (3.0 * x[0] * x[0] + 2.0 * x[0] + 1.0)
+                * (6.0 * x[1] * x[1] - 2.0 * x[1] + 2.0)
+                * (-6.0 * x[2] * x[2] + 2.0 * x[2] - 1.0)
+        }); // Integral on [0,1]³ is -18
+        assert!((integral + 18.0).abs() < 1e-12);
+
+        // n Gauss points per direction are exact up to total degree 2n - 1
+        assert!(integrates_exactly(&*LINEAR_2D, 3) && !integrates_exactly(&*LINEAR_2D, 4));
+        assert!(integrates_exactly(&*LINEAR_3D, 3) && !integrates_exactly(&*LINEAR_3D, 4));
+        assert!(integrates_exactly(&*LINEAR_4D, 3) && !integrates_exactly(&*LINEAR_4D, 4));
+        assert!(integrates_exactly(&*QUADRATIC_2D, 5) && !integrates_exactly(&*QUADRATIC_2D, 6));
+        assert!(integrates_exactly(&*QUADRATIC_3D, 5) && !integrates_exactly(&*QUADRATIC_3D, 6));
+    }
+
+    #[test]
+    fn test_integrates_exactly_1d() {
+        assert!(integrates_exactly(&*QUADRATIC_1D, 5));
+        assert!(!integrates_exactly(&*QUADRATIC_1D, 6));
+        assert!(integrates_exactly(&*LINEAR_1D, 3));
+        assert!(!integrates_exactly(&*LINEAR_1D, 5));
+    }
+
+    #[test]
+    fn test_gauss_legendre_1d() {
+        // The 3-point rule integrates x^5 exactly: 1/6 on [0,1]
+        let rule = gauss_legendre_1d(3);
+        let integral: f64 = rule.iter().map(|&(x, w)| w * x.powi(5)).sum();
+        assert!((integral - 1.0 / 6.0).abs() < 1e-14);
+
+        // Matches the hardcoded rules
+        for ((x, w), (point, weight)) in rule.iter().zip(QUADRATIC_1D.iter()) {
+            assert!((x - point[0]).abs() < 1e-12);
+            assert!((w - weight).abs() < 1e-12);
+        }
+        for ((x, w), (point, weight)) in gauss_legendre_1d(2).iter().zip(LINEAR_1D.iter()) {
+            assert!((x - point[0]).abs() < 1e-12);
+            assert!((w - weight).abs() < 1e-12);
+        }
+
+        // An n-point rule integrates polynomials up to degree 2n-1 exactly
+        let rule = gauss_legendre_1d(6);
+        let integral: f64 = rule.iter().map(|&(x, w)| w * x.powi(11)).sum();
+        assert!((integral - 1.0 / 12.0).abs() < 1e-14);
+    }
+
+    #[test]
+    fn test_quadrature_rule_dyn_tensor_product() {
+        let rule = QuadratureRuleDyn::gauss_legendre(3, 2);
+        assert_eq!(rule.len(), 9);
+
+        // Same points and weights as the static rule
+        for ((point, weight), (expected_point, expected_weight)) in rule.iter().zip(QUADRATIC_2D.iter()) {
+            assert!((point[0] - expected_point[0]).abs() < 1e-12);
+            assert!((point[1] - expected_point[1]).abs() < 1e-12);
+            assert!((weight - expected_weight).abs() < 1e-12);
+        }
+
+        // 4 points per direction integrate x^7 y^6 z^5 exactly on [0,1]^3
+        let rule = QuadratureRuleDyn::gauss_legendre(4, 3);
+        assert_eq!(rule.len(), 64);
+        let integral: f64 = rule
+            .iter()
+            .map(|(p, w)| w * p[0].powi(7) * p[1].powi(6) * p[2].powi(5))
+            .sum();
+        assert!((integral - 1.0 / (8.0 * 7.0 * 6.0)).abs() < 1e-14);
+    }
+
+    #[test]
+    fn test_anisotropic_cube_rule() {
+        let rule = anisotropic_cube_rule(2, 1, 1);
+        assert_eq!(rule.len(), 3 * 2 * 2);
+        assert!((rule.weights.iter().sum::<f64>() - 1.0).abs() < 1e-14);
+
+        // x^5 y^3 z needs 3 points in x and 2 in y and z
+        let monomial = |p: &[f64]| p[0].powi(5) * p[1].powi(3) * p[2];
+        let expected = 1.0 / (6.0 * 4.0 * 2.0);
+        let integral: f64 = rule.iter().map(|(p, w)| w * monomial(p)).sum();
+        assert!((integral - expected).abs() < 1e-14);
+
+        // The isotropic linear rule under-integrates x
+        let integral: f64 = rule_for(3, 1).unwrap().iter().map(|(p, w)| w * monomial(p)).sum();
+        assert!((integral - expected).abs() > 1e-4);
+
+        // Same rule as the isotropic one for equal orders
+        assert_eq!(anisotropic_cube_rule(1, 1, 1), rule_for(3, 1).unwrap());
+    }
+
+    #[test]
+    fn test_rule_for() {
+        for dim in 1..=3 {
+            for order in 0..=3 {
+                let rule = rule_for(dim, order).unwrap();
+                assert_eq!(rule.len(), (order + 1).pow(dim as u32));
+
+                // Integral of prod_i x_i^(2*order+1) on [0,1]^dim
+                let degree = 2 * order as i32 + 1;
+                let integral: f64 = rule
+                    .iter()
+                    .map(|(point, weight)| weight * point.iter().map(|x| x.powi(degree)).product::<f64>())
+                    .sum();
+                let expected = (1.0 / (degree + 1) as f64).powi(dim as i32);
+                assert!((integral - expected).abs() < 1e-14, "dim={}, order={}", dim, order);
+            }
+        }
+
+        assert!(matches!(rule_for(4, 1), Err(QuadratureError::UnsupportedRule { dim: 4, order: 1 })));
+        assert!(matches!(rule_for(0, 1), Err(QuadratureError::UnsupportedRule { dim: 0, order: 1 })));
+    }
+
+    #[test]
+    fn test_gauss_lobatto_1d() {
+        for num_points in 2..=6 {
+            let rule = gauss_lobatto_1d(num_points);
+            assert_eq!(rule.len(), num_points);
+
+            // Endpoints are included exactly
+            assert_eq!(rule[0].0, 0.0);
+            assert_eq!(rule[num_points - 1].0, 1.0);
+
+            // Exact up to degree 2n-3
+            let degree = 2 * num_points as i32 - 3;
+            for d in 0..=degree {
+                let integral: f64 = rule.iter().map(|&(x, w)| w * x.powi(d)).sum();
+                assert!((integral - 1.0 / (d + 1) as f64).abs() < 1e-14, "n={}, degree={}", num_points, d);
+            }
+
+            // ... but not degree 2n-2
+            let d = degree + 1;
+            let integral: f64 = rule.iter().map(|&(x, w)| w * x.powi(d)).sum();
+            assert!((integral - 1.0 / (d + 1) as f64).abs() > 1e-8, "n={}", num_points);
+        }
+
+        // 3-point rule is Simpson's rule
+        let rule = gauss_lobatto_1d(3);
+        assert!((rule[1].0 - 0.5).abs() < 1e-15);
+        assert!((rule[0].1 - 1.0 / 6.0).abs() < 1e-15);
+        assert!((rule[1].1 - 4.0 / 6.0).abs() < 1e-15);
+    }
+
+    #[test]
+    fn test_rule_for_family_lobatto() {
+        for dim in 1..=3 {
+            for order in 0..=3 {
+                let rule = rule_for_family(dim, order, QuadratureFamily::GaussLobatto).unwrap();
+                assert_eq!(rule.len(), (order + 2).pow(dim as u32));
+
+                let degree = 2 * order as i32 + 1;
+                let integral: f64 = rule
+                    .iter()
+                    .map(|(point, weight)| weight * point.iter().map(|x| x.powi(degree)).product::<f64>())
+                    .sum();
+                let expected = (1.0 / (degree + 1) as f64).powi(dim as i32);
+                assert!((integral - expected).abs() < 1e-14, "dim={}, order={}", dim, order);
+            }
+        }
+    }
+
+    #[test]
+    fn test_integrate() {
+        // Constants recover the measure of the reference element
+        assert!((integrate(&LINEAR_1D, |_| 2.0) - 2.0).abs() < 1e-14);
+        assert!((integrate(&QUADRATIC_2D, |_| 1.0) - 1.0).abs() < 1e-14);
+        assert!((integrate(&LINEAR_3D, |_| 3.0) - 3.0).abs() < 1e-14);
+
+        // Polynomials from test_quadrature_accuracy
+        assert!((integrate(&LINEAR_1D, |p| 2.0 * p[0] + 3.0) - 4.0).abs() < 1e-14);
+        assert!((integrate(&LINEAR_2D, |p| p[0] * p[1]) - 0.25).abs() < 1e-14);
+        assert!((integrate(&LINEAR_3D, |p| (4.0 + p[0]) * p[1] * p[2]) - 1.125).abs() < 1e-14);
+        assert!((integrate(&QUADRATIC_1D, |p| 3.0 * p[0] * p[0] + 2.0 * p[0] + 1.0) - 3.0).abs() < 1e-14);
+        assert!((integrate(&QUADRATIC_2D, |p| {
+            (3.0 * p[0] * p[0] + 2.0 * p[0] + 1.0) * (6.0 * p[1] * p[1] - 2.0 * p[1] + 1.0)
+        }) - 6.0).abs() < 1e-13);
+        assert!((integrate(&QUADRATIC_3D, |p| {
+            (3.0 * p[0] * p[0] + 2.0 * p[0] + 1.0) *
+            (6.0 * p[1] * p[1] - 2.0 * p[1] + 2.0) *
+            (-6.0 * p[2] * p[2] + 2.0 * p[2] - 1.0)
+        }) - (-18.0)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_integrate_into() {
+        // Integrals of 1, x and x^2 over [0,1]
+        let mut out = [0.0; 3];
+        integrate_into(&QUADRATIC_1D, |p| vec![1.0, p[0], p[0] * p[0]], &mut out);
+
+        let expected = [1.0, 0.5, 1.0 / 3.0];
+        for (value, expected) in out.iter().zip(expected.iter()) {
+            assert!((value - expected).abs() < 1e-14);
+        }
+
+        // Output is overwritten, not accumulated
+        integrate_into(&QUADRATIC_1D, |p| vec![1.0, p[0], p[0] * p[0]], &mut out);
+        assert!((out[0] - 1.0).abs() < 1e-14);
+    }
+
+    #[test]
+    fn test_triangle_rules() {
+        // Integral of x^a y^b over the reference triangle: a! b! / (a + b + 2)!
+        fn factorial(n: i32) -> f64 {
+            (1..=n).map(|k| k as f64).product()
+        }
+        fn exact(a: i32, b: i32) -> f64 {
+            factorial(a) * factorial(b) / factorial(a + b + 2)
+        }
+        fn check<const LEN: usize>(rule: &QuadratureRule<2, LEN>, degree: i32) {
+            for a in 0..=degree {
+                for b in 0..=degree - a {
+                    let integral = integrate(rule, |p| p[0].powi(a) * p[1].powi(b));
+                    assert!((integral - exact(a, b)).abs() < 1e-14, "x^{} y^{}: {} vs {}", a, b, integral, exact(a, b));
+                }
+            }
+        }
+
+        check(&triangle_1_point(), 1);
+        check(&triangle_3_point(), 2);
+        check(&triangle_6_point(), 4);
+
+        // Not exact beyond its degree
+        let integral = integrate(&triangle_3_point(), |p| p[0].powi(3));
+        assert!((integral - exact(3, 0)).abs() > 1e-6);
+    }
+
+    #[test]
+    fn test_tetrahedron_rules() {
+        // Integral of x^a y^b z^c over the reference tetrahedron: a! b! c! / (a + b + c + 3)!
+        fn factorial(n: i32) -> f64 {
+            (1..=n).map(|k| k as f64).product()
+        }
+        fn exact(a: i32, b: i32, c: i32) -> f64 {
+            factorial(a) * factorial(b) * factorial(c) / factorial(a + b + c + 3)
+        }
+        fn check<const LEN: usize>(rule: &QuadratureRule<3, LEN>, degree: i32) {
+            assert!((rule.weights.iter().sum::<f64>() - 1.0 / 6.0).abs() < 1e-15);
+            for a in 0..=degree {
+                for b in 0..=degree - a {
+                    for c in 0..=degree - a - b {
+                        let integral = integrate(rule, |p| p[0].powi(a) * p[1].powi(b) * p[2].powi(c));
+                        assert!((integral - exact(a, b, c)).abs() < 1e-15, "x^{} y^{} z^{}: {} vs {}", a, b, c, integral, exact(a, b, c));
+                    }
+                }
+            }
+        }
+
+        check(&tetrahedron_1_point(), 1);
+        check(&tetrahedron_4_point(), 2);
+        check(&tetrahedron_5_point(), 3);
     }
 }
\ No newline at end of file
@@ -10,23 +10,34 @@ pub mod elements {
         pub mod elastic_force_matrices {
             pub mod parametric_expansion_with_recursion;
         }
-        //pub mod determinant_and_adjugate;
+        pub mod determinant_and_adjugate;
+        pub mod elasticity;
         pub mod position_jacobian;
         pub mod integrate_elements;
         pub mod mass_matrices;
+        pub mod stiffness_matrices;
     }
     pub mod quadrature {
         pub mod quadrature_rules;
     }
     pub mod element_library {
         pub mod hypercube_elements;
+        pub mod simplex_elements;
     }
 }
 
 pub mod mesh {
+    pub mod boundary;
+    pub mod clean;
     pub mod locate_nodes_o_log_n;
+    pub mod node_coordinates;
     pub mod node_coordinates_ndarray;
-    //pub mod hypernode;
+    pub mod quality;
+    pub mod reorder;
+    pub mod spatial;
+    pub mod topology;
+    pub mod transform;
+    pub mod hypernode;
 }
 
 
@@ -0,0 +1,79 @@
+//! Boundary facet extraction.
+//!
+//! A facet (face of a hexahedron, edge of a quadrilateral) lies on the boundary when it
+//! belongs to exactly one element. Facets are matched by their sorted global node ids.
+
+use std::collections::HashMap;
+
+use crate::elements::element_library::hypercube_elements::ShapeFunctionKind;
+use crate::mesh::locate_nodes_o_log_n::MeshNodeConverter;
+
+/// Returns the global nodes of every facet shared by exactly one element.
+/// All elements are assumed to be of type `element_kind`. Facets are returned in
+/// element-id order, each with the node order given by `ShapeFunctionKind::faces`.
+pub fn extract_boundary_faces(converter: &MeshNodeConverter, element_kind: ShapeFunctionKind) -> Vec<Vec<u32>> {
+    let local_faces = element_kind.faces();
+
+    let global_faces: Vec<Vec<u32>> = converter
+        .iter_elements()
+        .flat_map(|(_, nodes)| {
+            local_faces
+                .iter()
+                .map(move |face| face.iter().map(|&local_node| nodes[local_node]).collect::<Vec<u32>>())
+        })
+        .collect();
+
+    let mut face_counts: HashMap<Vec<u32>, usize> = HashMap::with_capacity(global_faces.len());
+    for face in &global_faces {
+        *face_counts.entry(sorted_key(face)).or_insert(0) += 1;
+    }
+
+    global_faces
+        .into_iter()
+        .filter(|face| face_counts[&sorted_key(face)] == 1)
+        .collect()
+}
+
+/// Node ids of a facet in ascending order, the same for every local numbering of the facet
+pub(crate) fn sorted_key(face: &[u32]) -> Vec<u32> {
+    let mut key = face.to_vec();
+    key.sort_unstable();
+    key
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mesh::locate_nodes_o_log_n::converter_from_lines;
+
+    #[test]
+    fn test_two_hexes() {
+        // 2x1x1 block: nodes 0..5 on z=0 and 6..11 on z=1, x varying fastest
+        let converter = converter_from_lines(&["0 0 1 3 4 6 7 9 10", "1 1 2 4 5 7 8 10 11"]);
+
+        let faces = extract_boundary_faces(&converter, ShapeFunctionKind::Cube1);
+
+        assert_eq!(faces.len(), 10);
+        assert!(faces.iter().all(|face| face.len() == 4));
+
+        // The internal face x=1 of element 0 (x=0 of element 1) is excluded
+        assert!(!faces.iter().any(|face| sorted_key(face) == vec![1, 4, 7, 10]));
+
+        // Outer faces in x are kept
+        assert!(faces.contains(&vec![0, 3, 6, 9]));
+        assert!(faces.contains(&vec![2, 5, 8, 11]));
+    }
+
+    #[test]
+    fn test_two_quads() {
+        // 2x1 quads: nodes 0..2 on y=0 and 3..5 on y=1
+        let converter = converter_from_lines(&["0 0 1 3 4", "1 1 2 4 5"]);
+
+        let edges = extract_boundary_faces(&converter, ShapeFunctionKind::Square1);
+
+        assert_eq!(edges, vec![
+            vec![0, 3], vec![0, 1], vec![3, 4],
+            vec![2, 5], vec![1, 2], vec![4, 5],
+        ]);
+    }
+}
@@ -0,0 +1,116 @@
+//! Welding of geometrically coincident nodes.
+//!
+//! Meshes assembled from several parts repeat the nodes of the shared interfaces under distinct
+//! ids. `weld_nodes` collapses nodes closer than a tolerance and returns the mapping
+//! `old_to_new[old_id] = new_id`, in the convention of `reorder::apply_permutation`, which
+//! rewrites the element connectivity accordingly.
+
+use std::collections::HashMap;
+
+use ndarray::{Array2, ArrayView1};
+
+/// Merges nodes within `tol` (Euclidean distance) of each other.
+///
+/// `coords` has shape (DIM, n_nodes). Nodes are visited in id order and bucketed in a hash of
+/// cells of size `tol`, so only the 3^DIM cells around a node are searched. A node is merged into
+/// the first kept node within `tol`, otherwise it is kept with the next new id; new ids therefore
+/// follow the order of the first node of every cluster, and the welded coordinates are those of
+/// that first node.
+///
+/// The merge is greedy and not transitive: only kept nodes attract others. If a is within `tol`
+/// of b and b of c, but a is not within `tol` of c, then b is merged into a and c stays separate.
+///
+/// Returns the welded coordinates, with shape (DIM, n_welded), and `old_to_new`.
+///
+/// # Panics
+/// Panics if `tol` is not positive.
+pub fn weld_nodes(coords: &Array2<f64>, tol: f64) -> (Array2<f64>, Vec<u32>) {
+    assert!(tol > 0.0, "Weld tolerance must be positive, got {}", tol);
+    let dim = coords.nrows();
+
+    let mut old_to_new: Vec<u32> = Vec::with_capacity(coords.ncols());
+    let mut kept: Vec<usize> = Vec::new();
+    let mut cells: HashMap<Vec<i64>, Vec<u32>> = HashMap::new();
+
+    for (old_id, node) in coords.columns().into_iter().enumerate() {
+        let cell: Vec<i64> = node.iter().map(|&x| (x / tol).floor() as i64).collect();
+
+        let existing = neighbour_cells(&cell).find_map(|neighbour| {
+            cells.get(&neighbour)?.iter().copied().find(|&new_id| {
+                distance_squared(node, coords.column(kept[new_id as usize])) <= tol * tol
+            })
+        });
+
+        let new_id = existing.unwrap_or_else(|| {
+            let new_id = kept.len() as u32;
+            kept.push(old_id);
+            cells.entry(cell).or_default().push(new_id);
+            new_id
+        });
+        old_to_new.push(new_id);
+    }
+
+    let welded = Array2::from_shape_fn((dim, kept.len()), |(d, new_id)| coords[[d, kept[new_id]]]);
+    (welded, old_to_new)
+}
+
+/// The 3^DIM cells whose offsets from `cell` are in {-1, 0, 1} along every axis
+fn neighbour_cells(cell: &[i64]) -> impl Iterator<Item = Vec<i64>> + '_ {
+    (0..3_usize.pow(cell.len() as u32)).map(move |mut code| {
+        cell.iter()
+            .map(|&c| {
+                let offset = (code % 3) as i64 - 1;
+                code /= 3;
+                c.saturating_add(offset)
+            })
+            .collect()
+    })
+}
+
+fn distance_squared(a: ArrayView1<f64>, b: ArrayView1<f64>) -> f64 {
+    a.iter().zip(b.iter()).map(|(x, y)| (x - y) * (x - y)).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mesh::reorder::apply_permutation;
+    use ndarray::array;
+
+    #[test]
+    fn test_weld_coincident_nodes() {
+        // Two quadrilaterals meshed separately, sharing the edge x = 1 through nodes 1/4 and 2/7
+        let coords = array![
+            [0.0, 1.0, 1.0, 0.0, 1.0, 2.0, 2.0, 1.0],
+            [0.0, 0.0, 1.0, 1.0, 0.0, 0.0, 1.0, 1.0],
+        ];
+        let elements = vec![vec![0, 1, 2, 3], vec![4, 5, 6, 7]];
+
+        let (welded, old_to_new) = weld_nodes(&coords, 1e-9);
+        assert_eq!(old_to_new, vec![0, 1, 2, 3, 1, 4, 5, 2]);
+        assert_eq!(welded.shape(), [2, 6]);
+        assert_eq!(welded.column(4).to_vec(), vec![2.0, 0.0]);
+
+        let remapped = apply_permutation(&elements, &old_to_new);
+        assert_eq!(remapped, vec![vec![0, 1, 2, 3], vec![1, 4, 5, 2]]);
+        for (old_nodes, new_nodes) in elements.iter().zip(&remapped) {
+            for (&old_id, &new_id) in old_nodes.iter().zip(new_nodes) {
+                assert_eq!(coords.column(old_id as usize), welded.column(new_id as usize));
+            }
+        }
+    }
+
+    #[test]
+    fn test_weld_is_greedy() {
+        // a-b and b-c are within tolerance, a-c is not; b also straddles a cell boundary
+        let coords = array![[0.0, 0.9, 1.8], [0.0, 0.0, 0.0], [0.0, 0.0, 0.0]];
+        let (welded, old_to_new) = weld_nodes(&coords, 1.0);
+        assert_eq!(old_to_new, vec![0, 0, 1]);
+        assert_eq!(welded, array![[0.0, 1.8], [0.0, 0.0], [0.0, 0.0]]);
+
+        // Nodes further apart than the tolerance are kept
+        let (welded, old_to_new) = weld_nodes(&coords, 0.5);
+        assert_eq!(old_to_new, vec![0, 1, 2]);
+        assert_eq!(welded, coords);
+    }
+}
@@ -6,9 +6,11 @@
 //! ## Features:
 //! - Memory-mapped file I/O for instant loading
 //! - Zero-copy parsing with proper alignment
-//! - SIMD-accelerated operations
-//! - Parallel processing support
+//! - SIMD-accelerated operations (4-lane folds with the `simd` feature, e.g. `centroid_3d`)
+//! - Parallel processing support: a loaded file is read-only and `Sync`, `shared` wraps it in an
+//!   `Arc` for concurrent readers
 //! - Checksum validation
+//! - f64 or f32 coordinate storage; `coordinate` and `node_coords` read either as f64
 
 use std::mem::size_of;
 use std::sync::Arc;
@@ -17,6 +19,8 @@ use memmap2::Mmap;
 use bytemuck::{bytes_of, cast_slice, try_cast_slice};
 use twox_hash::XxHash64;
 use std::hash::Hasher;
+use ndarray::Array2;
+use serde::{Deserialize, Serialize};
 
 // =============================================================================
 // Core Data Structures
@@ -29,7 +33,7 @@ use std::hash::Hasher;
 pub struct NodeHeader {
     /// Magic bytes identifying the file format: "HYPERNOD"
     pub magic: [u8; 8],
-    /// Format version (currently 1)
+    /// Format version: 1, or 2 when the file may carry a metadata block
     pub version: u64,
     /// Coordinate data type: 0 = f32, 1 = f64
     pub coordinate_type: u8,
@@ -37,7 +41,7 @@ pub struct NodeHeader {
     pub dimensions: u8,
     /// Endianness: 0 = little, 1 = big
     pub endianness: u8,
-    /// Reserved for future flags
+    /// Bit flags, see `FLAG_METADATA`
     pub flags: u8,
     /// Total number of nodes in the file
     pub node_count: u64,
@@ -47,6 +51,34 @@ pub struct NodeHeader {
     pub checksum: u128,
 }
 
+impl NodeHeader {
+    /// Bytes per stored coordinate: 4 for f32 (`coordinate_type` 0), 8 for f64 (1)
+    pub fn coordinate_size(&self) -> usize {
+        if self.coordinate_type == 0 { size_of::<f32>() } else { size_of::<f64>() }
+    }
+}
+
+/// Set in `NodeHeader::flags` of version-2 files when a metadata block follows the header.
+///
+/// The block is a little-endian u64 byte length followed by the bincode-encoded `NodeMetadata`.
+/// `data_offset` points past it (rounded up to the header alignment), so readers that only
+/// follow `data_offset` skip the metadata.
+pub const FLAG_METADATA: u8 = 1;
+
+/// Descriptive information stored in the version-2 metadata block.
+/// Not covered by the checksum, which only protects the coordinate data.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct NodeMetadata {
+    /// Creation time, e.g. seconds since the Unix epoch
+    pub creation_timestamp: Option<u64>,
+    /// Name of the coordinate system, e.g. "Cartesian"
+    pub coordinate_system: Option<String>,
+    /// Length units of the coordinates
+    pub units: Option<String>,
+    /// Compression applied by the producer, if any
+    pub compression: Option<String>,
+}
+
 // Safe to transmute NodeHeader because it's repr(C) and contains only POD types
 unsafe impl bytemuck::Pod for NodeHeader {}
 unsafe impl bytemuck::Zeroable for NodeHeader {}
@@ -116,7 +148,16 @@ pub enum HyperNodeError {
     DataSizeMismatch,
     InvalidCoordinateType(u8),
     InvalidDataOffset,
-    AlignmentError
+    AlignmentError,
+    InvalidMetadata(String),
+    /// NaN or infinite value at the given coordinate index (node index * dimensions + component)
+    NonFiniteCoordinate { index: usize },
+    /// Node index not below `node_count`
+    NodeOutOfRange { node: u64, node_count: u64 },
+    /// Axis not below `dimensions`
+    AxisOutOfRange { axis: u8, dimensions: u8 },
+    /// More dimensions than the u8 `dimensions` header field can store
+    TooManyDimensions(usize),
 }
 
 impl std::fmt::Display for HyperNodeError {
@@ -130,7 +171,19 @@ impl std::fmt::Display for HyperNodeError {
             HyperNodeError::DataSizeMismatch => write!(f, "Data size mismatch"),
             HyperNodeError::InvalidCoordinateType(t) => write!(f, "Invalid coordinate type: {}", t),
             HyperNodeError::InvalidDataOffset => write!(f, "Invalid data offset"),
-            HyperNodeError::AlignmentError => write!(f, "Data is not properly aligned for zero-copy access"),
+            HyperNodeError::AlignmentError => write!(
+                f,
+                "Data is not properly aligned for zero-copy access, use get_nodes_2d_owned (or 3d/4d) to copy it"
+            ),
+            HyperNodeError::InvalidMetadata(msg) => write!(f, "Invalid metadata: {}", msg),
+            HyperNodeError::NonFiniteCoordinate { index } => write!(f, "Non-finite coordinate at index {}", index),
+            HyperNodeError::NodeOutOfRange { node, node_count } => {
+                write!(f, "Node {} out of range for {} nodes", node, node_count)
+            }
+            HyperNodeError::AxisOutOfRange { axis, dimensions } => {
+                write!(f, "Axis {} out of range for {} dimensions", axis, dimensions)
+            }
+            HyperNodeError::TooManyDimensions(d) => write!(f, "Too many dimensions: {}", d),
         }
     }
 }
@@ -143,7 +196,11 @@ impl From<std::io::Error> for HyperNodeError {
     }
 }
 
-/// Main HyperNode file structure
+/// Main HyperNode file structure.
+///
+/// All methods take `&self`: once loaded, the file is only read, whether the bytes are
+/// memory-mapped or owned. It is therefore `Send + Sync`, and `shared` returns an
+/// `Arc<HyperNodeFile>` that several threads can read concurrently.
 #[derive(Debug)]
 pub struct HyperNodeFile {
     /// File header containing format information
@@ -152,6 +209,12 @@ pub struct HyperNodeFile {
     pub data: NodeData,
 }
 
+// Keeps `HyperNodeFile` shareable across threads if its fields change
+const _: () = {
+    const fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<HyperNodeFile>();
+};
+
 // =============================================================================
 // Implementation - File Creation
 // =============================================================================
@@ -160,6 +223,33 @@ impl HyperNodeFile {
     pub fn create_from_nodes_f64(
         nodes: &[f64],
         dimensions: u8,
+    ) -> Result<Vec<u8>, HyperNodeError> {
+        Self::create_buffer(nodes, 1, dimensions, None)
+    }
+
+    /// Creates a file storing the coordinates in single precision (`coordinate_type` 0), half the
+    /// size of `create_from_nodes_f64`. Read it back with `coordinate`, `node_coords` or `to_ndarray`.
+    pub fn create_from_nodes_f32(
+        nodes: &[f32],
+        dimensions: u8,
+    ) -> Result<Vec<u8>, HyperNodeError> {
+        Self::create_buffer(nodes, 0, dimensions, None)
+    }
+
+    /// Creates a version-2 file with a metadata block between the header and the coordinates
+    pub fn create_from_nodes_f64_with_metadata(
+        nodes: &[f64],
+        dimensions: u8,
+        metadata: &NodeMetadata,
+    ) -> Result<Vec<u8>, HyperNodeError> {
+        Self::create_buffer(nodes, 1, dimensions, Some(metadata))
+    }
+
+    fn create_buffer<T: bytemuck::Pod>(
+        nodes: &[T],
+        coordinate_type: u8,
+        dimensions: u8,
+        metadata: Option<&NodeMetadata>,
     ) -> Result<Vec<u8>, HyperNodeError> {
         if !(2..=4).contains(&dimensions) {
             return Err(HyperNodeError::InvalidDimensions(dimensions));
@@ -171,9 +261,22 @@ impl HyperNodeFile {
 
         let node_count = nodes.len() / dimensions as usize;
         let header_size = size_of::<NodeHeader>();
+
+        let metadata_block: Vec<u8> = match metadata {
+            Some(metadata) => {
+                let blob = bincode::serialize(metadata).map_err(|e| HyperNodeError::InvalidMetadata(e.to_string()))?;
+                let mut block = (blob.len() as u64).to_le_bytes().to_vec();
+                block.extend_from_slice(&blob);
+                block
+            }
+            None => Vec::new(),
+        };
+
+        // Keep the coordinates aligned like the header
+        let data_start = (header_size + metadata_block.len()).next_multiple_of(std::mem::align_of::<NodeHeader>());
         
-        let data_size = nodes.len() * size_of::<f64>();
-        let total_size = header_size + data_size;
+        let data_size = std::mem::size_of_val(nodes);
+        let total_size = data_start + data_size;
         
         // Use a properly aligned vector
         let mut buffer = Vec::with_capacity(total_size);
@@ -181,18 +284,19 @@ impl HyperNodeFile {
 
         let mut header = NodeHeader {
             magic: *b"HYPERNOD",
-            version: 1,
-            coordinate_type: 1,
+            version: if metadata.is_some() { 2 } else { 1 },
+            coordinate_type,
             dimensions,
             endianness: 0,
-            flags: 0,
+            flags: if metadata.is_some() { FLAG_METADATA } else { 0 },
             node_count: node_count as u64,
-            data_offset: header_size as u64,
+            data_offset: data_start as u64,
             checksum: 0,
         };
 
+        buffer[header_size..header_size + metadata_block.len()].copy_from_slice(&metadata_block);
+
         let nodes_bytes = cast_slice(nodes);
-        let data_start = header_size;
         buffer[data_start..data_start + nodes_bytes.len()].copy_from_slice(nodes_bytes);
 
         let data_section = &buffer[data_start..];
@@ -221,6 +325,11 @@ impl HyperNodeFile {
         Self::from_bytes(NodeData::MemoryMapped(Arc::new(mmap)))
     }
 
+    /// Wraps the file in an `Arc` to read it from several threads
+    pub fn shared(self) -> Arc<Self> {
+        Arc::new(self)
+    }
+
     pub fn from_bytes(data: NodeData) -> Result<Self, HyperNodeError> {
         let bytes = match &data {
             NodeData::MemoryMapped(mmap) => &mmap[..],
@@ -229,10 +338,11 @@ impl HyperNodeFile {
 
         Self::validate_bytes(bytes)?;
 
-        let header: &NodeHeader = bytemuck::from_bytes(&bytes[..size_of::<NodeHeader>()]);
+        // Owned buffers are not guaranteed to be aligned like the header
+        let header: NodeHeader = bytemuck::pod_read_unaligned(&bytes[..size_of::<NodeHeader>()]);
 
         Ok(Self {
-            header: *header,
+            header,
             data,
         })
     }
@@ -268,29 +378,19 @@ impl HyperNodeFile {
                 &aligned_header.clone()
             };
 
-            if header.magic != *b"HYPERNOD" {
-                return Err(HyperNodeError::InvalidMagic);
-            }
-
-            if header.version != 1 {
-                return Err(HyperNodeError::UnsupportedVersion(header.version));
-            }
-
-            if !(2..=4).contains(&header.dimensions) {
-                return Err(HyperNodeError::InvalidDimensions(header.dimensions));
-            }
-
-            if header.coordinate_type != 1 {
-                return Err(HyperNodeError::InvalidCoordinateType(header.coordinate_type));
-            }
+            Self::validate_header(header)?;
 
             let data_start = header.data_offset as usize;
             if data_start > bytes.len() {
                 return Err(HyperNodeError::InvalidDataOffset);
             }
 
+            if header.version >= 2 && header.flags & FLAG_METADATA != 0 {
+                Self::decode_metadata(&bytes[..data_start])?;
+            }
+
             // Calculate expected data size
-            let node_size = header.dimensions as usize * size_of::<f64>();
+            let node_size = header.dimensions as usize * header.coordinate_size();
             let expected_data_size = header.node_count as usize * node_size;
             
             if data_start + expected_data_size > bytes.len() {
@@ -309,6 +409,171 @@ impl HyperNodeFile {
         Ok(())
     }
 
+    // Header fields checked by every reader
+    fn validate_header(header: &NodeHeader) -> Result<(), HyperNodeError> {
+        if header.magic != *b"HYPERNOD" {
+            return Err(HyperNodeError::InvalidMagic);
+        }
+
+        if !(1..=2).contains(&header.version) {
+            return Err(HyperNodeError::UnsupportedVersion(header.version));
+        }
+
+        if !(2..=4).contains(&header.dimensions) {
+            return Err(HyperNodeError::InvalidDimensions(header.dimensions));
+        }
+
+        if header.coordinate_type > 1 {
+            return Err(HyperNodeError::InvalidCoordinateType(header.coordinate_type));
+        }
+
+        Ok(())
+    }
+
+    /// Reads the coordinates of nodes `start..start + count` from a file, node after node, without
+    /// reading the rest of the data section. f32 coordinates are widened to f64.
+    ///
+    /// Only the header is validated. The checksum covers the whole data section and is not
+    /// verified, so the integrity of the returned subrange is not guaranteed; use `validate_bytes`
+    /// (or `load_memory_mapped`) when that matters. Returns `DataSizeMismatch` if the range
+    /// exceeds `node_count` or the file is truncated.
+    pub fn read_range(path: &str, start: u64, count: u64) -> Result<Vec<f64>, HyperNodeError> {
+        let file = std::fs::File::open(path)?;
+        if (file.metadata()?.len() as usize) < size_of::<NodeHeader>() {
+            return Err(HyperNodeError::DataSizeMismatch);
+        }
+        let mmap = unsafe { Mmap::map(&file)? };
+
+        let header: NodeHeader = bytemuck::pod_read_unaligned(&mmap[..size_of::<NodeHeader>()]);
+        Self::validate_header(&header)?;
+
+        let end = start.checked_add(count).ok_or(HyperNodeError::DataSizeMismatch)?;
+        if end > header.node_count {
+            return Err(HyperNodeError::DataSizeMismatch);
+        }
+
+        // The header is untrusted, so a crafted node count must not overflow the byte offsets
+        let node_size = header.dimensions as usize * header.coordinate_size();
+        let byte_offset = |node: u64| {
+            usize::try_from(node)
+                .ok()
+                .and_then(|node| node.checked_mul(node_size))
+                .and_then(|size| (header.data_offset as usize).checked_add(size))
+                .ok_or(HyperNodeError::DataSizeMismatch)
+        };
+        let range_start = byte_offset(start)?;
+        let range_end = byte_offset(end)?;
+        let bytes = mmap.get(range_start..range_end).ok_or(HyperNodeError::DataSizeMismatch)?;
+
+        Ok(bytes.chunks_exact(header.coordinate_size()).map(read_coordinate).collect())
+    }
+
+    /// Rewrites a file in the opposite byte order, in place.
+    ///
+    /// Every coordinate and the multi-byte header fields are byte-swapped, `endianness` is toggled
+    /// and the checksum is recomputed over the swapped data, so the result passes `validate_bytes`
+    /// on a machine of the target byte order. The input may be in either byte order. The
+    /// version-2 metadata block is always little-endian and is left untouched.
+    pub fn convert_endianness(bytes: &mut [u8]) -> Result<(), HyperNodeError> {
+        let header_size = size_of::<NodeHeader>();
+        if bytes.len() < header_size {
+            return Err(HyperNodeError::DataSizeMismatch);
+        }
+
+        let stored: NodeHeader = bytemuck::pod_read_unaligned(&bytes[..header_size]);
+        if stored.magic != *b"HYPERNOD" {
+            return Err(HyperNodeError::InvalidMagic);
+        }
+
+        // A foreign byte order shows up as a nonsensical version number
+        let is_native = (1..=2).contains(&stored.version);
+        let header = if is_native { stored } else { swap_header_bytes(stored) };
+
+        if !(1..=2).contains(&header.version) {
+            return Err(HyperNodeError::UnsupportedVersion(header.version));
+        }
+        if !(2..=4).contains(&header.dimensions) {
+            return Err(HyperNodeError::InvalidDimensions(header.dimensions));
+        }
+        if header.coordinate_type > 1 {
+            return Err(HyperNodeError::InvalidCoordinateType(header.coordinate_type));
+        }
+
+        let data_start = header.data_offset as usize;
+        if data_start < header_size || data_start > bytes.len() {
+            return Err(HyperNodeError::InvalidDataOffset);
+        }
+        let data_end = (header.node_count as usize)
+            .checked_mul(header.dimensions as usize * header.coordinate_size())
+            .and_then(|data_size| data_start.checked_add(data_size))
+            .filter(|&data_end| data_end <= bytes.len())
+            .ok_or(HyperNodeError::DataSizeMismatch)?;
+
+        for coordinate in bytes[data_start..data_end].chunks_exact_mut(header.coordinate_size()) {
+            coordinate.reverse();
+        }
+
+        let mut converted = header;
+        converted.endianness ^= 1;
+        converted.checksum = calculate_checksum(&bytes[data_start..data_end]);
+        if is_native {
+            converted = swap_header_bytes(converted);
+        }
+
+        // Patch field by field, so the header padding bytes are left as they were
+        let mut write_field = |offset: usize, field: &[u8]| bytes[offset..offset + field.len()].copy_from_slice(field);
+        write_field(std::mem::offset_of!(NodeHeader, version), &converted.version.to_ne_bytes());
+        write_field(std::mem::offset_of!(NodeHeader, endianness), &[converted.endianness]);
+        write_field(std::mem::offset_of!(NodeHeader, node_count), &converted.node_count.to_ne_bytes());
+        write_field(std::mem::offset_of!(NodeHeader, data_offset), &converted.data_offset.to_ne_bytes());
+        write_field(std::mem::offset_of!(NodeHeader, checksum), &converted.checksum.to_ne_bytes());
+
+        Ok(())
+    }
+
+    /// Checks that every coordinate is finite, returning the first NaN or infinity found.
+    ///
+    /// Not part of `validate_bytes`, since it reads the whole data section; recommended for
+    /// untrusted input, where the checksum only proves the data was not altered after writing.
+    pub fn validate_finite(&self) -> Result<(), HyperNodeError> {
+        let bytes = self.get_nodes()?;
+        match bytes
+            .chunks_exact(self.header.coordinate_size())
+            .position(|chunk| !read_coordinate(chunk).is_finite())
+        {
+            Some(index) => Err(HyperNodeError::NonFiniteCoordinate { index }),
+            None => Ok(()),
+        }
+    }
+
+    /// Metadata block of a version-2 file, or `None` if the file has none
+    pub fn metadata(&self) -> Option<NodeMetadata> {
+        if self.header.version < 2 || self.header.flags & FLAG_METADATA == 0 {
+            return None;
+        }
+
+        let bytes = match &self.data {
+            NodeData::MemoryMapped(mmap) => &mmap[..],
+            NodeData::Owned(vec) => &vec[..],
+        };
+        Self::decode_metadata(bytes.get(..self.header.data_offset as usize)?).ok()
+    }
+
+    // `bytes` runs from the start of the file up to `data_offset`
+    fn decode_metadata(bytes: &[u8]) -> Result<NodeMetadata, HyperNodeError> {
+        let block = bytes.get(size_of::<NodeHeader>()..).unwrap_or(&[]);
+        let (length, blob) = block
+            .split_first_chunk::<8>()
+            .ok_or(HyperNodeError::InvalidDataOffset)?;
+
+        let blob = usize::try_from(u64::from_le_bytes(*length))
+            .ok()
+            .and_then(|length| blob.get(..length))
+            .ok_or(HyperNodeError::InvalidDataOffset)?;
+
+        bincode::deserialize(blob).map_err(|e| HyperNodeError::InvalidMetadata(e.to_string()))
+    }
+
     pub fn get_nodes(&self) -> Result<&[u8], HyperNodeError> {
         let bytes = match &self.data {
             NodeData::MemoryMapped(mmap) => &mmap[..],
@@ -316,7 +581,7 @@ impl HyperNodeFile {
         };
 
         let data_start = self.header.data_offset as usize;
-        let node_size = self.header.dimensions as usize * size_of::<f64>();
+        let node_size = self.header.dimensions as usize * self.header.coordinate_size();
         let data_end = data_start + self.header.node_count as usize * node_size;
 
         if data_end > bytes.len() {
@@ -326,41 +591,179 @@ impl HyperNodeFile {
         Ok(&bytes[data_start..data_end])
     }
 
-    pub fn get_nodes_2d(&self) -> Result<&[Node2D], HyperNodeError> {
-        if self.header.dimensions != 2 {
-            return Err(HyperNodeError::InvalidDimensions(self.header.dimensions));
+    /// Coordinate `axis` of node `node`, widened to f64 for f32 files.
+    ///
+    /// Reads only the bytes of that coordinate, so it works for any alignment of the data.
+    pub fn coordinate(&self, node: u64, axis: u8) -> Result<f64, HyperNodeError> {
+        if axis >= self.header.dimensions {
+            return Err(HyperNodeError::AxisOutOfRange { axis, dimensions: self.header.dimensions });
         }
-        
-        let bytes = self.get_nodes()?;
-        
-        // Handle alignment for Vec<u8> by copying if necessary
-        if (bytes.as_ptr() as usize) % std::mem::align_of::<Node2D>() == 0 {
-            Ok(try_cast_slice(bytes).map_err(|_| HyperNodeError::DataSizeMismatch)?)
-        } else {
-            // Fallback: copy to aligned storage (not implemented here for simplicity)
-            Err(HyperNodeError::AlignmentError)
+        let coordinates = self.node_bytes(node)?;
+        let coordinate_size = self.header.coordinate_size();
+        let start = axis as usize * coordinate_size;
+        Ok(read_coordinate(&coordinates[start..start + coordinate_size]))
+    }
+
+    /// All coordinates of node `node`, widened to f64 for f32 files
+    pub fn node_coords(&self, node: u64) -> Result<Vec<f64>, HyperNodeError> {
+        Ok(self
+            .node_bytes(node)?
+            .chunks_exact(self.header.coordinate_size())
+            .map(read_coordinate)
+            .collect())
+    }
+
+    // Stored bytes of one node
+    fn node_bytes(&self, node: u64) -> Result<&[u8], HyperNodeError> {
+        if node >= self.header.node_count {
+            return Err(HyperNodeError::NodeOutOfRange { node, node_count: self.header.node_count });
         }
+        let node_size = self.header.dimensions as usize * self.header.coordinate_size();
+        let start = node as usize * node_size;
+        Ok(&self.get_nodes()?[start..start + node_size])
+    }
+
+    /// Zero-copy view of 2D nodes of an f64 file. Fails with `AlignmentError` if the data is misaligned,
+    /// which can happen for owned buffers; `get_nodes_2d_owned` works for any alignment.
+    pub fn get_nodes_2d(&self) -> Result<&[Node2D], HyperNodeError> {
+        self.get_nodes_cast(2)
     }
 
     pub fn get_nodes_3d(&self) -> Result<&[Node3D], HyperNodeError> {
-        if self.header.dimensions != 3 {
+        self.get_nodes_cast(3)
+    }
+
+    pub fn get_nodes_4d(&self) -> Result<&[Node4D], HyperNodeError> {
+        self.get_nodes_cast(4)
+    }
+
+    /// Copy of the 2D nodes into aligned storage, for any alignment of the underlying bytes
+    pub fn get_nodes_2d_owned(&self) -> Result<Vec<Node2D>, HyperNodeError> {
+        self.get_nodes_copied(2)
+    }
+
+    pub fn get_nodes_3d_owned(&self) -> Result<Vec<Node3D>, HyperNodeError> {
+        self.get_nodes_copied(3)
+    }
+
+    pub fn get_nodes_4d_owned(&self) -> Result<Vec<Node4D>, HyperNodeError> {
+        self.get_nodes_copied(4)
+    }
+
+    /// Iterates over copies of the 3D nodes, for any alignment of the underlying bytes,
+    /// without collecting them as `get_nodes_3d_owned` does
+    pub fn iter_nodes_3d(&self) -> Result<impl Iterator<Item = Node3D> + '_, HyperNodeError> {
+        self.iter_nodes_copied(3)
+    }
+
+    fn get_nodes_cast<T: bytemuck::Pod>(&self, dimensions: u8) -> Result<&[T], HyperNodeError> {
+        if self.header.dimensions != dimensions {
             return Err(HyperNodeError::InvalidDimensions(self.header.dimensions));
         }
-        
+        if self.header.coordinate_type != 1 {
+            return Err(HyperNodeError::InvalidCoordinateType(self.header.coordinate_type));
+        }
+
         let bytes = self.get_nodes()?;
-        Ok(try_cast_slice(bytes).map_err(|_| HyperNodeError::DataSizeMismatch)?)
+        if !bytes.as_ptr().cast::<T>().is_aligned() {
+            return Err(HyperNodeError::AlignmentError);
+        }
+        try_cast_slice(bytes).map_err(|_| HyperNodeError::DataSizeMismatch)
     }
 
-    pub fn get_nodes_4d(&self) -> Result<&[Node4D], HyperNodeError> {
-        if self.header.dimensions != 4 {
+    fn get_nodes_copied<T: bytemuck::Pod>(&self, dimensions: u8) -> Result<Vec<T>, HyperNodeError> {
+        Ok(self.iter_nodes_copied(dimensions)?.collect())
+    }
+
+    fn iter_nodes_copied<T: bytemuck::Pod>(&self, dimensions: u8) -> Result<impl Iterator<Item = T> + '_, HyperNodeError> {
+        if self.header.dimensions != dimensions {
             return Err(HyperNodeError::InvalidDimensions(self.header.dimensions));
         }
-        
+        if self.header.coordinate_type != 1 {
+            return Err(HyperNodeError::InvalidCoordinateType(self.header.coordinate_type));
+        }
+
+        Ok(self
+            .get_nodes()?
+            .chunks_exact(size_of::<T>())
+            .map(bytemuck::pod_read_unaligned))
+    }
+
+    /// Mean position of the 3D nodes. Requires aligned data (e.g. memory-mapped files), as `get_nodes_3d`.
+    ///
+    /// With the `simd` feature the sums are folded over 4 independent lanes, which the compiler
+    /// vectorizes; otherwise a scalar loop is used. Both agree up to rounding.
+    pub fn centroid_3d(&self) -> Result<[f64; 3], HyperNodeError> {
+        let nodes = self.get_nodes_3d()?;
+        if nodes.is_empty() {
+            return Err(HyperNodeError::DataSizeMismatch);
+        }
+
+        #[cfg(feature = "simd")]
+        let sum = sum_3d_lanes(nodes);
+        #[cfg(not(feature = "simd"))]
+        let sum = sum_3d_scalar(nodes);
+
+        let count = nodes.len() as f64;
+        Ok(sum.map(|component| component / count))
+    }
+
+    /// Copies the coordinates into a (dimensions, node_count) array, one node per column,
+    /// as returned by `read_nodes`. Works for any alignment of the underlying bytes, and widens
+    /// f32 coordinates to f64.
+    pub fn to_ndarray(&self) -> Result<Array2<f64>, HyperNodeError> {
         let bytes = self.get_nodes()?;
-        Ok(try_cast_slice(bytes).map_err(|_| HyperNodeError::DataSizeMismatch)?)
+        let coordinates: Vec<f64> = bytes
+            .chunks_exact(self.header.coordinate_size())
+            .map(read_coordinate)
+            .collect();
+
+        let dimensions = self.header.dimensions as usize;
+        let node_count = self.header.node_count as usize;
+        Ok(Array2::from_shape_fn((dimensions, node_count), |(row, col)| coordinates[col * dimensions + row]))
+    }
+
+    /// Serializes a (dimensions, node_count) array, one node per column, with `create_from_nodes_f64`
+    pub fn from_ndarray(coords: &Array2<f64>) -> Result<Vec<u8>, HyperNodeError> {
+        let dimensions = u8::try_from(coords.nrows()).map_err(|_| HyperNodeError::TooManyDimensions(coords.nrows()))?;
+
+        // Node-major order: x0, y0, x1, y1, ...
+        let nodes: Vec<f64> = coords.t().iter().copied().collect();
+        Self::create_from_nodes_f64(&nodes, dimensions)
     }
 }
 
+// Component-wise sum of the nodes for `centroid_3d`
+fn sum_3d_scalar(nodes: &[Node3D]) -> [f64; 3] {
+    nodes
+        .iter()
+        .fold([0.0; 3], |[x, y, z], node| [x + node.x, y + node.y, z + node.z])
+}
+
+// Four partial sums per component, one per lane, reduced at the end
+fn sum_3d_lanes(nodes: &[Node3D]) -> [f64; 3] {
+    const LANES: usize = 4;
+    let mut x = [0.0; LANES];
+    let mut y = [0.0; LANES];
+    let mut z = [0.0; LANES];
+
+    let chunks = nodes.chunks_exact(LANES);
+    let remainder = sum_3d_scalar(chunks.remainder());
+    for chunk in chunks {
+        for lane in 0..LANES {
+            x[lane] += chunk[lane].x;
+            y[lane] += chunk[lane].y;
+            z[lane] += chunk[lane].z;
+        }
+    }
+
+    [
+        x.iter().sum::<f64>() + remainder[0],
+        y.iter().sum::<f64>() + remainder[1],
+        z.iter().sum::<f64>() + remainder[2],
+    ]
+}
+
 // Simple hash function for demonstration - replace with xxHash3 in production
 fn simple_hash(data: &[u8]) -> u128 {
     use std::hash::Hasher;
@@ -369,7 +772,27 @@ fn simple_hash(data: &[u8]) -> u128 {
     (hasher.finish() as u128) << 64 | hasher.finish() as u128
 }
 
-fn calculate_checksum(data: &[u8]) -> u128 {
+// Widens one stored coordinate, 4 bytes for f32 or 8 bytes for f64, to f64
+fn read_coordinate(bytes: &[u8]) -> f64 {
+    match bytes.len() {
+        4 => f32::from_ne_bytes(bytes.try_into().unwrap()) as f64,
+        _ => f64::from_ne_bytes(bytes.try_into().unwrap()),
+    }
+}
+
+// Byte-swaps the multi-byte fields of a header
+fn swap_header_bytes(header: NodeHeader) -> NodeHeader {
+    NodeHeader {
+        version: header.version.swap_bytes(),
+        node_count: header.node_count.swap_bytes(),
+        data_offset: header.data_offset.swap_bytes(),
+        checksum: header.checksum.swap_bytes(),
+        ..header
+    }
+}
+
+/// 128-bit xxHash checksum of the HyperNode format, also used by other checksummed files
+pub(crate) fn calculate_checksum(data: &[u8]) -> u128 {
     
     // Use xxHash64 for maximum performance
     let mut hasher = XxHash64::with_seed(0);
@@ -445,6 +868,264 @@ mod tests {
         HyperNodeFile::validate_bytes(&data).expect("Empty nodes should be valid");
     }
 
+    #[test]
+    fn test_version_2_metadata() {
+        let coords = vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0];
+        let metadata = NodeMetadata {
+            creation_timestamp: Some(1234567890),
+            coordinate_system: Some("Cartesian".to_string()),
+            units: Some("meters".to_string()),
+            compression: None,
+        };
+
+        let data = HyperNodeFile::create_from_nodes_f64_with_metadata(&coords, 3, &metadata).unwrap();
+        HyperNodeFile::validate_bytes(&data).expect("Version 2 file with metadata should be valid");
+
+        let hypernode = HyperNodeFile::from_bytes(NodeData::Owned(data)).unwrap();
+        assert_eq!(hypernode.header.version, 2);
+        assert_eq!(hypernode.header.flags & FLAG_METADATA, FLAG_METADATA);
+        assert_eq!(hypernode.metadata(), Some(metadata));
+
+        // data_offset skips the header and the metadata block
+        let data_offset = hypernode.header.data_offset as usize;
+        assert!(data_offset > size_of::<NodeHeader>());
+        assert_eq!(data_offset % std::mem::align_of::<NodeHeader>(), 0);
+        let nodes: Vec<f64> = hypernode
+            .get_nodes()
+            .unwrap()
+            .chunks_exact(size_of::<f64>())
+            .map(|chunk| f64::from_ne_bytes(chunk.try_into().unwrap()))
+            .collect();
+        assert_eq!(nodes, coords);
+
+        // Version 1 files carry no metadata
+        let data = HyperNodeFile::create_from_nodes_f64(&coords, 3).unwrap();
+        let hypernode = HyperNodeFile::from_bytes(NodeData::Owned(data)).unwrap();
+        assert_eq!(hypernode.header.version, 1);
+        assert_eq!(hypernode.metadata(), None);
+    }
+
+    #[test]
+    fn test_centroid_3d() {
+        use rand::{rng, Rng};
+
+        // 1001 nodes, so the lane fold also has a remainder
+        let coords: Vec<f64> = (0..3 * 1001).map(|_| rng().random_range(-10.0..10.0)).collect();
+        let nodes: &[Node3D] = cast_slice(&coords);
+        let lanes = sum_3d_lanes(nodes);
+        let scalar = sum_3d_scalar(nodes);
+        for d in 0..3 {
+            assert!((lanes[d] - scalar[d]).abs() / 1001.0 < 1e-12);
+        }
+
+        // Memory-mapped file, so the nodes are aligned
+        let file = tempfile::NamedTempFile::new().unwrap();
+        std::fs::write(file.path(), HyperNodeFile::create_from_nodes_f64(&coords, 3).unwrap()).unwrap();
+        let hypernode = HyperNodeFile::load_memory_mapped(file.path().to_str().unwrap()).unwrap();
+        let centroid = hypernode.centroid_3d().unwrap();
+        for d in 0..3 {
+            assert!((centroid[d] - scalar[d] / 1001.0).abs() < 1e-12);
+        }
+
+        let data = HyperNodeFile::create_from_nodes_f64(&coords[..4], 2).unwrap();
+        let hypernode = HyperNodeFile::from_bytes(NodeData::Owned(data)).unwrap();
+        assert!(matches!(hypernode.centroid_3d(), Err(HyperNodeError::InvalidDimensions(2))));
+    }
+
+    #[test]
+    fn test_concurrent_reads_of_shared_file() {
+        let coords: Vec<f64> = (0..3 * 500).map(|i| i as f64 * 0.25).collect();
+        let expected_sum: f64 = coords.iter().sum();
+
+        let file = tempfile::NamedTempFile::new().unwrap();
+        std::fs::write(file.path(), HyperNodeFile::create_from_nodes_f64(&coords, 3).unwrap()).unwrap();
+        let mapped = HyperNodeFile::load_memory_mapped(file.path().to_str().unwrap()).unwrap().shared();
+        let owned = HyperNodeFile::from_bytes(NodeData::Owned(HyperNodeFile::create_from_nodes_f64(&coords, 3).unwrap()))
+            .unwrap()
+            .shared();
+
+        let handles: Vec<_> = (0..8)
+            .map(|thread| {
+                let hypernode = Arc::clone(if thread % 2 == 0 { &mapped } else { &owned });
+                std::thread::spawn(move || {
+                    // Owned buffers may be misaligned, the iterator works either way
+                    let iterated: f64 = hypernode.iter_nodes_3d().unwrap().map(|node| node.x + node.y + node.z).sum();
+                    let viewed: Option<f64> = hypernode.get_nodes_3d().ok().map(|nodes| nodes.iter().map(|node| node.x + node.y + node.z).sum());
+                    (iterated, viewed, hypernode.coordinate(499, 2).unwrap())
+                })
+            })
+            .collect();
+
+        for handle in handles {
+            let (iterated, viewed, last) = handle.join().unwrap();
+            assert_eq!(iterated, expected_sum);
+            assert!(viewed.is_none_or(|viewed| viewed == expected_sum));
+            assert_eq!(last, coords[3 * 499 + 2]);
+        }
+        assert!(mapped.get_nodes_3d().is_ok());
+        assert_eq!(Arc::strong_count(&mapped), 1);
+    }
+
+    #[test]
+    fn test_read_range() {
+        let coords: Vec<f64> = (0..300).map(|i| i as f64 * 0.5).collect();
+        let file = tempfile::NamedTempFile::new().unwrap();
+        std::fs::write(file.path(), HyperNodeFile::create_from_nodes_f64(&coords, 3).unwrap()).unwrap();
+        let path = file.path().to_str().unwrap();
+
+        // Middle 10 nodes of 100
+        assert_eq!(HyperNodeFile::read_range(path, 45, 10).unwrap(), coords[135..165].to_vec());
+        assert_eq!(HyperNodeFile::read_range(path, 0, 100).unwrap(), coords);
+        assert!(HyperNodeFile::read_range(path, 100, 0).unwrap().is_empty());
+
+        for (start, count) in [(95, 10), (100, 1), (u64::MAX, 2)] {
+            assert!(matches!(HyperNodeFile::read_range(path, start, count), Err(HyperNodeError::DataSizeMismatch)));
+        }
+
+        // A crafted node count lets the range pass the header check, but its byte offsets overflow
+        let mut data = HyperNodeFile::create_from_nodes_f64(&coords, 3).unwrap();
+        let node_count = std::mem::offset_of!(NodeHeader, node_count);
+        data[node_count..node_count + 8].copy_from_slice(&u64::MAX.to_ne_bytes());
+        std::fs::write(file.path(), data).unwrap();
+        for (start, count) in [(u64::MAX / 2, 1), (0, u64::MAX)] {
+            assert!(matches!(HyperNodeFile::read_range(path, start, count), Err(HyperNodeError::DataSizeMismatch)));
+        }
+    }
+
+    #[test]
+    fn test_owned_nodes_from_misaligned_buffer() {
+        let coords: Vec<f64> = (0..12).map(|i| i as f64 + 0.25).collect();
+        for dimensions in [2u8, 3, 4] {
+            // Shift the data section by one byte
+            let mut data = HyperNodeFile::create_from_nodes_f64(&coords, dimensions).unwrap();
+            let data_offset = std::mem::offset_of!(NodeHeader, data_offset);
+            let shifted = u64::from_ne_bytes(data[data_offset..data_offset + 8].try_into().unwrap()) + 1;
+            data[data_offset..data_offset + 8].copy_from_slice(&shifted.to_ne_bytes());
+            data.insert(shifted as usize - 1, 0);
+
+            let hypernode = HyperNodeFile::from_bytes(NodeData::Owned(data)).unwrap();
+            let misaligned = !hypernode.get_nodes().unwrap().as_ptr().cast::<f64>().is_aligned();
+            let flattened: Vec<f64> = match dimensions {
+                2 => {
+                    assert_eq!(misaligned, matches!(hypernode.get_nodes_2d(), Err(HyperNodeError::AlignmentError)));
+                    hypernode.get_nodes_2d_owned().unwrap().iter().flat_map(|n| [n.x, n.y]).collect()
+                }
+                3 => {
+                    assert_eq!(misaligned, matches!(hypernode.get_nodes_3d(), Err(HyperNodeError::AlignmentError)));
+                    hypernode.get_nodes_3d_owned().unwrap().iter().flat_map(|n| [n.x, n.y, n.z]).collect()
+                }
+                _ => {
+                    assert_eq!(misaligned, matches!(hypernode.get_nodes_4d(), Err(HyperNodeError::AlignmentError)));
+                    hypernode.get_nodes_4d_owned().unwrap().iter().flat_map(|n| [n.x, n.y, n.z, n.w]).collect()
+                }
+            };
+            assert_eq!(flattened, coords);
+        }
+
+        let data = HyperNodeFile::create_from_nodes_f64(&coords, 3).unwrap();
+        let hypernode = HyperNodeFile::from_bytes(NodeData::Owned(data)).unwrap();
+        assert!(matches!(hypernode.get_nodes_2d_owned(), Err(HyperNodeError::InvalidDimensions(3))));
+    }
+
+    #[test]
+    fn test_validate_finite() {
+        let coords = vec![1.0, 2.0, -3.5, 1e300, 0.0, -0.0];
+        let data = HyperNodeFile::create_from_nodes_f64(&coords, 2).unwrap();
+        let hypernode = HyperNodeFile::from_bytes(NodeData::Owned(data)).unwrap();
+        assert!(hypernode.validate_finite().is_ok());
+
+        for (index, value) in [(3, f64::NAN), (4, f64::INFINITY), (0, f64::NEG_INFINITY)] {
+            let mut coords = coords.clone();
+            coords[index] = value;
+            // The checksum is computed over the non-finite data, so only validate_finite catches it
+            let data = HyperNodeFile::create_from_nodes_f64(&coords, 2).unwrap();
+            HyperNodeFile::validate_bytes(&data).unwrap();
+            let hypernode = HyperNodeFile::from_bytes(NodeData::Owned(data)).unwrap();
+            assert!(matches!(hypernode.validate_finite(), Err(HyperNodeError::NonFiniteCoordinate { index: i }) if i == index));
+        }
+    }
+
+    #[test]
+    fn test_convert_endianness() {
+        let coords = vec![1.0, -2.5, 3.25, 1e-300, 5.0, 6.0];
+        let metadata = NodeMetadata { units: Some("mm".to_string()), ..NodeMetadata::default() };
+        for original in [
+            HyperNodeFile::create_from_nodes_f64(&coords, 3).unwrap(),
+            HyperNodeFile::create_from_nodes_f64_with_metadata(&coords, 2, &metadata).unwrap(),
+        ] {
+            let mut bytes = original.clone();
+            HyperNodeFile::convert_endianness(&mut bytes).unwrap();
+            assert_ne!(bytes, original);
+
+            // Converted file, read as the target machine would
+            let header = swap_header_bytes(bytemuck::pod_read_unaligned(&bytes[..size_of::<NodeHeader>()]));
+            assert_eq!(header.endianness, 1);
+            let data_start = header.data_offset as usize;
+            let data = &bytes[data_start..data_start + coords.len() * size_of::<f64>()];
+            assert_eq!(header.checksum, calculate_checksum(data));
+            let first: [u8; 8] = data[..8].try_into().unwrap();
+            assert_eq!(f64::from_bits(u64::from_ne_bytes(first).swap_bytes()), coords[0]);
+
+            HyperNodeFile::convert_endianness(&mut bytes).unwrap();
+            assert_eq!(bytes, original);
+        }
+    }
+
+    #[test]
+    fn test_ndarray_round_trip() {
+        let coords = ndarray::array![[0.0, 1.5, -2.25, 1e-7], [3.0, 0.1, 7.0, -1e9], [0.3, 0.0, 4.5, 2.0]];
+
+        let data = HyperNodeFile::from_ndarray(&coords).unwrap();
+        let hypernode = HyperNodeFile::from_bytes(NodeData::Owned(data)).unwrap();
+        assert_eq!(hypernode.header.dimensions, 3);
+        assert_eq!(hypernode.header.node_count, 4);
+
+        let round_trip = hypernode.to_ndarray().unwrap();
+        assert_eq!(round_trip.dim(), coords.dim());
+        for (a, b) in round_trip.iter().zip(coords.iter()) {
+            assert!((a - b).abs() < 1e-12);
+        }
+
+        assert!(matches!(
+            HyperNodeFile::from_ndarray(&Array2::zeros((5, 2))),
+            Err(HyperNodeError::InvalidDimensions(5))
+        ));
+        assert!(matches!(
+            HyperNodeFile::from_ndarray(&Array2::zeros((300, 1))),
+            Err(HyperNodeError::TooManyDimensions(300))
+        ));
+    }
+
+    #[test]
+    fn test_coordinate_access_f32_and_f64() {
+        // Exactly representable in f32, so both files hold the same values
+        let coords_64: Vec<f64> = vec![0.5, -1.25, 3.0, 1024.0, -0.0078125, 7.75];
+        let coords_32: Vec<f32> = coords_64.iter().map(|&x| x as f32).collect();
+
+        let file_64 = HyperNodeFile::from_bytes(NodeData::Owned(HyperNodeFile::create_from_nodes_f64(&coords_64, 3).unwrap())).unwrap();
+        let bytes_32 = HyperNodeFile::create_from_nodes_f32(&coords_32, 3).unwrap();
+        HyperNodeFile::validate_bytes(&bytes_32).unwrap();
+        let file_32 = HyperNodeFile::from_bytes(NodeData::Owned(bytes_32)).unwrap();
+        assert_eq!(file_32.header.coordinate_type, 0);
+        assert_eq!(file_32.get_nodes().unwrap().len(), coords_64.len() * size_of::<f32>());
+
+        for file in [&file_64, &file_32] {
+            assert_eq!(file.node_coords(1).unwrap(), vec![1024.0, -0.0078125, 7.75]);
+            for node in 0..2 {
+                for axis in 0..3 {
+                    assert_eq!(file.coordinate(node, axis).unwrap(), coords_64[node as usize * 3 + axis as usize]);
+                }
+            }
+            assert!(matches!(file.coordinate(2, 0), Err(HyperNodeError::NodeOutOfRange { node: 2, node_count: 2 })));
+            assert!(matches!(file.coordinate(0, 3), Err(HyperNodeError::AxisOutOfRange { axis: 3, dimensions: 3 })));
+            assert!(matches!(file.node_coords(5), Err(HyperNodeError::NodeOutOfRange { .. })));
+        }
+        assert_eq!(file_32.to_ndarray().unwrap(), file_64.to_ndarray().unwrap());
+
+        // Typed f64 views refuse single-precision data
+        assert!(matches!(file_32.get_nodes_3d_owned(), Err(HyperNodeError::InvalidCoordinateType(0))));
+    }
+
     #[test]
     fn test_memory_mapped_alignment() {
         // Create a temporary file to test memory-mapped alignment
@@ -1,10 +1,24 @@
+use std::collections::{BTreeMap, HashMap};
 use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::io::{BufRead, BufReader, BufWriter, Write};
 use std::path::Path;
 
+use memmap2::Mmap;
+use rayon::prelude::*;
+
+// (element_id, node_ids) in file order, and the max node id of one chunk
+type ParsedChunk = (Vec<(u32, Vec<u32>)>, u32);
+
+// (element_id, global node pairs) of one element
+type ElementPairs = (u32, Vec<(u32, u32)>);
+
+// (element_to_nodes, node_to_elements, index_to_element_id) read by the second pass
+type ConnectivityTables = (Vec<Vec<u32>>, Vec<Vec<(u32, u8)>>, Vec<u32>);
+
 #[derive(Debug, Clone)]
 pub struct MeshNodeConverter {
     element_to_nodes: Vec<Vec<u32>>,
+    node_to_elements: Vec<Vec<(u32, u8)>>, // node_to_elements[global_node_id] -> Vec of (element_id, local_node_num)
     index_to_element_id: Vec<u32>, // Sorted array for binary search
     max_node_id: u32,
     num_elements: usize,
@@ -17,6 +31,10 @@ pub enum MeshError {
     ElementNotFound(u32),
     InvalidLocalNode(u8),
     NodeOutOfRange(u8),
+    DuplicateElement(u32),
+    EmptyElement(u32),
+    UnsortedElementIds,
+    ZeroNodeId(u32),
 }
 
 impl From<std::io::Error> for MeshError {
@@ -33,37 +51,249 @@ impl std::fmt::Display for MeshError {
             MeshError::ElementNotFound(id) => write!(f, "Element {} not found", id),
             MeshError::InvalidLocalNode(num) => write!(f, "Invalid local node number {}", num),
             MeshError::NodeOutOfRange(num) => write!(f, "Local node number {} out of range", num),
+            MeshError::DuplicateElement(id) => write!(f, "Element {} is defined more than once", id),
+            MeshError::EmptyElement(id) => write!(f, "Element {} has no nodes", id),
+            MeshError::UnsortedElementIds => write!(f, "Element ids are not sorted and unique, lookups are unreliable"),
+            MeshError::ZeroNodeId(id) => write!(f, "Element {} references node 0 in one-based connectivity", id),
         }
     }
 }
 
 impl std::error::Error for MeshError {}
 
+/// Numbering of the node ids in a connectivity file, see `MeshNodeConverter::new_with_base`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum IndexBase {
+    #[default]
+    ZeroBased,
+    OneBased,
+}
+
+/// Element and node statistics of a `MeshNodeConverter`, see `MeshNodeConverter::summary`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MeshSummary {
+    pub num_elements: usize,
+    /// Largest node id referenced by some element (0 for an empty mesh)
+    pub max_node_id: u32,
+    pub min_nodes_per_element: usize,
+    pub max_nodes_per_element: usize,
+    /// Number of elements for every nodes-per-element count, e.g. {4: 120, 8: 36}
+    pub element_size_histogram: BTreeMap<usize, usize>,
+}
+
+impl std::fmt::Display for MeshSummary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Elements: {}", self.num_elements)?;
+        writeln!(f, "Max node id: {}", self.max_node_id)?;
+        write!(f, "Nodes per element: {} to {}", self.min_nodes_per_element, self.max_nodes_per_element)?;
+        for (size, count) in &self.element_size_histogram {
+            write!(f, "\n  {} nodes: {} elements", size, count)?;
+        }
+        Ok(())
+    }
+}
+
 impl MeshNodeConverter {
     pub fn new<P: AsRef<Path>>(connectivity_file: P) -> Result<Self, MeshError> {
         // First pass: count elements and find max node ID
         let (max_node_id, element_count) = Self::first_pass(&connectivity_file)?;
 
         // Second pass: build data structures
-        let (element_to_nodes, index_to_element_id) = 
-            Self::second_pass(&connectivity_file, element_count)?;
+        let (element_to_nodes, node_to_elements, index_to_element_id) = 
+            Self::second_pass(&connectivity_file, element_count, max_node_id)?;
 
         // Create a vector of indices and sort them based on element IDs
         let mut indices: Vec<usize> = (0..index_to_element_id.len()).collect();
         indices.sort_by_key(|&i| index_to_element_id[i]);
 
         // Reorder both arrays using the sorted indices
-        let index_to_element_id = indices.iter().map(|&i| index_to_element_id[i]).collect();
+        let index_to_element_id: Vec<u32> = indices.iter().map(|&i| index_to_element_id[i]).collect();
         let element_to_nodes = indices.iter().map(|&i| element_to_nodes[i].clone()).collect();
 
+        Self::check_duplicate_elements(&index_to_element_id)?;
+
+        Ok(Self {
+            element_to_nodes,
+            node_to_elements,
+            index_to_element_id,
+            max_node_id,
+            num_elements: element_count,
+        })
+    }
+
+    /// Same as `new`, for a file whose node ids start at `base`. One-based node ids are shifted
+    /// down by one, so the converter and `to_assembly_connectivity` are 0-based either way.
+    /// Element ids are kept as written. A node id 0 in a one-based file fails with `ZeroNodeId`.
+    pub fn new_with_base<P: AsRef<Path>>(connectivity_file: P, base: IndexBase) -> Result<Self, MeshError> {
+        match base {
+            IndexBase::ZeroBased => Self::new(connectivity_file),
+            IndexBase::OneBased => {
+                let elements: Vec<(u32, Vec<u32>)> = ConnectivityStream::from_path(connectivity_file)?
+                    .map(|element| {
+                        let (element_id, node_ids) = element?;
+                        let node_ids = node_ids
+                            .into_iter()
+                            .map(|node_id| node_id.checked_sub(1).ok_or(MeshError::ZeroNodeId(element_id)))
+                            .collect::<Result<_, MeshError>>()?;
+                        Ok((element_id, node_ids))
+                    })
+                    .collect::<Result<_, MeshError>>()?;
+                Self::from_elements(elements)
+            }
+        }
+    }
+
+    /// Same result as `new`, but memory-maps the file and parses line-aligned chunks on the
+    /// rayon thread pool in a single pass. Element count and max node id are reduced from the chunks.
+    pub fn new_parallel<P: AsRef<Path>>(connectivity_file: P) -> Result<Self, MeshError> {
+        Self::new_parallel_with_chunks(connectivity_file, rayon::current_num_threads() * 4)
+    }
+
+    fn new_parallel_with_chunks<P: AsRef<Path>>(connectivity_file: P, num_chunks: usize) -> Result<Self, MeshError> {
+        let file = File::open(connectivity_file)?;
+
+        // Mapping an empty file fails on some platforms
+        let mmap = if file.metadata()?.len() == 0 { None } else { Some(unsafe { Mmap::map(&file)? }) };
+        let bytes: &[u8] = mmap.as_deref().unwrap_or(&[]);
+
+        let chunks: Vec<ParsedChunk> = Self::line_aligned_ranges(bytes, num_chunks)
+            .into_par_iter()
+            .map(|(start, end)| Self::parse_chunk(&bytes[start..end]))
+            .collect::<Result<_, MeshError>>()?;
+
+        let element_count: usize = chunks.iter().map(|(elements, _)| elements.len()).sum();
+        let max_node_id = chunks.iter().map(|&(_, chunk_max)| chunk_max).max().unwrap_or(0);
+
+        // Merge in file order, so node_to_elements matches `new`
+        let mut elements: Vec<(u32, Vec<u32>)> = Vec::with_capacity(element_count);
+        for (chunk_elements, _) in chunks {
+            elements.extend(chunk_elements);
+        }
+
+        Self::from_elements_with_max(elements, max_node_id)
+    }
+
+    /// Builds the converter from in-memory (element_id, node_ids) pairs, as if they were the lines
+    /// of a connectivity file read by `new` in the same order. Fails with `EmptyElement` for an
+    /// element without nodes and `DuplicateElement` for an element id given twice.
+    pub fn from_elements(elements: Vec<(u32, Vec<u32>)>) -> Result<Self, MeshError> {
+        if let Some((element_id, _)) = elements.iter().find(|(_, node_ids)| node_ids.is_empty()) {
+            return Err(MeshError::EmptyElement(*element_id));
+        }
+
+        let max_node_id: u32 = elements.par_iter().flat_map_iter(|(_, node_ids)| node_ids.iter().copied()).max().unwrap_or(0);
+        Self::from_elements_with_max(elements, max_node_id)
+    }
+
+    /// `from_elements` for non-empty elements whose largest node id is already known
+    fn from_elements_with_max(mut elements: Vec<(u32, Vec<u32>)>, max_node_id: u32) -> Result<Self, MeshError> {
+        let element_count: usize = elements.len();
+        let node_to_elements = Self::build_node_to_elements(&elements, max_node_id);
+
+        // Stable sort, as in `new`
+        elements.par_sort_by_key(|&(element_id, _)| element_id);
+        let (index_to_element_id, element_to_nodes): (Vec<u32>, Vec<Vec<u32>>) = elements.into_iter().unzip();
+
+        Self::check_duplicate_elements(&index_to_element_id)?;
+
         Ok(Self {
             element_to_nodes,
+            node_to_elements,
             index_to_element_id,
             max_node_id,
             num_elements: element_count,
         })
     }
 
+    /// Inverts the connectivity on the rayon thread pool. Every node lists its elements in the
+    /// order of `elements`, as the serial loop of `new` does.
+    fn build_node_to_elements(elements: &[(u32, Vec<u32>)], max_node_id: u32) -> Vec<Vec<(u32, u8)>> {
+        // (node_id, element_id, local_node_num); the stable sort keeps the element order per node
+        let mut incidences: Vec<(u32, u32, u8)> = elements
+            .par_iter()
+            .flat_map_iter(|(element_id, node_ids)| {
+                node_ids.iter().enumerate().map(move |(local_idx, &node_id)| (node_id, *element_id, local_idx as u8))
+            })
+            .collect();
+        incidences.par_sort_by_key(|&(node_id, _, _)| node_id);
+
+        (0..=max_node_id)
+            .into_par_iter()
+            .map(|node_id| {
+                let start = incidences.partition_point(|&(id, _, _)| id < node_id);
+                let end = incidences.partition_point(|&(id, _, _)| id <= node_id);
+                incidences[start..end].iter().map(|&(_, element_id, local_idx)| (element_id, local_idx)).collect()
+            })
+            .collect()
+    }
+
+    /// Binary search on the sorted element ids requires them to be unique
+    fn check_duplicate_elements(sorted_element_ids: &[u32]) -> Result<(), MeshError> {
+        match sorted_element_ids.windows(2).find(|pair| pair[0] == pair[1]) {
+            Some(pair) => Err(MeshError::DuplicateElement(pair[0])),
+            None => Ok(()),
+        }
+    }
+
+    /// Splits `bytes` into at most `num_chunks` ranges that start and end on line boundaries
+    fn line_aligned_ranges(bytes: &[u8], num_chunks: usize) -> Vec<(usize, usize)> {
+        let num_chunks = num_chunks.max(1);
+        let mut ranges = Vec::with_capacity(num_chunks);
+        let mut start = 0;
+
+        for k in 1..=num_chunks {
+            if start >= bytes.len() {
+                break;
+            }
+            let target = (bytes.len() * k / num_chunks).max(start);
+            let end = if k == num_chunks {
+                bytes.len()
+            } else {
+                bytes[target..]
+                    .iter()
+                    .position(|&b| b == b'\n')
+                    .map_or(bytes.len(), |offset| target + offset + 1)
+            };
+            ranges.push((start, end));
+            start = end;
+        }
+
+        ranges
+    }
+
+    /// Parses the lines of one chunk into (element_id, node_ids) in file order, with the max node id
+    fn parse_chunk(chunk: &[u8]) -> Result<ParsedChunk, MeshError> {
+        let text = std::str::from_utf8(chunk)
+            .map_err(|e| MeshError::ParseError(format!("Invalid UTF-8: {}", e)))?;
+
+        let mut elements = Vec::new();
+        let mut max_node_id = 0;
+
+        for line in text.lines() {
+            if is_skipped_line(line) {
+                continue;
+            }
+            let mut parts = line.split_whitespace();
+
+            let Some(element_str) = parts.next() else { continue };
+            let element_id: u32 = element_str.parse().map_err(|_| {
+                MeshError::ParseError(format!("Element ID {} is invalid as u32", element_str))
+            })?;
+
+            let node_ids: Vec<u32> = parts
+                .map(|s| s.parse().map_err(|_| MeshError::ParseError(format!("Invalid node ID: {}", s))))
+                .collect::<Result<_, MeshError>>()?;
+            if node_ids.is_empty() {
+                return Err(MeshError::EmptyElement(element_id));
+            }
+
+            max_node_id = node_ids.iter().copied().fold(max_node_id, u32::max);
+            elements.push((element_id, node_ids));
+        }
+
+        Ok((elements, max_node_id))
+    }
+
     fn first_pass<P: AsRef<Path>>(path: P) -> Result<(u32, usize), MeshError> {
         let file = File::open(path)?;
         let reader = BufReader::new(file);
@@ -73,11 +303,11 @@ impl MeshNodeConverter {
 
         for line in reader.lines() {
             let line = line?;
-            let mut parts = line.trim().split_whitespace();
-            
-            if parts.next().is_none() {
+            if is_skipped_line(&line) {
                 continue;
             }
+            let mut parts = line.split_whitespace();
+            parts.next();
 
             element_count += 1;
             
@@ -95,16 +325,21 @@ impl MeshNodeConverter {
     fn second_pass<P: AsRef<Path>>(
         path: P,
         element_count: usize,
-    ) -> Result<(Vec<Vec<u32>>, Vec<u32>), MeshError> {
+        max_node_id: u32,
+    ) -> Result<ConnectivityTables, MeshError> {
         let file = File::open(path)?;
         let reader = BufReader::new(file);
 
         let mut element_to_nodes = Vec::with_capacity(element_count);
         let mut index_to_element_id = Vec::with_capacity(element_count);
+        let mut node_to_elements = vec![Vec::new(); max_node_id as usize + 1];
 
         for line in reader.lines() {
             let line = line?;
-            let mut parts = line.trim().split_whitespace();
+            if is_skipped_line(&line) {
+                continue;
+            }
+            let mut parts = line.split_whitespace();
             
             let Some(element_str) = parts.next() else { continue };
             let element_id: u32 = element_str.parse().map_err(|_| {
@@ -115,12 +350,20 @@ impl MeshNodeConverter {
                 .map(|s| s.parse().map_err(|_| MeshError::ParseError(format!("Invalid node ID: {}", s))))
                 .collect();
             let node_ids = node_ids?;
+            if node_ids.is_empty() {
+                return Err(MeshError::EmptyElement(element_id));
+            }
+
+            // Update node_to_elements mapping
+            for (local_idx, &node_id) in node_ids.iter().enumerate() {
+                node_to_elements[node_id as usize].push((element_id, local_idx as u8));
+            }
 
             index_to_element_id.push(element_id);
             element_to_nodes.push(node_ids);
         }
 
-        Ok((element_to_nodes, index_to_element_id))
+        Ok((element_to_nodes, node_to_elements, index_to_element_id))
     }
 
     pub fn local_to_global(&self, element_id: u32, local_node_num: u8) -> Result<u32, MeshError> {
@@ -155,19 +398,32 @@ impl MeshNodeConverter {
         Ok((nodes[local_node1 as usize], nodes[local_node2 as usize]))
     }
 
-    pub fn get_global_pairs_for_elements(&self, element_ids: &[u32]) -> Result<Vec<(u32, Vec<(u32, u32)>)>, MeshError> {
+    pub fn get_global_pairs_for_elements(&self, element_ids: &[u32]) -> Result<Vec<ElementPairs>, MeshError> {
         element_ids.iter()
-            .map(|&elem_id| {
-                let nodes = self.get_element_nodes(elem_id)?;
-                let pairs = nodes.iter()
-                    .enumerate()
-                    .flat_map(|(i, &x)| nodes[i..].iter().map(move |&y| (x, y)))
-                    .collect();
-                Ok((elem_id, pairs))
-            })
+            .map(|&elem_id| Ok((elem_id, self.get_all_global_pairs(elem_id)?)))
             .collect()
     }
 
+    /// Parallel version of `get_global_nodes_for_elements`. Results keep the order of `element_ids`,
+    /// and the error is the one of the first missing element in that order.
+    pub fn par_get_global_nodes_for_elements(&self, element_ids: &[u32]) -> Result<Vec<(u32, Vec<u32>)>, MeshError> {
+        let results: Vec<Result<(u32, Vec<u32>), MeshError>> = element_ids
+            .par_iter()
+            .map(|&elem_id| Ok((elem_id, self.get_element_nodes(elem_id)?.to_vec())))
+            .collect();
+        results.into_iter().collect()
+    }
+
+    /// Parallel version of `get_global_pairs_for_elements`, with the same ordering and error as
+    /// `par_get_global_nodes_for_elements`
+    pub fn par_get_global_pairs_for_elements(&self, element_ids: &[u32]) -> Result<Vec<ElementPairs>, MeshError> {
+        let results: Vec<Result<ElementPairs, MeshError>> = element_ids
+            .par_iter()
+            .map(|&elem_id| Ok((elem_id, self.get_all_global_pairs(elem_id)?)))
+            .collect();
+        results.into_iter().collect()
+    }
+
     pub fn get_all_local_pairs(&self, element_id: u32) -> Result<Vec<(u8, u8)>, MeshError> {
         let num_nodes = self.get_element_nodes(element_id)?.len() as u8;
         let nodes: Vec<u8> = (0..num_nodes).collect();  // Convert range to Vec<u8>
@@ -182,6 +438,170 @@ impl MeshNodeConverter {
             .collect())
     }
 
+    /// Writes lines of `element_id node0 node1 ...` in element-id order (the format read by `new`)
+    pub fn write_connectivity<W: Write>(&self, writer: W) -> Result<(), MeshError> {
+        let mut writer = BufWriter::new(writer);
+
+        for (element_id, nodes) in self.iter_elements() {
+            write!(writer, "{}", element_id)?;
+            for node_id in nodes {
+                write!(writer, " {}", node_id)?;
+            }
+            writeln!(writer)?;
+        }
+
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Iterates over (element_id, global nodes) in element-id order
+    pub fn iter_elements(&self) -> impl Iterator<Item = (u32, &[u32])> {
+        self.index_to_element_id
+            .iter()
+            .copied()
+            .zip(self.element_to_nodes.iter().map(|nodes| nodes.as_slice()))
+    }
+
+    /// Removes an element, keeping the element ids sorted and the node-to-element map up to date.
+    ///
+    /// `max_node_id` is not updated and becomes an upper bound of the node ids in use,
+    /// until `recompute_max_node_id` is called.
+    pub fn remove_element(&mut self, element_id: u32) -> Result<(), MeshError> {
+        let index = self.find_element_index(element_id)?;
+        self.index_to_element_id.remove(index);
+        let nodes = self.element_to_nodes.remove(index);
+
+        for node_id in nodes {
+            self.node_to_elements[node_id as usize].retain(|&(id, _)| id != element_id);
+        }
+
+        self.num_elements -= 1;
+        Ok(())
+    }
+
+    /// Restores `max_node_id` to the largest node id of the remaining elements (0 if there are none)
+    pub fn recompute_max_node_id(&mut self) {
+        self.max_node_id = self.element_to_nodes.iter().flatten().copied().max().unwrap_or(0);
+        self.node_to_elements.truncate(self.max_node_id as usize + 1);
+    }
+
+    /// Element node lists as 0-based usize indices, in element-id order, together with the
+    /// number of nodes (max_node_id + 1), as expected by `assembly::initialize_stiffness_matrix`.
+    /// Unused node ids are kept, so they become empty rows of the assembled matrix.
+    pub fn to_assembly_connectivity(&self) -> (Vec<Vec<usize>>, usize) {
+        let connectivity = self
+            .element_to_nodes
+            .iter()
+            .map(|nodes| nodes.iter().map(|&node_id| node_id as usize).collect())
+            .collect();
+        let num_nodes = if self.num_elements == 0 { 0 } else { self.max_node_id as usize + 1 };
+        (connectivity, num_nodes)
+    }
+
+    /// Same as `to_assembly_connectivity`, but renumbers the nodes referenced by some element to
+    /// the contiguous range 0..num_nodes, keeping their relative order. The returned mapping gives
+    /// the original node id of every compact index.
+    pub fn to_compact_assembly_connectivity(&self) -> (Vec<Vec<usize>>, usize, Vec<u32>) {
+        let mut compact_index: Vec<usize> = vec![usize::MAX; self.node_to_elements.len()];
+        let mut compact_to_node_id: Vec<u32> = Vec::new();
+        for (node_id, elements) in self.node_to_elements.iter().enumerate() {
+            if !elements.is_empty() {
+                compact_index[node_id] = compact_to_node_id.len();
+                compact_to_node_id.push(node_id as u32);
+            }
+        }
+
+        let connectivity = self
+            .element_to_nodes
+            .iter()
+            .map(|nodes| nodes.iter().map(|&node_id| compact_index[node_id as usize]).collect())
+            .collect();
+        (connectivity, compact_to_node_id.len(), compact_to_node_id)
+    }
+
+    /// Relabels the nodes referenced by some element to the dense range 0..num_distinct, keeping
+    /// their relative order. Returns the relabelled converter, the original id of every new id
+    /// (new_to_old) and its inverse (old_to_new), e.g. to reindex the coordinate array.
+    pub fn compact_node_ids(&self) -> (MeshNodeConverter, Vec<u32>, HashMap<u32, u32>) {
+        let mut new_to_old: Vec<u32> = Vec::new();
+        let mut node_to_elements: Vec<Vec<(u32, u8)>> = Vec::new();
+        for (node_id, elements) in self.node_to_elements.iter().enumerate() {
+            if !elements.is_empty() {
+                new_to_old.push(node_id as u32);
+                node_to_elements.push(elements.clone());
+            }
+        }
+
+        let old_to_new: HashMap<u32, u32> = new_to_old
+            .iter()
+            .enumerate()
+            .map(|(new_id, &old_id)| (old_id, new_id as u32))
+            .collect();
+
+        let element_to_nodes = self
+            .element_to_nodes
+            .iter()
+            .map(|nodes| nodes.iter().map(|node_id| old_to_new[node_id]).collect())
+            .collect();
+
+        let compacted = MeshNodeConverter {
+            element_to_nodes,
+            node_to_elements,
+            index_to_element_id: self.index_to_element_id.clone(),
+            max_node_id: new_to_old.len().saturating_sub(1) as u32,
+            num_elements: self.num_elements,
+        };
+        debug_assert!(compacted.is_sorted_unique());
+        (compacted, new_to_old, old_to_new)
+    }
+
+    /// Element count, largest node id in use and distribution of the number of nodes per element,
+    /// computed in a single pass over the elements
+    pub fn summary(&self) -> MeshSummary {
+        let mut max_node_id: u32 = 0;
+        let mut element_size_histogram: BTreeMap<usize, usize> = BTreeMap::new();
+        for nodes in &self.element_to_nodes {
+            max_node_id = nodes.iter().copied().fold(max_node_id, u32::max);
+            *element_size_histogram.entry(nodes.len()).or_insert(0) += 1;
+        }
+
+        MeshSummary {
+            num_elements: self.element_to_nodes.len(),
+            max_node_id,
+            min_nodes_per_element: element_size_histogram.keys().next().copied().unwrap_or(0),
+            max_nodes_per_element: element_size_histogram.keys().next_back().copied().unwrap_or(0),
+            element_size_histogram,
+        }
+    }
+
+    /// Returns all (element_id, local_node_num) pairs for elements containing the given global node
+    pub fn elements_containing_node(&self, node_id: u32) -> &[(u32, u8)] {
+        self.node_to_elements
+            .get(node_id as usize)
+            .map_or(&[], |elements| elements.as_slice())
+    }
+
+    /// Returns the ids of all elements sharing at least `min_shared_nodes` nodes with the given element,
+    /// excluding the element itself, sorted by element id
+    pub fn element_neighbors(&self, element_id: u32, min_shared_nodes: usize) -> Result<Vec<u32>, MeshError> {
+        let nodes = self.get_element_nodes(element_id)?;
+
+        let mut shared_counts: BTreeMap<u32, usize> = BTreeMap::new();
+        for &node_id in nodes {
+            for &(other_id, _) in self.elements_containing_node(node_id) {
+                if other_id != element_id {
+                    *shared_counts.entry(other_id).or_insert(0) += 1;
+                }
+            }
+        }
+
+        Ok(shared_counts
+            .into_iter()
+            .filter(|&(_, count)| count >= min_shared_nodes)
+            .map(|(other_id, _)| other_id)
+            .collect())
+    }
+
     fn generate_all_pairs<T: Copy>(items: &[T]) -> Vec<(T, T)> {
         let mut pairs = Vec::with_capacity(items.len().pow(2) / 2);
         
@@ -199,10 +619,21 @@ impl MeshNodeConverter {
         Ok(&self.element_to_nodes[dense_idx])
     }
 
+    /// A hit of the binary search is always the right element. A miss is only meaningful if the
+    /// ids are sorted and unique. Debug builds check that on the miss path to tell
+    /// `ElementNotFound` apart from `UnsortedElementIds`; release builds skip the linear scan
+    /// and trust the invariant that every constructor establishes.
     fn find_element_index(&self, element_id: u32) -> Result<usize, MeshError> {
-        self.index_to_element_id
-            .binary_search(&element_id)
-            .map_err(|_| MeshError::ElementNotFound(element_id))
+        match self.index_to_element_id.binary_search(&element_id) {
+            Ok(index) => Ok(index),
+            Err(_) if cfg!(debug_assertions) && !self.is_sorted_unique() => Err(MeshError::UnsortedElementIds),
+            Err(_) => Err(MeshError::ElementNotFound(element_id)),
+        }
+    }
+
+    /// Invariant of `index_to_element_id` that the binary search relies on
+    fn is_sorted_unique(&self) -> bool {
+        self.index_to_element_id.windows(2).all(|pair| pair[0] < pair[1])
     }
 
     pub fn max_node_id(&self) -> u32 {
@@ -214,10 +645,89 @@ impl MeshNodeConverter {
     }
 }
 
-#[cfg(not(test))]
+/// Blank lines and `#` comment lines carry no element; every parser skips them the same way
+fn is_skipped_line(line: &str) -> bool {
+    let line = line.trim();
+    line.is_empty() || line.starts_with('#')
+}
+
+/// Lazily parses a connectivity file line by line, in file order, without building the lookup
+/// tables of `MeshNodeConverter`. Suited to single sweeps over meshes that do not fit in memory.
+pub struct ConnectivityStream {
+    reader: BufReader<File>,
+    line: String,
+    node_ids: Vec<u32>, // Scratch buffer reused by `next_element`
+}
+
+impl ConnectivityStream {
+    pub fn from_path<P: AsRef<Path>>(connectivity_file: P) -> Result<Self, MeshError> {
+        let file = File::open(connectivity_file)?;
+        Ok(Self {
+            reader: BufReader::new(file),
+            line: String::new(),
+            node_ids: Vec::new(),
+        })
+    }
+
+    /// Parses the next element into the internal scratch buffer, without allocating.
+    /// Blank and comment lines are skipped; returns `None` at end of file.
+    pub fn next_element(&mut self) -> Option<Result<(u32, &[u32]), MeshError>> {
+        loop {
+            self.line.clear();
+            match self.reader.read_line(&mut self.line) {
+                Ok(0) => return None,
+                Ok(_) => {}
+                Err(e) => return Some(Err(e.into())),
+            }
+
+            if is_skipped_line(&self.line) {
+                continue;
+            }
+            let mut parts = self.line.split_whitespace();
+            let Some(element_str) = parts.next() else { continue };
+            let element_id: u32 = match element_str.parse() {
+                Ok(id) => id,
+                Err(_) => return Some(Err(MeshError::ParseError(format!("Element ID {} is invalid as u32", element_str)))),
+            };
+
+            self.node_ids.clear();
+            for s in parts {
+                match s.parse() {
+                    Ok(node_id) => self.node_ids.push(node_id),
+                    Err(_) => return Some(Err(MeshError::ParseError(format!("Invalid node ID: {}", s)))),
+                }
+            }
+            if self.node_ids.is_empty() {
+                return Some(Err(MeshError::EmptyElement(element_id)));
+            }
+
+            return Some(Ok((element_id, &self.node_ids)));
+        }
+    }
+}
+
+impl Iterator for ConnectivityStream {
+    type Item = Result<(u32, Vec<u32>), MeshError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_element()
+            .map(|result| result.map(|(element_id, node_ids)| (element_id, node_ids.to_vec())))
+    }
+}
+
+/// Converter read by `new` from a connectivity file with the given lines, for tests
+#[cfg(test)]
+pub(crate) fn converter_from_lines<S: AsRef<str>>(lines: &[S]) -> MeshNodeConverter {
+    let mut file = tempfile::NamedTempFile::new().unwrap();
+    for line in lines {
+        writeln!(file, "{}", line.as_ref()).unwrap();
+    }
+    MeshNodeConverter::new(file.path()).unwrap()
+}
+
+#[cfg(test)]
 mod tests {
     use super::*;
-    use std::io::Write;
     use tempfile::NamedTempFile;
     use rand::{rng, Rng, seq::SliceRandom};
 
@@ -259,7 +769,7 @@ mod tests {
         file
     }
 
-    //#[test]
+    #[test]
     fn test_new_with_simple_file() {
         let file = create_simple_test_file();
         let converter = MeshNodeConverter::new(file.path()).unwrap();
@@ -268,7 +778,32 @@ mod tests {
         assert_eq!(converter.num_elements(), 3);
     }
 
-    //#[test]
+    #[test]
+    fn test_summary() {
+        let file = create_simple_test_file();
+        let converter = MeshNodeConverter::new(file.path()).unwrap();
+
+        let summary = converter.summary();
+        assert_eq!(summary.num_elements, 3);
+        assert_eq!(summary.max_node_id, 14);
+        assert_eq!(summary.min_nodes_per_element, 3);
+        assert_eq!(summary.max_nodes_per_element, 3);
+        assert_eq!(summary.element_size_histogram, BTreeMap::from([(3, 3)]));
+        assert_eq!(
+            summary.to_string(),
+            "Elements: 3\nMax node id: 14\nNodes per element: 3 to 3\n  3 nodes: 3 elements"
+        );
+
+        let mut file = NamedTempFile::new().unwrap();
+        writeln!(file, "0 1 2 3 4").unwrap();
+        writeln!(file, "1 4 5 6").unwrap();
+        writeln!(file, "2 6 7 8 9").unwrap();
+        let summary = MeshNodeConverter::new(file.path()).unwrap().summary();
+        assert_eq!((summary.min_nodes_per_element, summary.max_nodes_per_element), (3, 4));
+        assert_eq!(summary.element_size_histogram, BTreeMap::from([(3, 1), (4, 2)]));
+    }
+
+    #[test]
     fn test_local_to_global() {
         let file = create_simple_test_file();
         let converter = MeshNodeConverter::new(file.path()).unwrap();
@@ -284,7 +819,7 @@ mod tests {
         assert!(matches!(converter.local_to_global(99, 0), Err(MeshError::ElementNotFound(99))));
     }
 
-    //#[test]
+    #[test]
     fn test_get_global_nodes_for_elements() {
         let file = create_simple_test_file();
         let converter = MeshNodeConverter::new(file.path()).unwrap();
@@ -295,7 +830,38 @@ mod tests {
         assert_eq!(result[1], (1, vec![11, 12, 13]));
     }
 
-    //#[test]
+    #[test]
+    fn test_par_get_global_nodes_matches_serial() {
+        let mut file = NamedTempFile::new().unwrap();
+        for element_id in 0..2000 {
+            let num_nodes = rng().random_range(3..=8);
+            write!(file, "{}", element_id).unwrap();
+            for _ in 0..num_nodes {
+                write!(file, " {}", rng().random_range(0..500u32)).unwrap();
+            }
+            writeln!(file).unwrap();
+        }
+        let converter = MeshNodeConverter::new(file.path()).unwrap();
+
+        let mut element_ids: Vec<u32> = (0..2000).collect();
+        element_ids.shuffle(&mut rng());
+        assert_eq!(
+            converter.par_get_global_nodes_for_elements(&element_ids).unwrap(),
+            converter.get_global_nodes_for_elements(&element_ids).unwrap()
+        );
+        assert_eq!(
+            converter.par_get_global_pairs_for_elements(&element_ids).unwrap(),
+            converter.get_global_pairs_for_elements(&element_ids).unwrap()
+        );
+
+        // The first missing id in the list is reported
+        element_ids[100] = 5000;
+        element_ids[1500] = 6000;
+        assert!(matches!(converter.par_get_global_nodes_for_elements(&element_ids), Err(MeshError::ElementNotFound(5000))));
+        assert!(matches!(converter.par_get_global_pairs_for_elements(&element_ids), Err(MeshError::ElementNotFound(5000))));
+    }
+
+    #[test]
     fn test_local_pair_to_global() {
         let file = create_simple_test_file();
         let converter = MeshNodeConverter::new(file.path()).unwrap();
@@ -311,7 +877,7 @@ mod tests {
         ));
     }
 
-    //#[test]
+    #[test]
     fn test_get_all_local_pairs() {
         let file = create_simple_test_file();
         let converter = MeshNodeConverter::new(file.path()).unwrap();
@@ -326,7 +892,7 @@ mod tests {
         assert!(pairs.contains(&(2, 2)));
     }
 
-    //#[test]
+    #[test]
     fn test_get_all_global_pairs() {
         let file = create_simple_test_file();
         let converter = MeshNodeConverter::new(file.path()).unwrap();
@@ -368,7 +934,7 @@ mod tests {
         assert!(duration < std::time::Duration::from_millis(100), "Lookups took too long");
     }
 
-    //#[test]
+    #[test]
     fn test_element_sorting() {
         // Create a file with non-sequential element IDs
         let mut file = NamedTempFile::new().unwrap();
@@ -387,7 +953,7 @@ mod tests {
         assert_eq!(converter.element_to_nodes[2], vec![7, 8, 9]); // Element 200
     }
 
-    //#[test]
+    #[test]
     fn test_invalid_file_format() {
         // File with non-numeric data
         let mut file = NamedTempFile::new().unwrap();
@@ -407,7 +973,7 @@ mod tests {
         assert_eq!(converter.num_elements(), 2);
     }
 
-    //#[test]
+    #[test]
     fn test_empty_file() {
         let file = NamedTempFile::new().unwrap();
         let converter = MeshNodeConverter::new(file.path()).unwrap();
@@ -415,4 +981,353 @@ mod tests {
         assert_eq!(converter.num_elements(), 0);
         assert_eq!(converter.max_node_id(), 0);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_node_to_elements_mapping() {
+        let file = create_simple_test_file();
+        let converter = MeshNodeConverter::new(file.path()).unwrap();
+        
+        // Node 11 is in elements 0 and 1
+        let elements = converter.elements_containing_node(11);
+        assert_eq!(elements.len(), 2);
+        assert!(elements.contains(&(0, 1)));  // Node 11 is local node 1 in element 0
+        assert!(elements.contains(&(1, 0)));  // Node 11 is local node 0 in element 1
+        
+        // Node 14 is only in element 2
+        let elements = converter.elements_containing_node(14);
+        assert_eq!(elements.len(), 1);
+        assert_eq!(elements[0], (2, 2));
+
+        // Unused and out-of-range nodes
+        assert!(converter.elements_containing_node(0).is_empty());
+        assert!(converter.elements_containing_node(99).is_empty());
+    }
+
+    #[test]
+    fn test_element_neighbors() {
+        let file = create_simple_test_file();
+        let converter = MeshNodeConverter::new(file.path()).unwrap();
+        
+        // Any shared node
+        assert_eq!(converter.element_neighbors(0, 1).unwrap(), vec![1, 2]);
+        assert_eq!(converter.element_neighbors(1, 1).unwrap(), vec![0, 2]);
+        assert_eq!(converter.element_neighbors(2, 1).unwrap(), vec![0, 1]);
+        
+        // Elements 0 and 2 only share node 12
+        assert_eq!(converter.element_neighbors(0, 2).unwrap(), vec![1]);
+        assert_eq!(converter.element_neighbors(1, 2).unwrap(), vec![0, 2]);
+        assert_eq!(converter.element_neighbors(2, 2).unwrap(), vec![1]);
+        
+        // No element shares all three nodes
+        assert!(converter.element_neighbors(1, 3).unwrap().is_empty());
+        
+        assert!(matches!(converter.element_neighbors(99, 1), Err(MeshError::ElementNotFound(99))));
+    }
+
+    fn assert_same_converter(a: &MeshNodeConverter, b: &MeshNodeConverter) {
+        assert_eq!(a.element_to_nodes, b.element_to_nodes);
+        assert_eq!(a.node_to_elements, b.node_to_elements);
+        assert_eq!(a.index_to_element_id, b.index_to_element_id);
+        assert_eq!(a.max_node_id, b.max_node_id);
+        assert_eq!(a.num_elements, b.num_elements);
+    }
+
+    #[test]
+    fn test_new_parallel_matches_new() {
+        let file = create_simple_test_file();
+        let converter = MeshNodeConverter::new(file.path()).unwrap();
+        assert_same_converter(&MeshNodeConverter::new_parallel(file.path()).unwrap(), &converter);
+
+        // Shuffled ids, blank lines and CRLF endings, split into many chunks
+        let mut file = NamedTempFile::new().unwrap();
+        let mut element_ids: Vec<u32> = (0..500).collect();
+        element_ids.shuffle(&mut rng());
+        for (i, element_id) in element_ids.iter().enumerate() {
+            let num_nodes = rng().random_range(3..=8);
+            write!(file, "{}", element_id).unwrap();
+            for _ in 0..num_nodes {
+                write!(file, " {}", rng().random_range(0..200u32)).unwrap();
+            }
+            if i % 7 == 0 {
+                write!(file, "\r\n\n").unwrap();
+            } else {
+                writeln!(file).unwrap();
+            }
+        }
+
+        let converter = MeshNodeConverter::new(file.path()).unwrap();
+        for num_chunks in [1, 2, 7, 64, 10_000] {
+            let parallel = MeshNodeConverter::new_parallel_with_chunks(file.path(), num_chunks).unwrap();
+            assert_same_converter(&parallel, &converter);
+        }
+        assert_same_converter(&MeshNodeConverter::new_parallel(file.path()).unwrap(), &converter);
+    }
+
+    #[test]
+    fn test_from_elements_matches_new() {
+        let file = create_simple_test_file();
+        let elements = vec![(0, vec![10, 11, 12]), (1, vec![11, 12, 13]), (2, vec![12, 13, 14])];
+        assert_same_converter(&MeshNodeConverter::from_elements(elements).unwrap(), &MeshNodeConverter::new(file.path()).unwrap());
+
+        // Unsorted ids, in the same order as the file lines
+        let elements = vec![(7, vec![3, 1]), (2, vec![1, 0, 4]), (5, vec![4])];
+        let mut file = NamedTempFile::new().unwrap();
+        for (element_id, node_ids) in &elements {
+            writeln!(file, "{} {}", element_id, node_ids.iter().map(u32::to_string).collect::<Vec<_>>().join(" ")).unwrap();
+        }
+        let converter = MeshNodeConverter::from_elements(elements).unwrap();
+        assert_same_converter(&converter, &MeshNodeConverter::new(file.path()).unwrap());
+        assert_eq!(converter.local_to_global(2, 2).unwrap(), 4);
+
+        let file = NamedTempFile::new().unwrap();
+        assert_same_converter(&MeshNodeConverter::from_elements(Vec::new()).unwrap(), &MeshNodeConverter::new(file.path()).unwrap());
+
+        assert!(matches!(MeshNodeConverter::from_elements(vec![(3, vec![1]), (3, vec![2])]), Err(MeshError::DuplicateElement(3))));
+        assert!(matches!(MeshNodeConverter::from_elements(vec![(0, vec![1]), (4, vec![])]), Err(MeshError::EmptyElement(4))));
+    }
+
+    #[test]
+    fn test_one_based_node_ids() {
+        let zero_based = create_simple_test_file();
+        let mut one_based = NamedTempFile::new().unwrap();
+        writeln!(one_based, "# same elements, nodes numbered from 1").unwrap();
+        writeln!(one_based, "2 13 14 15").unwrap();
+        writeln!(one_based, "0 11 12 13").unwrap();
+        writeln!(one_based, "1 12 13 14").unwrap();
+
+        let converter = MeshNodeConverter::new_with_base(one_based.path(), IndexBase::OneBased).unwrap();
+        let expected = MeshNodeConverter::new(zero_based.path()).unwrap();
+        assert_eq!(converter.element_to_nodes, expected.element_to_nodes);
+        assert_eq!(converter.index_to_element_id, expected.index_to_element_id);
+        assert_eq!(converter.max_node_id(), 14);
+        assert_eq!(converter.to_assembly_connectivity(), expected.to_assembly_connectivity());
+        // Same elements per node, up to the order of the file lines
+        for (node_elements, expected_elements) in converter.node_to_elements.iter().zip(&expected.node_to_elements) {
+            let mut node_elements = node_elements.clone();
+            node_elements.sort();
+            assert_eq!(&node_elements, expected_elements);
+        }
+
+        assert_same_converter(&MeshNodeConverter::new_with_base(zero_based.path(), IndexBase::ZeroBased).unwrap(), &expected);
+        assert_eq!(IndexBase::default(), IndexBase::ZeroBased);
+
+        let mut invalid = NamedTempFile::new().unwrap();
+        writeln!(invalid, "4 1 2 3").unwrap();
+        writeln!(invalid, "5 0 1 2").unwrap();
+        assert!(matches!(MeshNodeConverter::new_with_base(invalid.path(), IndexBase::OneBased), Err(MeshError::ZeroNodeId(5))));
+        assert!(MeshNodeConverter::new_with_base(invalid.path(), IndexBase::ZeroBased).is_ok());
+    }
+
+    #[test]
+    fn test_new_parallel_empty_and_invalid_files() {
+        let file = NamedTempFile::new().unwrap();
+        let converter = MeshNodeConverter::new_parallel(file.path()).unwrap();
+        assert_same_converter(&converter, &MeshNodeConverter::new(file.path()).unwrap());
+
+        let mut file = NamedTempFile::new().unwrap();
+        writeln!(file, "0 1 2").unwrap();
+        writeln!(file, "1 2 x").unwrap();
+        assert!(matches!(MeshNodeConverter::new_parallel(file.path()), Err(MeshError::ParseError(_))));
+    }
+
+    #[test]
+    fn test_duplicate_element_id() {
+        let mut file = NamedTempFile::new().unwrap();
+        writeln!(file, "3 1 2 3").unwrap();
+        writeln!(file, "5 2 3 4").unwrap();
+        writeln!(file, "3 4 5 6").unwrap();
+
+        assert!(matches!(MeshNodeConverter::new(file.path()), Err(MeshError::DuplicateElement(3))));
+        assert!(matches!(MeshNodeConverter::new_parallel(file.path()), Err(MeshError::DuplicateElement(3))));
+    }
+
+    #[test]
+    fn test_unsorted_element_ids_detected() {
+        let file = create_simple_test_file();
+        let mut converter = MeshNodeConverter::new(file.path()).unwrap();
+        assert!(converter.is_sorted_unique());
+        assert!(matches!(converter.local_to_global(7, 0), Err(MeshError::ElementNotFound(7))));
+
+        // Corrupt the internal order: in debug builds, lookups that miss report the broken invariant
+        converter.index_to_element_id.reverse();
+        assert!(!converter.is_sorted_unique());
+        if cfg!(debug_assertions) {
+            assert!(matches!(converter.local_to_global(0, 0), Err(MeshError::UnsortedElementIds)));
+            assert!(matches!(converter.local_to_global(7, 0), Err(MeshError::UnsortedElementIds)));
+        } else {
+            assert!(matches!(converter.local_to_global(7, 0), Err(MeshError::ElementNotFound(7))));
+        }
+
+        // Duplicates break the invariant as well
+        converter.index_to_element_id = vec![0, 1, 1];
+        assert!(!converter.is_sorted_unique());
+    }
+
+    #[test]
+    fn test_comment_and_blank_lines() {
+        let mut file = NamedTempFile::new().unwrap();
+        writeln!(file, "# connectivity exported by a mesher").unwrap();
+        writeln!(file, "#element nodes 1 2 x").unwrap();
+        writeln!(file).unwrap();
+        writeln!(file, "0 10 11 12").unwrap();
+        writeln!(file, "   # indented comment").unwrap();
+        writeln!(file, "  ").unwrap();
+        writeln!(file, "1 11 12 13").unwrap();
+        writeln!(file, "# trailing comment").unwrap();
+
+        let converter = MeshNodeConverter::new(file.path()).unwrap();
+        assert_eq!(converter.num_elements(), 2);
+        assert_eq!(converter.max_node_id(), 13);
+        assert_eq!(converter.local_to_global(1, 2).unwrap(), 13);
+        assert_same_converter(&MeshNodeConverter::new_parallel_with_chunks(file.path(), 3).unwrap(), &converter);
+
+        let streamed: Vec<(u32, Vec<u32>)> = ConnectivityStream::from_path(file.path()).unwrap().map(Result::unwrap).collect();
+        assert_eq!(streamed, vec![(0, vec![10, 11, 12]), (1, vec![11, 12, 13])]);
+    }
+
+    #[test]
+    fn test_id_only_line() {
+        let mut file = NamedTempFile::new().unwrap();
+        writeln!(file, "0 1 2 3").unwrap();
+        writeln!(file, "7").unwrap();
+        writeln!(file, "1 2 3 4").unwrap();
+
+        assert!(matches!(MeshNodeConverter::new(file.path()), Err(MeshError::EmptyElement(7))));
+        assert!(matches!(MeshNodeConverter::new_parallel(file.path()), Err(MeshError::EmptyElement(7))));
+    }
+
+    #[test]
+    fn test_connectivity_stream_file_order() {
+        let file = create_simple_test_file();
+        let streamed: Vec<(u32, Vec<u32>)> = ConnectivityStream::from_path(file.path())
+            .unwrap()
+            .collect::<Result<_, MeshError>>()
+            .unwrap();
+
+        assert_eq!(streamed, vec![
+            (0, vec![10, 11, 12]),
+            (1, vec![11, 12, 13]),
+            (2, vec![12, 13, 14]),
+        ]);
+
+        let mut file = NamedTempFile::new().unwrap();
+        writeln!(file, "4 1 2").unwrap();
+        writeln!(file).unwrap();
+        writeln!(file, "3 x").unwrap();
+        let mut stream = ConnectivityStream::from_path(file.path()).unwrap();
+        assert_eq!(stream.next_element().unwrap().unwrap(), (4, &[1, 2][..]));
+        assert!(matches!(stream.next_element(), Some(Err(MeshError::ParseError(_)))));
+        assert!(stream.next_element().is_none());
+    }
+
+    #[test]
+    fn test_iter_elements_in_id_order() {
+        let file = create_simple_test_file();
+        let converter = MeshNodeConverter::new(file.path()).unwrap();
+        let elements: Vec<(u32, Vec<u32>)> = converter.iter_elements().map(|(id, nodes)| (id, nodes.to_vec())).collect();
+        assert_eq!(elements, vec![
+            (0, vec![10, 11, 12]),
+            (1, vec![11, 12, 13]),
+            (2, vec![12, 13, 14]),
+        ]);
+
+        // Unsorted input files are still walked in element-id order
+        let mut file = NamedTempFile::new().unwrap();
+        writeln!(file, "2 12 13 14").unwrap();
+        writeln!(file, "0 10 11 12").unwrap();
+        writeln!(file, "1 11 12 13").unwrap();
+        let converter = MeshNodeConverter::new(file.path()).unwrap();
+
+        let elements: Vec<(u32, Vec<u32>)> = converter.iter_elements().map(|(id, nodes)| (id, nodes.to_vec())).collect();
+        assert_eq!(elements, vec![
+            (0, vec![10, 11, 12]),
+            (1, vec![11, 12, 13]),
+            (2, vec![12, 13, 14]),
+        ]);
+    }
+
+    #[test]
+    fn test_remove_element() {
+        let file = create_simple_test_file();
+        let mut converter = MeshNodeConverter::new(file.path()).unwrap();
+
+        converter.remove_element(2).unwrap();
+        assert_eq!(converter.num_elements(), 2);
+        assert!(matches!(converter.local_to_global(2, 0), Err(MeshError::ElementNotFound(2))));
+        assert!(matches!(converter.remove_element(2), Err(MeshError::ElementNotFound(2))));
+        assert_eq!(converter.local_to_global(0, 2).unwrap(), 12);
+        assert_eq!(converter.local_to_global(1, 2).unwrap(), 13);
+        assert_eq!(converter.elements_containing_node(13), &[(1, 2)]);
+        assert!(converter.elements_containing_node(14).is_empty());
+
+        // Node 14 was only used by element 2
+        assert_eq!(converter.max_node_id(), 14);
+        converter.recompute_max_node_id();
+        assert_eq!(converter.max_node_id(), 13);
+
+        converter.remove_element(0).unwrap();
+        assert_eq!(converter.iter_elements().map(|(id, _)| id).collect::<Vec<_>>(), vec![1]);
+        assert_eq!(converter.local_to_global(1, 0).unwrap(), 11);
+    }
+
+    #[test]
+    fn test_assembly_connectivity() {
+        use crate::assemble::assembly::initialize_stiffness_matrix;
+
+        let file = create_simple_test_file();
+        let converter = MeshNodeConverter::new(file.path()).unwrap();
+
+        let (elements, num_nodes) = converter.to_assembly_connectivity();
+        assert_eq!(num_nodes, 15);
+        assert_eq!(elements[2], vec![12, 13, 14]);
+        let matrix = initialize_stiffness_matrix(num_nodes, &elements, 3).unwrap();
+        assert_eq!(matrix.shape(), (45, 45));
+
+        // Nodes 0..=9 are unused and dropped
+        let (elements, num_nodes, compact_to_node_id) = converter.to_compact_assembly_connectivity();
+        assert_eq!(num_nodes, 5);
+        assert_eq!(compact_to_node_id, vec![10, 11, 12, 13, 14]);
+        assert_eq!(elements, vec![vec![0, 1, 2], vec![1, 2, 3], vec![2, 3, 4]]);
+        let matrix = initialize_stiffness_matrix(num_nodes, &elements, 3).unwrap();
+        assert_eq!(matrix.shape(), (15, 15));
+    }
+
+    #[test]
+    fn test_compact_node_ids() {
+        let file = create_simple_test_file();
+        let converter = MeshNodeConverter::new(file.path()).unwrap();
+
+        let (compacted, new_to_old, old_to_new) = converter.compact_node_ids();
+        assert_eq!(new_to_old, vec![10, 11, 12, 13, 14]);
+        assert_eq!(old_to_new.len(), 5);
+        for (new_id, &old_id) in new_to_old.iter().enumerate() {
+            assert_eq!(old_to_new[&old_id], new_id as u32);
+        }
+
+        assert_eq!(compacted.max_node_id(), 4);
+        assert_eq!(compacted.num_elements(), 3);
+        assert_eq!(compacted.get_global_nodes_for_elements(&[0, 2]).unwrap(), vec![(0, vec![0, 1, 2]), (2, vec![2, 3, 4])]);
+        assert_eq!(compacted.elements_containing_node(2), converter.elements_containing_node(12));
+        assert!(compacted.elements_containing_node(5).is_empty());
+    }
+
+    #[test]
+    fn test_write_connectivity_round_trip() {
+        let mut file = NamedTempFile::new().unwrap();
+        writeln!(file, "100 1 2 3").unwrap();
+        writeln!(file, "50 4 5 6 7").unwrap();
+        writeln!(file, "200 7 8 9").unwrap();
+        let converter = MeshNodeConverter::new(file.path()).unwrap();
+        
+        let mut buffer: Vec<u8> = Vec::new();
+        converter.write_connectivity(&mut buffer).unwrap();
+        assert_eq!(String::from_utf8(buffer.clone()).unwrap(), "50 4 5 6 7\n100 1 2 3\n200 7 8 9\n");
+        
+        let mut written = NamedTempFile::new().unwrap();
+        written.write_all(&buffer).unwrap();
+        let reread = MeshNodeConverter::new(written.path()).unwrap();
+        
+        assert_eq!(reread.element_to_nodes, converter.element_to_nodes);
+        assert_eq!(reread.index_to_element_id, converter.index_to_element_id);
+    }
+}
@@ -1,7 +1,7 @@
-use std::io::{self, BufRead};
-use std::str::FromStr;
-use nalgebra::{Vector3, Vector2, Matrix3xX, Matrix2xX, U3, U2};
-use nalgebra::dimension::{Dynamic};
+use std::io;
+use nalgebra::{Vector3, Vector2, Matrix3xX, Matrix2xX};
+
+use crate::mesh::node_coordinates_ndarray::{data_lines, parse_coord_line, NodeError};
 
 #[derive(Debug)]
 pub struct Node3(Vector3<f64>);
@@ -23,65 +23,23 @@ impl Node2 {
     }
 }
 
-/// Common parsing function for both 2D and 3D cases
-fn parse_line<const N: usize>(line: &str) -> io::Result<[f64; N]> {
-    let line = line.trim();
-    if line.is_empty() {
-        return Err(io::Error::new(
-            io::ErrorKind::InvalidData,
-            "Empty line encountered",
-        ));
-    }
-
-    let mut coords = [0.0; N];
-    let mut count = 0;
-    let mut parts = line.split(|c: char| c.is_whitespace() || c == ',')
-        .filter(|s| !s.is_empty());
-
-    // Parse exactly N coordinates
-    for (i, coord) in coords.iter_mut().enumerate() {
-        let part = match parts.next() {
-            Some(p) => p,
-            None => break,
-        };
-        
-        *coord = f64::from_str(part).map_err(|e| {
-            io::Error::new(
-                io::ErrorKind::InvalidData,
-                format!("Invalid coordinate at position {}: {}", i, e),
-            )
-        })?;
-        count += 1;
-    }
-
-    // Check for correct number of coordinates
-    if count != N {
-        return Err(io::Error::new(
-            io::ErrorKind::InvalidData,
-            format!("Expected {} coordinates, found {}", N, count),
-        ));
-    }
-
-    // Check for extra coordinates
-    if parts.next().is_some() {
-        return Err(io::Error::new(
-            io::ErrorKind::InvalidData,
-            format!("Found more than {} coordinates", N),
-        ));
-    }
-
-    Ok(coords)
+/// Common reading loop for both 2D and 3D cases, with the line parsing of `node_coordinates_ndarray`:
+/// blank lines and `#` comments are skipped, errors carry the 1-based line number
+fn read_coords<const N: usize, R: io::Read>(reader: R) -> Result<Vec<[f64; N]>, NodeError> {
+    data_lines(reader)
+        .map(|line| {
+            let (line_number, line) = line?;
+            parse_coord_line(&line).map_err(|source| NodeError::AtLine { line: line_number, source: Box::new(source) })
+        })
+        .collect()
 }
 
-pub fn read_nodes_3D<R: io::Read>(reader: R) -> io::Result<Matrix3xX<f64>> {
-    let reader = io::BufReader::new(reader);
-    let mut nodes = Vec::new();
-
-    for line in reader.lines() {
-        let line = line?;
-        let coords: [f64; 3] = parse_line(&line)?;
-        nodes.push(Vector3::new(coords[0], coords[1], coords[2]));
-    }
+#[allow(non_snake_case)]
+pub fn read_nodes_3D<R: io::Read>(reader: R) -> Result<Matrix3xX<f64>, NodeError> {
+    let nodes: Vec<Vector3<f64>> = read_coords::<3, _>(reader)?
+        .into_iter()
+        .map(Vector3::from)
+        .collect();
 
     if nodes.is_empty() {
         return Ok(Matrix3xX::zeros(0));
@@ -90,15 +48,12 @@ pub fn read_nodes_3D<R: io::Read>(reader: R) -> io::Result<Matrix3xX<f64>> {
     Ok(Matrix3xX::from_columns(&nodes))
 }
 
-pub fn read_nodes_2D<R: io::Read>(reader: R) -> io::Result<Matrix2xX<f64>> {
-    let reader = io::BufReader::new(reader);
-    let mut nodes = Vec::new();
-
-    for line in reader.lines() {
-        let line = line?;
-        let coords: [f64; 2] = parse_line(&line)?;
-        nodes.push(Vector2::new(coords[0], coords[1]));
-    }
+#[allow(non_snake_case)]
+pub fn read_nodes_2D<R: io::Read>(reader: R) -> Result<Matrix2xX<f64>, NodeError> {
+    let nodes: Vec<Vector2<f64>> = read_coords::<2, _>(reader)?
+        .into_iter()
+        .map(Vector2::from)
+        .collect();
 
     if nodes.is_empty() {
         return Ok(Matrix2xX::zeros(0));
@@ -110,41 +65,60 @@ pub fn read_nodes_2D<R: io::Read>(reader: R) -> io::Result<Matrix2xX<f64>> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    
+    use crate::mesh::node_coordinates_ndarray::read_nodes;
+
     #[test]
     fn test_read_nodes() {
         let data = "\
             0.0234 3.45 7.546\n\
             2.4534, 564.44, 6.453\n\
             5.34 7.883 10.44".as_bytes();
-        
+
         let nodes = read_nodes_3D(data).unwrap();
-        
-        assert_eq!(nodes.len(), 3);
-        assert_eq!(nodes[0].x, 0.0234);
-        assert_eq!(nodes[0].y, 3.45);
-        assert_eq!(nodes[0].z, 7.546);
-        
-        assert_eq!(nodes[1].x, 2.4534);
-        assert_eq!(nodes[1].y, 564.44);
-        assert_eq!(nodes[1].z, 6.453);
-        
-        assert_eq!(nodes[2].x, 5.34);
-        assert_eq!(nodes[2].y, 7.883);
-        assert_eq!(nodes[2].z, 10.44);
+
+        assert_eq!(nodes.ncols(), 3);
+        assert_eq!(nodes[(0, 0)], 0.0234);
+        assert_eq!(nodes[(1, 0)], 3.45);
+        assert_eq!(nodes[(2, 0)], 7.546);
+
+        assert_eq!(nodes[(0, 1)], 2.4534);
+        assert_eq!(nodes[(1, 1)], 564.44);
+        assert_eq!(nodes[(2, 1)], 6.453);
+
+        assert_eq!(nodes[(0, 2)], 5.34);
+        assert_eq!(nodes[(1, 2)], 7.883);
+        assert_eq!(nodes[(2, 2)], 10.44);
     }
-    
+
     #[test]
     fn test_empty_input() {
         let data = "".as_bytes();
         let nodes = read_nodes_3D(data).unwrap();
         assert!(nodes.is_empty());
     }
-    
+
     #[test]
     fn test_invalid_input() {
         let data = "1.0 2.0".as_bytes();
         let result = read_nodes_3D(data);
         assert!(result.is_err());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_matches_ndarray_reader() {
+        let data = "# x y\n\n1.5e-3, -2E2\n  # moved node\n4e1 0.25 # corner\n";
+
+        let nodes = read_nodes_2D(data.as_bytes()).unwrap();
+        let expected = read_nodes::<2, _>(data.as_bytes()).unwrap();
+        assert_eq!(nodes.shape(), expected.dim());
+        for ((row, col), &value) in expected.indexed_iter() {
+            assert_eq!(nodes[(row, col)], value);
+        }
+        assert_eq!(nodes[(1, 0)], -200.0);
+
+        let data = "# header\n1 2 3\n\n4 5 x6\n";
+        let error = read_nodes_3D(data.as_bytes()).unwrap_err();
+        assert_eq!(error, read_nodes::<3, _>(data.as_bytes()).unwrap_err());
+        assert_eq!(error.to_string(), "Line 4: Invalid coordinate at position 2: 'x6'");
+    }
+}
@@ -2,8 +2,13 @@
 //!
 //! This module provides structures and functions for parsing node coordinates
 //! from text input and representing them as validated 2D or 3D points.
+//! `write_nodes` emits coordinates in the same text format, and `read_nodes_with_progress`
+//! reports the bytes consumed while reading large files.
+//!
+//! Blank lines and `#` comments, whole-line or trailing, are skipped by every reader, and
+//! `parse_coord_line` is shared with the nalgebra readers of `node_coordinates`.
 
-use std::io::BufRead;
+use std::io::{BufRead, Read, Write};
 use std::str::FromStr;
 use ndarray::{Array2, Array1};
 
@@ -20,6 +25,12 @@ pub enum NodeError {
     ExtraCoordinates { expected: usize },
     /// Invalid array dimension for node type
     InvalidDimension { expected: usize, found: usize },
+    /// Invalid node id format
+    InvalidNodeId { value: String },
+    /// Node id appearing on more than one line
+    DuplicateNodeId { id: u32 },
+    /// Error on a given 1-based line of the input
+    AtLine { line: usize, source: Box<NodeError> },
 }
 
 impl std::fmt::Display for NodeError {
@@ -38,11 +49,27 @@ impl std::fmt::Display for NodeError {
             NodeError::InvalidDimension { expected, found } => {
                 write!(f, "Expected {} elements, found {}", expected, found)
             }
+            NodeError::InvalidNodeId { value } => {
+                write!(f, "Invalid node id: '{}'", value)
+            }
+            NodeError::DuplicateNodeId { id } => {
+                write!(f, "Duplicate node id {}", id)
+            }
+            NodeError::AtLine { line, source } => {
+                write!(f, "Line {}: {}", line, source)
+            }
         }
     }
 }
 
-impl std::error::Error for NodeError {}
+impl std::error::Error for NodeError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            NodeError::AtLine { source, .. } => Some(source.as_ref()),
+            _ => None,
+        }
+    }
+}
 
 /// Represents a 3D node with exactly three coordinates (x, y, z).
 ///
@@ -282,15 +309,93 @@ impl TryFrom<Vec<f64>> for Node2 {
     }
 }
 
+/// Represents a node with exactly DIM coordinates, e.g. space-time (x, y, z, t) for DIM=4.
+///
+/// # Examples
+/// ```
+/// use node_reader::NodeN;
+///
+/// let node = NodeN::<4>::new([1.0, 2.0, 3.0, 4.0]);
+/// assert_eq!(node.get(3), Some(4.0));
+/// assert_eq!(node.get(4), None);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct NodeN<const DIM: usize>(Array1<f64>);
+
+impl<const DIM: usize> NodeN<DIM> {
+    /// Creates a new node with the given coordinates.
+    #[inline]
+    pub fn new(coords: [f64; DIM]) -> Self {
+        NodeN(Array1::from_vec(coords.to_vec()))
+    }
+
+    /// Creates a node from an existing array, validating the dimension.
+    ///
+    /// # Arguments
+    /// * `arr` - An array containing exactly DIM elements
+    ///
+    /// # Returns
+    /// * `Ok(NodeN)` if the array has exactly DIM elements
+    /// * `Err(NodeError)` if the array has incorrect dimensions
+    #[inline]
+    pub fn from_array(arr: Array1<f64>) -> Result<Self, NodeError> {
+        if arr.len() != DIM {
+            return Err(NodeError::InvalidDimension {
+                expected: DIM,
+                found: arr.len(),
+            });
+        }
+        Ok(NodeN(arr))
+    }
+
+    /// Returns the i-th coordinate of the node, or `None` if i >= DIM.
+    #[inline]
+    pub fn get(&self, i: usize) -> Option<f64> {
+        self.0.get(i).copied()
+    }
+
+    /// Returns the node coordinates as a slice.
+    #[inline]
+    pub fn as_slice(&self) -> &[f64] {
+        self.0.as_slice().unwrap()
+    }
+
+    /// Returns a reference to the underlying array.
+    #[inline]
+    pub fn as_array(&self) -> &Array1<f64> {
+        &self.0
+    }
+}
+
+impl<const DIM: usize> TryFrom<Array1<f64>> for NodeN<DIM> {
+    type Error = NodeError;
+
+    /// Attempts to convert an `Array1<f64>` into a `NodeN`.
+    fn try_from(arr: Array1<f64>) -> Result<Self, Self::Error> {
+        NodeN::from_array(arr)
+    }
+}
+
+impl<const DIM: usize> TryFrom<Vec<f64>> for NodeN<DIM> {
+    type Error = NodeError;
+
+    /// Attempts to convert a `Vec<f64>` into a `NodeN`.
+    fn try_from(vec: Vec<f64>) -> Result<Self, Self::Error> {
+        NodeN::from_array(Array1::from_vec(vec))
+    }
+}
+
 /// Parses a line of text into an array of exactly N floating-point coordinates.
 ///
+/// Coordinates are separated by whitespace or commas and may use exponent notation (`1.5e-3`).
+///
 /// # Arguments
 /// * `line` - A line of text containing coordinates separated by whitespace or commas
 ///
 /// # Returns
 /// * `Ok([f64; N])` - Array of parsed coordinates
 /// * `Err(NodeError)` - If parsing fails for any reason
-fn parse_line<const N: usize>(line: &str) -> Result<[f64; N], NodeError> {
+pub fn parse_coord_line<const N: usize>(line: &str) -> Result<[f64; N], NodeError> {
     let line = line.trim();
     if line.is_empty() {
         return Err(NodeError::EmptyInput);
@@ -331,9 +436,31 @@ fn parse_line<const N: usize>(line: &str) -> Result<[f64; N], NodeError> {
     Ok(coords)
 }
 
+/// Lines of `reader` that carry data, as (1-based line number, content without comment).
+///
+/// Everything from a `#` to the end of the line is a comment; lines left blank are skipped.
+/// Read failures are reported as `NodeError::AtLine` wrapping `InvalidCoordinate`.
+pub(crate) fn data_lines<R: std::io::Read>(reader: R) -> impl Iterator<Item = Result<(usize, String), NodeError>> {
+    std::io::BufReader::new(reader)
+        .lines()
+        .enumerate()
+        .filter_map(|(index, line)| {
+            let line = match line {
+                Ok(line) => line,
+                Err(e) => {
+                    let source = NodeError::InvalidCoordinate { position: 0, value: e.to_string() };
+                    return Some(Err(NodeError::AtLine { line: index + 1, source: Box::new(source) }));
+                }
+            };
+            let data = line.split('#').next().unwrap_or("").trim();
+            (!data.is_empty()).then(|| Ok((index + 1, data.to_string())))
+        })
+}
+
 /// Reads 2D or 3D nodes from a reader and returns them as an array of shape (DIM, n_nodes).
 ///
-/// Every single line of the input should contain exactly DIM coordinates separated by whitespace or commas.
+/// Every data line of the input should contain exactly DIM coordinates separated by whitespace or commas.
+/// Blank lines and `#` comments are skipped.
 /// For 2D nodes, use DIM=2 with coordinates (x, y).
 /// For 3D nodes, use DIM=3 with coordinates (x, y, z).
 ///
@@ -344,7 +471,7 @@ fn parse_line<const N: usize>(line: &str) -> Result<[f64; N], NodeError> {
 ///
 /// # Returns
 /// * `Ok(Array2<f64>)` - 2D array with shape (DIM, n_nodes) containing node coordinates
-/// * `Err(NodeError)` - If reading or parsing fails
+/// * `Err(NodeError)` - If reading or parsing fails, as `NodeError::AtLine` with the 1-based line number
 ///
 /// # Examples
 /// ```
@@ -362,16 +489,12 @@ fn parse_line<const N: usize>(line: &str) -> Result<[f64; N], NodeError> {
 /// assert_eq!(nodes.shape(), [2, 2]);
 /// ```
 pub fn read_nodes<const DIM: usize, R: std::io::Read>(reader: R) -> Result<Array2<f64>, NodeError> {
-    let reader = std::io::BufReader::new(reader);
     let mut nodes: Vec<[f64; DIM]> = Vec::new();
 
-    for line in reader.lines() {
-        let line = line.map_err(|e| NodeError::InvalidCoordinate {
-            position: 0,
-            value: e.to_string(),
-        })?;
-        
-        let coords: [f64; DIM] = parse_line(&line)?;
+    for line in data_lines(reader) {
+        let (line_number, line) = line?;
+        let coords: [f64; DIM] = parse_coord_line(&line)
+            .map_err(|source| NodeError::AtLine { line: line_number, source: Box::new(source) })?;
         nodes.push(coords);
     }
 
@@ -381,16 +504,239 @@ pub fn read_nodes<const DIM: usize, R: std::io::Read>(reader: R) -> Result<Array
 
     // Convert Vec<[f64; DIM]> to array with shape (DIM, n_nodes)
     // Each column represents a node
-    let n_nodes = nodes.len();
-    let mut array = Array2::zeros((DIM, n_nodes));
-    
-    for (row_idx, node_coords) in nodes.iter().enumerate() {
-        for (col_idx, &coord) in node_coords.iter().enumerate() {
-            array[[row_idx, col_idx]] = coord;
+    Ok(nodes_to_array(&nodes))
+}
+
+/// Number of bytes between two calls of the `read_nodes_with_progress` callback
+const PROGRESS_INTERVAL: u64 = 1 << 20;
+
+/// Same as `read_nodes`, calling `on_progress` with the total number of bytes read so far
+/// after every MiB of input, and once more with the final count when the input is exhausted.
+///
+/// # Arguments
+/// * `reader` - An input reader implementing `std::io::Read`
+/// * `on_progress` - Callback receiving the number of bytes consumed from `reader`
+///
+/// # Examples
+/// ```
+/// use node_reader::read_nodes_with_progress;
+///
+/// let data = "1.0 2.0\n3.0 4.0\n";
+/// let mut bytes_read = 0;
+/// let nodes = read_nodes_with_progress::<2, _>(data.as_bytes(), |bytes| bytes_read = bytes).unwrap();
+/// assert_eq!(bytes_read, data.len() as u64);
+/// ```
+pub fn read_nodes_with_progress<const DIM: usize, R: Read>(reader: R, on_progress: impl FnMut(u64)) -> Result<Array2<f64>, NodeError> {
+    read_nodes::<DIM, _>(ProgressReader::new(reader, PROGRESS_INTERVAL, on_progress))
+}
+
+/// Counts the bytes read through it and reports them every `interval` bytes and at the end of input
+struct ProgressReader<R, F> {
+    inner: R,
+    on_progress: F,
+    interval: u64,
+    bytes_read: u64,
+    next_report: u64,
+    finished: bool,
+}
+
+impl<R: Read, F: FnMut(u64)> ProgressReader<R, F> {
+    fn new(inner: R, interval: u64, on_progress: F) -> Self {
+        ProgressReader { inner, on_progress, interval, bytes_read: 0, next_report: interval, finished: false }
+    }
+}
+
+impl<R: Read, F: FnMut(u64)> Read for ProgressReader<R, F> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.bytes_read += n as u64;
+
+        if n == 0 && !buf.is_empty() {
+            if !self.finished {
+                self.finished = true;
+                (self.on_progress)(self.bytes_read);
+            }
+        } else if self.bytes_read >= self.next_report {
+            (self.on_progress)(self.bytes_read);
+            self.next_report = (self.bytes_read / self.interval + 1) * self.interval;
         }
+        Ok(n)
     }
-    
-    Ok(array)
+}
+
+/// Writes an array of shape (DIM, n_nodes) as text that `read_nodes::<DIM, _>` parses back.
+///
+/// Every node is written on its own line as DIM whitespace-separated coordinates in scientific
+/// notation with `precision` digits after the decimal point. A precision of 16 or more
+/// reproduces the coordinates exactly.
+///
+/// # Arguments
+/// * `coords` - Array with shape (DIM, n_nodes), one node per column
+/// * `writer` - An output writer implementing `std::io::Write`
+/// * `precision` - Number of digits after the decimal point
+///
+/// # Returns
+/// * `Ok(())` - If all nodes were written
+/// * `Err(io::Error)` - If writing fails, or with `InvalidInput` if `coords` does not have DIM rows
+///
+/// # Examples
+/// ```
+/// use node_reader::{read_nodes, write_nodes};
+///
+/// let nodes = read_nodes::<2, _>("1.0 2.0\n3.0 4.0\n".as_bytes()).unwrap();
+/// let mut buffer = Vec::new();
+/// write_nodes::<2, _>(&nodes, &mut buffer, 16).unwrap();
+/// assert_eq!(read_nodes::<2, _>(buffer.as_slice()).unwrap(), nodes);
+/// ```
+pub fn write_nodes<const DIM: usize, W: Write>(coords: &Array2<f64>, writer: W, precision: usize) -> std::io::Result<()> {
+    if coords.nrows() != DIM {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            NodeError::InvalidDimension { expected: DIM, found: coords.nrows() },
+        ));
+    }
+
+    let mut writer = std::io::BufWriter::new(writer);
+    for column in coords.columns() {
+        for (i, value) in column.iter().enumerate() {
+            let separator = if i == 0 { "" } else { " " };
+            write!(writer, "{}{:.*e}", separator, precision, value)?;
+        }
+        writeln!(writer)?;
+    }
+    writer.flush()
+}
+
+/// Reads 2D or 3D nodes prefixed with an integer id and returns the ids together with
+/// an array of shape (DIM, n_nodes).
+///
+/// Every data line of the input should contain `id x y [z]` separated by whitespace or commas;
+/// blank lines and `#` comments are skipped. The i-th entry of the returned ids belongs to the i-th column of the array.
+///
+/// # Arguments
+/// * `reader` - An input reader implementing `std::io::Read`
+///
+/// # Returns
+/// * `Ok((Vec<u32>, Array2<f64>))` - Node ids in file order and the (DIM, n_nodes) coordinates
+/// * `Err(NodeError)` - If reading or parsing fails, or if an id appears more than once, as
+///   `NodeError::AtLine` with the 1-based line number
+///
+/// # Examples
+/// ```
+/// use node_reader::read_nodes_with_ids;
+///
+/// let data = "10 1.0 2.0\n4, 3.0, 4.0\n".as_bytes();
+/// let (ids, nodes) = read_nodes_with_ids::<2, _>(data).unwrap();
+/// assert_eq!(ids, vec![10, 4]);
+/// assert_eq!(nodes.shape(), [2, 2]);
+/// ```
+pub fn read_nodes_with_ids<const DIM: usize, R: std::io::Read>(reader: R) -> Result<(Vec<u32>, Array2<f64>), NodeError> {
+    let mut ids: Vec<u32> = Vec::new();
+    let mut nodes: Vec<[f64; DIM]> = Vec::new();
+    let mut seen_ids = std::collections::HashSet::new();
+
+    for line in data_lines(reader) {
+        let (line_number, line) = line?;
+        let line = line.as_str();
+
+        let mut parse_line = || -> Result<(u32, [f64; DIM]), NodeError> {
+            let (id_str, coords_str) = line
+                .split_once(|c: char| c.is_whitespace() || c == ',')
+                .unwrap_or((line, ""));
+
+            let id: u32 = id_str.parse().map_err(|_| NodeError::InvalidNodeId {
+                value: id_str.to_string(),
+            })?;
+            if !seen_ids.insert(id) {
+                return Err(NodeError::DuplicateNodeId { id });
+            }
+
+            let coords: [f64; DIM] = match parse_coord_line(coords_str) {
+                Err(NodeError::EmptyInput) => Err(NodeError::WrongCoordinateCount { expected: DIM, found: 0 }),
+                result => result,
+            }?;
+            Ok((id, coords))
+        };
+        let (id, coords) = parse_line()
+            .map_err(|source| NodeError::AtLine { line: line_number, source: Box::new(source) })?;
+
+        ids.push(id);
+        nodes.push(coords);
+    }
+
+    Ok((ids, nodes_to_array(&nodes)))
+}
+
+/// Growable set of nodes that materializes the (DIM, n_nodes) array on demand.
+///
+/// # Examples
+/// ```
+/// use node_reader::NodeSet;
+///
+/// let mut nodes = NodeSet::<2>::new();
+/// nodes.push([1.0, 2.0]);
+/// nodes.push([3.0, 4.0]);
+/// assert_eq!(nodes.len(), 2);
+/// assert_eq!(nodes.into_array().shape(), [2, 2]);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct NodeSet<const DIM: usize> {
+    nodes: Vec<[f64; DIM]>,
+}
+
+impl<const DIM: usize> NodeSet<DIM> {
+    /// Creates an empty node set.
+    pub fn new() -> Self {
+        NodeSet { nodes: Vec::new() }
+    }
+
+    /// Appends a node at the end of the set.
+    pub fn push(&mut self, coords: [f64; DIM]) {
+        self.nodes.push(coords);
+    }
+
+    /// Returns the number of nodes.
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Returns `true` if the set contains no nodes.
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Returns the coordinates of the i-th node, or `None` if out of range.
+    pub fn coords(&self, i: usize) -> Option<[f64; DIM]> {
+        self.nodes.get(i).copied()
+    }
+
+    /// Converts the set into an array of shape (DIM, n_nodes), one node per column.
+    pub fn into_array(self) -> Array2<f64> {
+        nodes_to_array(&self.nodes)
+    }
+}
+
+impl<const DIM: usize> From<Array2<f64>> for NodeSet<DIM> {
+    /// Wraps an array of shape (DIM, n_nodes), such as the output of `read_nodes`.
+    ///
+    /// # Panics
+    /// If the array does not have DIM rows.
+    fn from(array: Array2<f64>) -> Self {
+        assert_eq!(array.nrows(), DIM, "Expected an array with {} rows, found {}", DIM, array.nrows());
+
+        let nodes = array
+            .columns()
+            .into_iter()
+            .map(|column| std::array::from_fn(|i| column[i]))
+            .collect();
+
+        NodeSet { nodes }
+    }
+}
+
+/// Converts a list of nodes to an array of shape (DIM, n_nodes), one node per column.
+fn nodes_to_array<const DIM: usize>(nodes: &[[f64; DIM]]) -> Array2<f64> {
+    Array2::from_shape_fn((DIM, nodes.len()), |(row, col)| nodes[col][row])
 }
 
 #[cfg(test)]
@@ -409,15 +755,15 @@ mod tests {
         assert_eq!(nodes.shape(), [3,3]);
         //println!("nodes = {:?}", nodes);
         assert_eq!(nodes[[0,0]], 0.0234);
-        assert_eq!(nodes[[0,1]], 3.45);
-        assert_eq!(nodes[[0,2]], 7.546);
+        assert_eq!(nodes[[1,0]], 3.45);
+        assert_eq!(nodes[[2,0]], 7.546);
         
-        assert_eq!(nodes[[1,0]], 2.4534);
+        assert_eq!(nodes[[0,1]], 2.4534);
         assert_eq!(nodes[[1,1]], 564.44);
-        assert_eq!(nodes[[1,2]], 6.453);
+        assert_eq!(nodes[[2,1]], 6.453);
         
-        assert_eq!(nodes[[2,0]], 5.34);
-        assert_eq!(nodes[[2,1]], 7.883);
+        assert_eq!(nodes[[0,2]], 5.34);
+        assert_eq!(nodes[[1,2]], 7.883);
         assert_eq!(nodes[[2,2]], 10.44);
     }
     
@@ -434,4 +780,200 @@ mod tests {
         let result = read_nodes::<3, _>(data);
         assert!(result.is_err());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_node_n() {
+        let node = NodeN::<4>::new([1.0, 2.0, 3.0, 4.0]);
+        assert_eq!(node.as_slice(), &[1.0, 2.0, 3.0, 4.0]);
+        assert_eq!(node.get(3), Some(4.0));
+        assert_eq!(node.get(4), None);
+
+        assert_eq!(NodeN::<4>::try_from(vec![1.0, 2.0, 3.0, 4.0]), Ok(node));
+        assert_eq!(
+            NodeN::<4>::try_from(vec![1.0, 2.0, 3.0]),
+            Err(NodeError::InvalidDimension { expected: 4, found: 3 })
+        );
+        assert_eq!(
+            NodeN::<4>::from_array(Array1::zeros(5)),
+            Err(NodeError::InvalidDimension { expected: 4, found: 5 })
+        );
+
+        // Columns of a 4D read are valid nodes
+        let nodes = read_nodes::<4, _>("1 2 3 4\n5 6 7 8\n".as_bytes()).unwrap();
+        let node = NodeN::<4>::try_from(nodes.column(1).to_owned()).unwrap();
+        assert_eq!(node.as_slice(), &[5.0, 6.0, 7.0, 8.0]);
+    }
+
+    #[test]
+    fn test_read_nodes_reports_line() {
+        let data = "0.0 0.0\n1.0 0.0\n1.0 abc\n0.0 1.0\n".as_bytes();
+        let error = read_nodes::<2, _>(data).unwrap_err();
+        assert_eq!(error, NodeError::AtLine {
+            line: 3,
+            source: Box::new(NodeError::InvalidCoordinate { position: 1, value: "abc".to_string() }),
+        });
+        assert_eq!(error.to_string(), "Line 3: Invalid coordinate at position 1: 'abc'");
+    }
+
+    #[test]
+    fn test_comments_blank_lines_and_exponents() {
+        let data = "\
+            # x y z, exported by the mesher\n\
+            \n\
+            1.5e-3 -2E2 0.0\n\
+            \t  \n\
+            1e0, 2.5e+1, -3.75e-10  # refined corner\n".as_bytes();
+        let nodes = read_nodes::<3, _>(data).unwrap();
+        assert_eq!(nodes, ndarray::array![[1.5e-3, 1.0], [-200.0, 25.0], [0.0, -3.75e-10]]);
+
+        let data = "# id x y\n3 1e-2 2e3\n\n9 -4.0E1 5 # last\n".as_bytes();
+        let (ids, nodes) = read_nodes_with_ids::<2, _>(data).unwrap();
+        assert_eq!(ids, vec![3, 9]);
+        assert_eq!(nodes, ndarray::array![[0.01, -40.0], [2000.0, 5.0]]);
+
+        // Line numbers count the skipped lines
+        let error = read_nodes::<2, _>("# header\n\n1.0 2.0\n1.0e 2.0\n".as_bytes()).unwrap_err();
+        assert_eq!(error.to_string(), "Line 4: Invalid coordinate at position 0: '1.0e'");
+        assert_eq!(read_nodes::<2, _>("# only comments\n\n".as_bytes()).unwrap().shape(), [2, 0]);
+    }
+
+    #[test]
+    fn test_read_nodes_with_progress() {
+        let data: String = (0..2000).map(|i| format!("{}.5 {}e-3 -{}\n", i, i, i)).collect();
+
+        let mut reports = Vec::new();
+        let nodes = read_nodes_with_progress::<3, _>(data.as_bytes(), |bytes| reports.push(bytes)).unwrap();
+        assert_eq!(nodes, read_nodes::<3, _>(data.as_bytes()).unwrap());
+        assert_eq!(reports, vec![data.len() as u64]);
+
+        // With a small interval: increasing counts, at least one per interval, ending at the input length
+        let mut reports = Vec::new();
+        let reader = ProgressReader::new(data.as_bytes(), 1000, |bytes| reports.push(bytes));
+        read_nodes::<3, _>(reader).unwrap();
+        assert!(reports.len() > data.len() / 8192);
+        assert!(reports.windows(2).all(|pair| pair[0] <= pair[1]));
+        assert_eq!(reports.last(), Some(&(data.len() as u64)));
+
+        let mut reports = Vec::new();
+        read_nodes_with_progress::<2, _>("".as_bytes(), |bytes| reports.push(bytes)).unwrap();
+        assert_eq!(reports, vec![0]);
+    }
+
+    #[test]
+    fn test_read_nodes_with_ids_whitespace() {
+        let data = "\
+            7 0.5 1.5 2.5\n\
+            3 -1.0 0.0 4.0".as_bytes();
+
+        let (ids, nodes) = read_nodes_with_ids::<3, _>(data).unwrap();
+
+        assert_eq!(ids, vec![7, 3]);
+        assert_eq!(nodes.shape(), [3, 2]);
+        assert_eq!(nodes.column(0).to_vec(), vec![0.5, 1.5, 2.5]);
+        assert_eq!(nodes.column(1).to_vec(), vec![-1.0, 0.0, 4.0]);
+    }
+
+    #[test]
+    fn test_read_nodes_with_ids_commas() {
+        let data = "\
+            1, 0.25, 0.75\n\
+            2,1.0,2.0\n\
+            5 , 3.0 , 4.0".as_bytes();
+
+        let (ids, nodes) = read_nodes_with_ids::<2, _>(data).unwrap();
+
+        assert_eq!(ids, vec![1, 2, 5]);
+        assert_eq!(nodes.shape(), [2, 3]);
+        assert_eq!(nodes.row(0).to_vec(), vec![0.25, 1.0, 3.0]);
+        assert_eq!(nodes.row(1).to_vec(), vec![0.75, 2.0, 4.0]);
+    }
+
+    #[test]
+    fn test_read_nodes_with_ids_invalid() {
+        let at_line = |line: usize, source: NodeError| Err(NodeError::AtLine { line, source: Box::new(source) });
+
+        let duplicate = "1 0.0 0.0\n# comment\n1 1.0 1.0".as_bytes();
+        assert_eq!(read_nodes_with_ids::<2, _>(duplicate), at_line(3, NodeError::DuplicateNodeId { id: 1 }));
+
+        let bad_id = "\na 0.0 0.0".as_bytes();
+        assert_eq!(
+            read_nodes_with_ids::<2, _>(bad_id),
+            at_line(2, NodeError::InvalidNodeId { value: "a".to_string() })
+        );
+
+        let missing_coords = "4".as_bytes();
+        assert_eq!(
+            read_nodes_with_ids::<2, _>(missing_coords),
+            at_line(1, NodeError::WrongCoordinateCount { expected: 2, found: 0 })
+        );
+
+        let bad_coord = "1 0.0 0.0\n2 0.0 x".as_bytes();
+        assert_eq!(
+            read_nodes_with_ids::<2, _>(bad_coord),
+            at_line(2, NodeError::InvalidCoordinate { position: 1, value: "x".to_string() })
+        );
+
+        let empty = "".as_bytes();
+        let (ids, nodes) = read_nodes_with_ids::<3, _>(empty).unwrap();
+        assert!(ids.is_empty());
+        assert_eq!(nodes.shape(), [3, 0]);
+    }
+
+    #[test]
+    fn test_write_nodes_round_trip() {
+        let nodes_2d = ndarray::array![[0.0, -1.5, 6.02214076e23, 1.0 / 3.0], [-0.0, 2.5e-12, -7.25, -1e-300]];
+        let nodes_3d = ndarray::array![[1.0, -2.0e5], [0.1, 3.3e-7], [-4.75, 123456.789]];
+
+        let mut buffer = Vec::new();
+        write_nodes::<2, _>(&nodes_2d, &mut buffer, 16).unwrap();
+        assert_eq!(String::from_utf8(buffer.clone()).unwrap().lines().count(), 4);
+        assert_eq!(read_nodes::<2, _>(buffer.as_slice()).unwrap(), nodes_2d);
+
+        let mut buffer = Vec::new();
+        write_nodes::<3, _>(&nodes_3d, &mut buffer, 16).unwrap();
+        assert_eq!(read_nodes::<3, _>(buffer.as_slice()).unwrap(), nodes_3d);
+
+        // Lower precision rounds to the requested number of digits
+        let mut buffer = Vec::new();
+        write_nodes::<3, _>(&nodes_3d, &mut buffer, 3).unwrap();
+        assert!(String::from_utf8(buffer.clone()).unwrap().starts_with("1.000e0 1.000e-1 -4.750e0\n"));
+        let reread = read_nodes::<3, _>(buffer.as_slice()).unwrap();
+        for (a, b) in reread.iter().zip(nodes_3d.iter()) {
+            assert!((a - b).abs() <= 1e-3 * b.abs());
+        }
+
+        let error = write_nodes::<3, _>(&nodes_2d, Vec::new(), 16).unwrap_err();
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn test_node_set_push_matches_read_nodes() {
+        let data = "1.0 2.0 3.0\n4.0 5.0 6.0\n".as_bytes();
+        let expected = read_nodes::<3, _>(data).unwrap();
+
+        let mut nodes = NodeSet::<3>::new();
+        assert!(nodes.is_empty());
+        nodes.push([1.0, 2.0, 3.0]);
+        nodes.push([4.0, 5.0, 6.0]);
+
+        assert_eq!(nodes.len(), 2);
+        assert_eq!(nodes.coords(1), Some([4.0, 5.0, 6.0]));
+        assert_eq!(nodes.coords(2), None);
+        assert_eq!(nodes.into_array(), expected);
+    }
+
+    #[test]
+    fn test_node_set_from_array() {
+        let data = "1.0 2.0\n3.0 4.0\n5.0 6.0\n".as_bytes();
+        let array = read_nodes::<2, _>(data).unwrap();
+
+        let mut nodes = NodeSet::<2>::from(array.clone());
+        assert_eq!(nodes.len(), 3);
+        assert_eq!(nodes.coords(2), Some([5.0, 6.0]));
+
+        nodes.push([7.0, 8.0]);
+        let grown = nodes.into_array();
+        assert_eq!(grown.shape(), [2, 4]);
+        assert_eq!(grown.column(3).to_vec(), vec![7.0, 8.0]);
+    }
+}
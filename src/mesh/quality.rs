@@ -0,0 +1,149 @@
+//! Mesh diagnostics based on the element geometry.
+//!
+//! The measure of an element (length in 1D, area in 2D, volume in 3D) is ∫ det J dξ over the
+//! reference element, evaluated with a Gauss-Legendre rule that integrates det J exactly for
+//! undistorted elements. The determinant is signed, so an element whose local node numbering
+//! is mirrored with respect to the reference element has a negative measure.
+//!
+//! The scaled Jacobian at the element centre complements the measure: it flags skewed and
+//! sliver elements whose volume alone looks reasonable.
+
+use ndarray::Array2;
+
+use crate::elements::element_library::hypercube_elements::ShapeFunctionKind;
+use crate::elements::parametric_topology_element::determinant_and_adjugate::determinant_expansion;
+use crate::elements::parametric_topology_element::position_jacobian::compute_position_jacobian;
+use crate::elements::quadrature::quadrature_rules::QuadratureRuleDyn;
+use crate::mesh::locate_nodes_o_log_n::MeshNodeConverter;
+
+/// Signed measure of every element, as (element_id, measure) in element-id order.
+///
+/// `coords` has shape (DIM, total_nodes) and every element of `converter` is assumed to be of `kind`.
+///
+/// # Panics
+/// Panics if the coordinate dimension differs from `kind.dimension()`.
+pub fn element_measures(coords: &Array2<f64>, converter: &MeshNodeConverter, kind: ShapeFunctionKind) -> Vec<(u32, f64)> {
+    let num_points: usize = match kind {
+        ShapeFunctionKind::Line1 | ShapeFunctionKind::Square1 | ShapeFunctionKind::Cube1 => 2,
+        _ => 4,
+    };
+    let rule = QuadratureRuleDyn::gauss_legendre(num_points, kind.dimension() as usize);
+
+    // Shape function derivatives are the same for every element
+    let jacobians_at_points: Vec<Array2<f64>> = rule
+        .points
+        .iter()
+        .map(|point| kind.evaluate_jacobian_of_shape_functions(point))
+        .collect();
+
+    converter
+        .iter_elements()
+        .map(|(element_id, node_ids)| {
+            let measure: f64 = jacobians_at_points
+                .iter()
+                .zip(&rule.weights)
+                .map(|(jacobian_shape_functions, weight)| {
+                    let position_jacobian = compute_position_jacobian(coords, node_ids, jacobian_shape_functions);
+                    determinant(&position_jacobian) * weight
+                })
+                .sum();
+            (element_id, measure)
+        })
+        .collect()
+}
+
+/// Scaled Jacobian of every element at its centre, as (element_id, quality) in element-id order.
+///
+/// The quality is det(J) divided by the product of the column norms of J. It lies in [-1, 1],
+/// is 1 for undistorted (rectangular) elements, tends to 0 for skewed or sliver elements and is
+/// negative for inverted ones. Degenerate elements with a zero-length column get 0.
+///
+/// # Panics
+/// Panics if the coordinate dimension differs from `kind.dimension()`.
+pub fn jacobian_quality(coords: &Array2<f64>, converter: &MeshNodeConverter, kind: ShapeFunctionKind) -> Vec<(u32, f64)> {
+    let jacobian_shape_functions = kind.evaluate_jacobian_of_shape_functions(&kind.center());
+
+    converter
+        .iter_elements()
+        .map(|(element_id, node_ids)| {
+            let position_jacobian = compute_position_jacobian(coords, node_ids, &jacobian_shape_functions);
+            let column_norms: f64 = position_jacobian
+                .columns()
+                .into_iter()
+                .map(|column| column.dot(&column).sqrt())
+                .product();
+
+            let quality = if column_norms == 0.0 { 0.0 } else { determinant(&position_jacobian) / column_norms };
+            (element_id, quality)
+        })
+        .collect()
+}
+
+/// Ids of the elements with a negative measure
+pub fn inverted_elements(measures: &[(u32, f64)]) -> Vec<u32> {
+    measures
+        .iter()
+        .filter(|&&(_, measure)| measure < 0.0)
+        .map(|&(element_id, _)| element_id)
+        .collect()
+}
+
+fn determinant(jac: &Array2<f64>) -> f64 {
+    match determinant_expansion(jac) {
+        Ok(determinant) => determinant,
+        Err(e) => panic!("{}", e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mesh::locate_nodes_o_log_n::converter_from_lines;
+
+    #[test]
+    fn test_unit_cube_and_inverted_hexahedron() {
+        let nodes = ShapeFunctionKind::Cube1.reference_nodes();
+        let coords = Array2::from_shape_fn((3, 8), |(d, i)| nodes[i][d]);
+
+        // Element 1 swaps the x=0 and x=1 nodes, mirroring the element
+        let converter = converter_from_lines(&["0 0 1 2 3 4 5 6 7", "1 1 0 3 2 5 4 7 6"]);
+        let measures = element_measures(&coords, &converter, ShapeFunctionKind::Cube1);
+
+        assert_eq!(measures.len(), 2);
+        assert_eq!(measures[0].0, 0);
+        assert!((measures[0].1 - 1.0).abs() < 1e-12);
+        assert_eq!(measures[1].0, 1);
+        assert!((measures[1].1 + 1.0).abs() < 1e-12);
+        assert_eq!(inverted_elements(&measures), vec![1]);
+    }
+
+    #[test]
+    fn test_jacobian_quality() {
+        let nodes = ShapeFunctionKind::Cube1.reference_nodes();
+        let converter = converter_from_lines(&["0 0 1 2 3 4 5 6 7", "1 1 0 3 2 5 4 7 6"]);
+
+        // Box with unequal sides is still ideal, the mirrored element is -1
+        let coords = Array2::from_shape_fn((3, 8), |(d, i)| nodes[i][d] * [2.0, 1.0, 0.5][d]);
+        let quality = jacobian_quality(&coords, &converter, ShapeFunctionKind::Cube1);
+        assert!((quality[0].1 - 1.0).abs() < 1e-12);
+        assert!((quality[1].1 + 1.0).abs() < 1e-12);
+
+        // Shear the top face by its height: 45 degree skew gives 1/sqrt(2)
+        let sheared = Array2::from_shape_fn((3, 8), |(d, i)| nodes[i][d] + if d == 0 { nodes[i][2] } else { 0.0 });
+        let quality = jacobian_quality(&sheared, &converter, ShapeFunctionKind::Cube1);
+        assert!((quality[0].1 - 0.5_f64.sqrt()).abs() < 1e-12);
+        assert!(quality[0].1 < 0.8);
+    }
+
+    #[test]
+    fn test_quadrilateral_area() {
+        // Trapezoid with parallel sides 2 and 1, height 1
+        let coords = ndarray::array![[0.0, 2.0, 0.0, 1.0], [0.0, 0.0, 1.0, 1.0]];
+        let converter = converter_from_lines(&["5 0 1 2 3"]);
+        let measures = element_measures(&coords, &converter, ShapeFunctionKind::Square1);
+
+        assert_eq!(measures[0].0, 5);
+        assert!((measures[0].1 - 1.5).abs() < 1e-12);
+        assert!(inverted_elements(&measures).is_empty());
+    }
+}
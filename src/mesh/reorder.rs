@@ -0,0 +1,232 @@
+//! Node renumbering for reducing the bandwidth of assembled matrices.
+//!
+//! The (reverse) Cuthill–McKee ordering is computed from the node adjacency graph implied
+//! by the element connectivity. Permutations are returned as `permutation[old_id] = new_id`
+//! and cover every id in `0..=max_node_id`; ids not referenced by any element keep their
+//! relative order and are placed after the connected nodes.
+
+use std::collections::VecDeque;
+
+use crate::mesh::locate_nodes_o_log_n::MeshNodeConverter;
+
+/// Cuthill–McKee permutation mapping old node ids to new ones
+pub fn cuthill_mckee(converter: &MeshNodeConverter) -> Vec<u32> {
+    let ordering = cuthill_mckee_ordering(converter);
+    ordering_to_permutation(&ordering)
+}
+
+/// Reverse Cuthill–McKee permutation mapping old node ids to new ones
+pub fn reverse_cuthill_mckee(converter: &MeshNodeConverter) -> Vec<u32> {
+    let mut ordering = cuthill_mckee_ordering(converter);
+    let connected = ordering.len() - unused_nodes(converter).count();
+    ordering[..connected].reverse();
+    ordering_to_permutation(&ordering)
+}
+
+/// Rewrites an element-connectivity list with the new numbering
+pub fn apply_permutation(elements: &[Vec<u32>], permutation: &[u32]) -> Vec<Vec<u32>> {
+    elements
+        .iter()
+        .map(|nodes| nodes.iter().map(|&node_id| permutation[node_id as usize]).collect())
+        .collect()
+}
+
+/// Largest difference between two node ids sharing an element
+pub fn bandwidth(elements: &[Vec<u32>]) -> u32 {
+    elements
+        .iter()
+        .filter_map(|nodes| Some(nodes.iter().max()? - nodes.iter().min()?))
+        .max()
+        .unwrap_or(0)
+}
+
+/// Sorted, deduplicated neighbours of every node (indexed by global node id)
+fn node_adjacency(converter: &MeshNodeConverter) -> Vec<Vec<u32>> {
+    let mut adjacency: Vec<Vec<u32>> = vec![Vec::new(); converter.max_node_id() as usize + 1];
+
+    for (_, nodes) in converter.iter_elements() {
+        for &node_i in nodes {
+            for &node_j in nodes {
+                if node_i != node_j {
+                    adjacency[node_i as usize].push(node_j);
+                }
+            }
+        }
+    }
+
+    for neighbours in adjacency.iter_mut() {
+        neighbours.sort_unstable();
+        neighbours.dedup();
+    }
+
+    adjacency
+}
+
+fn unused_nodes(converter: &MeshNodeConverter) -> impl Iterator<Item = u32> + '_ {
+    (0..=converter.max_node_id()).filter(|&node_id| converter.elements_containing_node(node_id).is_empty())
+}
+
+/// Nodes in Cuthill–McKee order, followed by the nodes not referenced by any element
+fn cuthill_mckee_ordering(converter: &MeshNodeConverter) -> Vec<u32> {
+    let adjacency = node_adjacency(converter);
+    let num_nodes = adjacency.len();
+
+    let mut visited = vec![false; num_nodes];
+    let mut ordering: Vec<u32> = Vec::with_capacity(num_nodes);
+
+    for node_id in unused_nodes(converter) {
+        visited[node_id as usize] = true;
+    }
+
+    // One breadth-first sweep per connected component
+    for seed in 0..num_nodes as u32 {
+        if visited[seed as usize] {
+            continue;
+        }
+
+        let start = pseudo_peripheral_node(&adjacency, seed);
+        visited[start as usize] = true;
+
+        let mut queue = VecDeque::from([start]);
+        while let Some(node_id) = queue.pop_front() {
+            ordering.push(node_id);
+
+            let mut neighbours: Vec<u32> = adjacency[node_id as usize]
+                .iter()
+                .copied()
+                .filter(|&neighbour| !visited[neighbour as usize])
+                .collect();
+            neighbours.sort_by_key(|&neighbour| (adjacency[neighbour as usize].len(), neighbour));
+
+            for neighbour in neighbours {
+                visited[neighbour as usize] = true;
+                queue.push_back(neighbour);
+            }
+        }
+    }
+
+    ordering.extend(unused_nodes(converter));
+    ordering
+}
+
+/// George–Liu search: repeatedly restart from a minimum-degree node of the last level
+/// until the eccentricity stops increasing
+fn pseudo_peripheral_node(adjacency: &[Vec<u32>], seed: u32) -> u32 {
+    let mut current = seed;
+    let mut levels = level_structure(adjacency, current);
+
+    loop {
+        let last_level = levels.last().expect("level structure contains the root");
+        let candidate = *last_level
+            .iter()
+            .min_by_key(|&&node_id| (adjacency[node_id as usize].len(), node_id))
+            .expect("levels are never empty");
+
+        let candidate_levels = level_structure(adjacency, candidate);
+        if candidate_levels.len() <= levels.len() {
+            return current;
+        }
+
+        current = candidate;
+        levels = candidate_levels;
+    }
+}
+
+/// Breadth-first level structure rooted at `root`
+fn level_structure(adjacency: &[Vec<u32>], root: u32) -> Vec<Vec<u32>> {
+    let mut visited = vec![false; adjacency.len()];
+    visited[root as usize] = true;
+
+    let mut levels = vec![vec![root]];
+    loop {
+        let mut next_level = Vec::new();
+        for &node_id in levels.last().unwrap() {
+            for &neighbour in &adjacency[node_id as usize] {
+                if !visited[neighbour as usize] {
+                    visited[neighbour as usize] = true;
+                    next_level.push(neighbour);
+                }
+            }
+        }
+
+        if next_level.is_empty() {
+            return levels;
+        }
+        levels.push(next_level);
+    }
+}
+
+fn ordering_to_permutation(ordering: &[u32]) -> Vec<u32> {
+    let mut permutation = vec![0u32; ordering.len()];
+    for (new_id, &old_id) in ordering.iter().enumerate() {
+        permutation[old_id as usize] = new_id as u32;
+    }
+    permutation
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mesh::locate_nodes_o_log_n::converter_from_lines;
+
+    // 5x2 quad grid (6x3 nodes) with a scrambled node numbering
+    fn create_scrambled_grid() -> (MeshNodeConverter, Vec<Vec<u32>>) {
+        let (nx, ny) = (6u32, 3u32);
+        let scramble = |i: u32, j: u32| ((j * nx + i) * 7) % (nx * ny);
+
+        let mut elements = Vec::new();
+        for j in 0..ny - 1 {
+            for i in 0..nx - 1 {
+                elements.push(vec![
+                    scramble(i, j),
+                    scramble(i + 1, j),
+                    scramble(i + 1, j + 1),
+                    scramble(i, j + 1),
+                ]);
+            }
+        }
+
+        let lines: Vec<String> = elements
+            .iter()
+            .enumerate()
+            .map(|(element_id, nodes)| {
+                let nodes: Vec<String> = nodes.iter().map(|node| node.to_string()).collect();
+                format!("{} {}", element_id, nodes.join(" "))
+            })
+            .collect();
+
+        (converter_from_lines(&lines), elements)
+    }
+
+    fn assert_bijection(permutation: &[u32], max_node_id: u32) {
+        assert_eq!(permutation.len(), max_node_id as usize + 1);
+        let mut sorted = permutation.to_vec();
+        sorted.sort_unstable();
+        assert_eq!(sorted, (0..=max_node_id).collect::<Vec<u32>>());
+    }
+
+    #[test]
+    fn test_cuthill_mckee_reduces_bandwidth() {
+        let (converter, elements) = create_scrambled_grid();
+
+        let original = bandwidth(&elements);
+
+        let permutation = cuthill_mckee(&converter);
+        assert_bijection(&permutation, converter.max_node_id());
+        assert!(bandwidth(&apply_permutation(&elements, &permutation)) <= original);
+
+        let permutation = reverse_cuthill_mckee(&converter);
+        assert_bijection(&permutation, converter.max_node_id());
+        assert!(bandwidth(&apply_permutation(&elements, &permutation)) <= original);
+    }
+
+    #[test]
+    fn test_unused_nodes_are_placed_last() {
+        let converter = converter_from_lines(&["0 5 2", "1 2 4"]);
+        let permutation = reverse_cuthill_mckee(&converter);
+        assert_bijection(&permutation, 5);
+
+        // Nodes 0, 1 and 3 are not referenced by any element
+        assert_eq!(&[permutation[0], permutation[1], permutation[3]], &[3, 4, 5]);
+    }
+}
@@ -0,0 +1,381 @@
+//! Nearest-node queries on node coordinates.
+//!
+//! `GridIndex` buckets the nodes into a uniform grid over their bounding box, with a cell size
+//! close to the average node spacing so each cell holds about one node. Queries visit the cells
+//! in growing shells around the query point and stop as soon as no unvisited cell can contain a
+//! closer node. Directions much thinner than the largest extent count as flat, and the grid never
+//! has many more cells than nodes.
+
+use ndarray::Array2;
+
+use crate::mesh::hypernode::{HyperNodeError, HyperNodeFile};
+
+// Extents below this fraction of the largest extent are flat; also the smallest relative cell size
+const FLAT_TOLERANCE: f64 = 1e-9;
+
+// Upper bound on the number of grid cells per node
+const MAX_CELLS_PER_NODE: usize = 2;
+
+/// Uniform grid over the nodes of a (DIM, n_nodes) coordinate array. Node ids are column indices.
+#[derive(Debug, Clone)]
+pub struct GridIndex {
+    coords: Array2<f64>,
+    origin: Vec<f64>,
+    cell_size: f64,
+    cells_per_dim: Vec<usize>,
+    cell_start: Vec<usize>, // Nodes of cell c are cell_nodes[cell_start[c]..cell_start[c + 1]]
+    cell_nodes: Vec<u32>,
+}
+
+impl GridIndex {
+    pub fn new(coords: &Array2<f64>) -> Self {
+        let (dim, num_nodes) = coords.dim();
+        let origin: Vec<f64> = coords.rows().into_iter().map(|row| row.fold(f64::INFINITY, |lo, &x| lo.min(x))).collect();
+        let extents: Vec<f64> = coords
+            .rows()
+            .into_iter()
+            .zip(&origin)
+            .map(|(row, lo)| row.fold(f64::NEG_INFINITY, |hi, &x| hi.max(x)) - lo)
+            .collect();
+
+        // Average spacing over the directions the nodes actually span
+        let largest: f64 = extents.iter().copied().fold(0.0, f64::max);
+        let spanned: Vec<f64> = extents.iter().copied().filter(|&extent| extent > largest * FLAT_TOLERANCE).collect();
+        let mut cell_size: f64 = if spanned.is_empty() {
+            1.0
+        } else {
+            let spacing = (spanned.iter().product::<f64>() / num_nodes as f64).powf(1.0 / spanned.len() as f64);
+            spacing.max(largest * FLAT_TOLERANCE)
+        };
+
+        // Grow the cells until the grid is not much larger than the node count
+        let max_cells: usize = num_nodes.max(1) * MAX_CELLS_PER_NODE;
+        let mut cells_per_dim: Vec<usize> = Self::cells_per_dim(&extents, cell_size);
+        let mut num_cells: usize = cells_per_dim.iter().fold(1, |total: usize, &cells| total.saturating_mul(cells));
+        while num_cells > max_cells {
+            let ratio = num_cells as f64 / max_cells as f64;
+            cell_size *= ratio.powf(1.0 / spanned.len().max(1) as f64).max(1.01);
+            cells_per_dim = Self::cells_per_dim(&extents, cell_size);
+            num_cells = cells_per_dim.iter().fold(1, |total: usize, &cells| total.saturating_mul(cells));
+        }
+
+        let mut index = Self {
+            coords: coords.clone(),
+            origin,
+            cell_size,
+            cells_per_dim,
+            cell_start: Vec::new(),
+            cell_nodes: Vec::new(),
+        };
+
+        // Counting sort of the nodes by cell
+        let num_cells: usize = if dim == 0 || num_nodes == 0 { 0 } else { index.cells_per_dim.iter().product() };
+        let node_cells: Vec<usize> = coords
+            .columns()
+            .into_iter()
+            .map(|point| index.linear_index(&index.cell_of(&point.to_vec())))
+            .collect();
+        let mut cell_start: Vec<usize> = vec![0; num_cells + 1];
+        for &cell in &node_cells {
+            cell_start[cell + 1] += 1;
+        }
+        for c in 0..num_cells {
+            cell_start[c + 1] += cell_start[c];
+        }
+        let mut next: Vec<usize> = cell_start.clone();
+        let mut cell_nodes: Vec<u32> = vec![0; num_nodes];
+        for (node_id, &cell) in node_cells.iter().enumerate() {
+            cell_nodes[next[cell]] = node_id as u32;
+            next[cell] += 1;
+        }
+
+        index.cell_start = cell_start;
+        index.cell_nodes = cell_nodes;
+        index
+    }
+
+    fn cells_per_dim(extents: &[f64], cell_size: f64) -> Vec<usize> {
+        extents.iter().map(|&extent| ((extent / cell_size) as usize).saturating_add(1)).collect()
+    }
+
+    /// Index of the nodes of a HyperNode file
+    pub fn from_hypernode(file: &HyperNodeFile) -> Result<Self, HyperNodeError> {
+        Ok(Self::new(&file.to_ndarray()?))
+    }
+
+    pub fn num_nodes(&self) -> usize {
+        self.coords.ncols()
+    }
+
+    /// Id of the node closest to `point` (the smallest id on ties).
+    ///
+    /// # Panics
+    /// Panics if the index has no nodes or `point` does not match the spatial dimension.
+    pub fn nearest(&self, point: &[f64]) -> u32 {
+        assert!(self.num_nodes() > 0, "Nearest node query on an empty index");
+        self.k_nearest(point, 1)[0]
+    }
+
+    /// Ids of the `k` nodes closest to `point`, sorted by distance (then by id).
+    /// Returns all nodes if there are fewer than `k`.
+    ///
+    /// # Panics
+    /// Panics if `point` does not match the spatial dimension.
+    pub fn k_nearest(&self, point: &[f64], k: usize) -> Vec<u32> {
+        let dim = self.coords.nrows();
+        assert_eq!(point.len(), dim, "Query point has {} coordinates, expected {}", point.len(), dim);
+        if k == 0 || self.num_nodes() == 0 {
+            return Vec::new();
+        }
+
+        let centre = self.cell_of(point);
+        let mut candidates: Vec<(f64, u32)> = Vec::with_capacity(k + 1);
+        let max_radius: usize = self.cells_per_dim.iter().copied().max().unwrap_or(0);
+
+        for radius in 0..=max_radius {
+            self.for_each_cell_in_shell(&centre, radius, |cell| {
+                for &node_id in self.nodes_in_cell(cell) {
+                    let distance_sq: f64 = self
+                        .coords
+                        .column(node_id as usize)
+                        .iter()
+                        .zip(point)
+                        .map(|(x, p)| (x - p) * (x - p))
+                        .sum();
+                    let position = candidates.partition_point(|&candidate| candidate < (distance_sq, node_id));
+                    if position < k {
+                        candidates.insert(position, (distance_sq, node_id));
+                        candidates.truncate(k);
+                    }
+                }
+            });
+
+            // Every node outside the visited cells is at least this far away
+            let bound: f64 = self.distance_outside_shell(point, &centre, radius);
+            if candidates.len() == k && candidates[k - 1].0 <= bound * bound {
+                break;
+            }
+            if bound.is_infinite() {
+                break;
+            }
+        }
+
+        candidates.into_iter().map(|(_, node_id)| node_id).collect()
+    }
+
+    /// Ids of the nodes at distance at most `radius` from `point`, sorted by id.
+    ///
+    /// # Panics
+    /// Panics if `point` does not match the spatial dimension.
+    pub fn within_radius(&self, point: &[f64], radius: f64) -> Vec<u32> {
+        let min: Vec<f64> = point.iter().map(|&x| x - radius).collect();
+        let max: Vec<f64> = point.iter().map(|&x| x + radius).collect();
+        let mut node_ids = self.within_box(&min, &max);
+        node_ids.retain(|&node_id| {
+            let distance_sq: f64 = self
+                .coords
+                .column(node_id as usize)
+                .iter()
+                .zip(point)
+                .map(|(x, p)| (x - p) * (x - p))
+                .sum();
+            distance_sq <= radius * radius
+        });
+        node_ids
+    }
+
+    /// Ids of the nodes inside the axis-aligned box [min, max] (bounds included), sorted by id.
+    /// An empty box (max < min in some direction) gives no nodes.
+    ///
+    /// # Panics
+    /// Panics if `min` or `max` does not match the spatial dimension.
+    pub fn within_box(&self, min: &[f64], max: &[f64]) -> Vec<u32> {
+        let dim = self.coords.nrows();
+        assert_eq!(min.len(), dim, "Box corner has {} coordinates, expected {}", min.len(), dim);
+        assert_eq!(max.len(), dim, "Box corner has {} coordinates, expected {}", max.len(), dim);
+        if self.num_nodes() == 0 || min.iter().zip(max).any(|(lo, hi)| lo > hi) {
+            return Vec::new();
+        }
+
+        let mut node_ids: Vec<u32> = Vec::new();
+        self.for_each_cell_in_range(&self.cell_of(min), &self.cell_of(max), |cell| {
+            node_ids.extend(self.nodes_in_cell(cell).iter().copied().filter(|&node_id| {
+                self.coords
+                    .column(node_id as usize)
+                    .iter()
+                    .zip(min.iter().zip(max))
+                    .all(|(x, (lo, hi))| lo <= x && x <= hi)
+            }));
+        });
+        node_ids.sort_unstable();
+        node_ids
+    }
+
+    /// Cell containing `point`, clamped to the grid
+    fn cell_of(&self, point: &[f64]) -> Vec<usize> {
+        point
+            .iter()
+            .zip(&self.origin)
+            .zip(&self.cells_per_dim)
+            .map(|((&x, &lo), &cells)| (((x - lo) / self.cell_size).max(0.0) as usize).min(cells - 1))
+            .collect()
+    }
+
+    fn linear_index(&self, cell: &[usize]) -> usize {
+        cell.iter()
+            .zip(&self.cells_per_dim)
+            .rev()
+            .fold(0, |index, (&c, &cells)| index * cells + c)
+    }
+
+    fn nodes_in_cell(&self, cell: &[usize]) -> &[u32] {
+        let c = self.linear_index(cell);
+        &self.cell_nodes[self.cell_start[c]..self.cell_start[c + 1]]
+    }
+
+    /// Calls `f` on every grid cell at Chebyshev distance exactly `radius` from `centre`
+    fn for_each_cell_in_shell(&self, centre: &[usize], radius: usize, mut f: impl FnMut(&[usize])) {
+        let lower: Vec<usize> = centre.iter().map(|&c| c.saturating_sub(radius)).collect();
+        let upper: Vec<usize> = centre.iter().zip(&self.cells_per_dim).map(|(&c, &cells)| (c + radius).min(cells - 1)).collect();
+
+        self.for_each_cell_in_range(&lower, &upper, |cell| {
+            if cell.iter().zip(centre).any(|(&c, &c0)| c.abs_diff(c0) == radius) {
+                f(cell);
+            }
+        });
+    }
+
+    /// Calls `f` on every grid cell between `lower` and `upper` (included) in each direction
+    fn for_each_cell_in_range(&self, lower: &[usize], upper: &[usize], mut f: impl FnMut(&[usize])) {
+        let mut cell: Vec<usize> = lower.to_vec();
+        loop {
+            f(&cell);
+
+            // Advance the multi-index
+            let mut d = 0;
+            while d < cell.len() && cell[d] == upper[d] {
+                cell[d] = lower[d];
+                d += 1;
+            }
+            if d == cell.len() {
+                return;
+            }
+            cell[d] += 1;
+        }
+    }
+
+    /// Lower bound of the distance from `point` to any node in a cell farther than `radius`
+    /// from `centre`, or infinity if the shells up to `radius` cover the whole grid
+    fn distance_outside_shell(&self, point: &[f64], centre: &[usize], radius: usize) -> f64 {
+        let mut bound = f64::INFINITY;
+        for (d, (&c, &cells)) in centre.iter().zip(&self.cells_per_dim).enumerate() {
+            if c > radius {
+                let lower = self.origin[d] + (c - radius) as f64 * self.cell_size;
+                bound = bound.min(point[d] - lower);
+            }
+            if c + radius + 1 < cells {
+                let upper = self.origin[d] + (c + radius + 1) as f64 * self.cell_size;
+                bound = bound.min(upper - point[d]);
+            }
+        }
+        bound.max(0.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Nodes of a regular nx x ny grid with unit spacing, x varying fastest
+    fn regular_grid(nx: usize, ny: usize) -> Array2<f64> {
+        Array2::from_shape_fn((2, nx * ny), |(d, i)| if d == 0 { (i % nx) as f64 } else { (i / nx) as f64 })
+    }
+
+    fn brute_force_k_nearest(coords: &Array2<f64>, point: &[f64], k: usize) -> Vec<u32> {
+        let mut distances: Vec<(f64, u32)> = coords
+            .columns()
+            .into_iter()
+            .enumerate()
+            .map(|(i, x)| (x.iter().zip(point).map(|(a, b)| (a - b) * (a - b)).sum(), i as u32))
+            .collect();
+        distances.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        distances.into_iter().take(k).map(|(_, i)| i).collect()
+    }
+
+    #[test]
+    fn test_nearest_on_regular_grid() {
+        let coords = regular_grid(10, 8);
+        let index = GridIndex::new(&coords);
+
+        // Slightly off the lower-left corner of each grid cell
+        for j in 0..7 {
+            for i in 0..9 {
+                let point = [i as f64 + 0.3, j as f64 + 0.4];
+                assert_eq!(index.nearest(&point), (j * 10 + i) as u32);
+            }
+        }
+
+        // Outside the bounding box
+        assert_eq!(index.nearest(&[-5.0, 100.0]), 70);
+        assert_eq!(index.nearest(&[20.0, -3.0]), 9);
+    }
+
+    #[test]
+    fn test_range_queries() {
+        let coords = regular_grid(10, 10);
+        let index = GridIndex::new(&coords);
+
+        // Node 55 at (5, 5): itself, then its 4 edge neighbours, then the 4 diagonal ones
+        assert_eq!(index.within_radius(&[5.0, 5.0], 0.5), vec![55]);
+        assert_eq!(index.within_radius(&[5.0, 5.0], 1.0), vec![45, 54, 55, 56, 65]);
+        assert_eq!(index.within_radius(&[5.0, 5.0], 1.5).len(), 9);
+        assert_eq!(index.within_radius(&[5.0, 5.0], 2.0).len(), 13);
+
+        // Larger than the domain, and empty regions
+        assert_eq!(index.within_radius(&[0.0, 0.0], 100.0).len(), 100);
+        assert!(index.within_radius(&[50.0, 50.0], 1.0).is_empty());
+        assert!(index.within_radius(&[5.0, 5.0], -1.0).is_empty());
+
+        assert_eq!(index.within_box(&[1.5, 2.0], &[3.0, 3.5]), vec![22, 23, 32, 33]);
+        assert_eq!(index.within_box(&[-10.0, -10.0], &[10.0, 10.0]).len(), 100);
+        assert!(index.within_box(&[3.0, 3.0], &[2.0, 4.0]).is_empty());
+        assert!(index.within_box(&[3.2, 3.2], &[3.8, 3.8]).is_empty());
+    }
+
+    #[test]
+    fn test_k_nearest_matches_brute_force() {
+        use rand::{rng, Rng};
+
+        let coords = Array2::from_shape_fn((3, 500), |_| rng().random_range(-1.0..1.0));
+        let index = GridIndex::new(&coords);
+        for _ in 0..50 {
+            let point: Vec<f64> = (0..3).map(|_| rng().random_range(-1.5..1.5)).collect();
+            for k in [1, 5, 20] {
+                assert_eq!(index.k_nearest(&point, k), brute_force_k_nearest(&coords, &point, k));
+            }
+        }
+
+        // Fewer nodes than requested
+        let small = regular_grid(2, 1);
+        assert_eq!(GridIndex::new(&small).k_nearest(&[0.9, 0.0], 5), vec![1, 0]);
+    }
+
+    #[test]
+    fn test_nearly_flat_point_cloud() {
+        use rand::{rng, Rng};
+
+        // A 20 x 20 planar grid in 3D with round-off noise in z
+        let grid = regular_grid(20, 20);
+        let coords = Array2::from_shape_fn((3, 400), |(d, i)| if d < 2 { grid[[d, i]] } else { rng().random_range(0.0..1e-12) });
+        let index = GridIndex::new(&coords);
+
+        assert_eq!(index.cells_per_dim[2], 1);
+        assert!(index.cells_per_dim.iter().product::<usize>() <= 400 * MAX_CELLS_PER_NODE);
+        for _ in 0..50 {
+            let point: Vec<f64> = (0..3).map(|d| rng().random_range(-1.0..20.0) * if d < 2 { 1.0 } else { 0.1 }).collect();
+            for k in [1, 4] {
+                assert_eq!(index.k_nearest(&point, k), brute_force_k_nearest(&coords, &point, k));
+            }
+        }
+    }
+}
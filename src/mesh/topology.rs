@@ -0,0 +1,75 @@
+//! Element-to-face incidence.
+//!
+//! The faces of an element (faces of a hexahedron, edges of a quadrilateral) follow the local
+//! numbering of `ShapeFunctionKind::faces`. Two elements share a face when one of their faces
+//! has the same set of global nodes, so faces are matched by their sorted node ids, as for the
+//! boundary extraction.
+
+use crate::elements::element_library::hypercube_elements::ShapeFunctionKind;
+use crate::mesh::boundary::sorted_key;
+use crate::mesh::locate_nodes_o_log_n::MeshNodeConverter;
+
+/// Global nodes of every face of an element, in the order of `ShapeFunctionKind::faces`,
+/// or `None` if the element does not exist.
+pub fn element_faces(converter: &MeshNodeConverter, element_id: u32, kind: ShapeFunctionKind) -> Option<Vec<Vec<u32>>> {
+    let (_, nodes) = converter.get_global_nodes_for_elements(&[element_id]).ok()?.pop()?;
+
+    Some(
+        kind.faces()
+            .iter()
+            .map(|face| face.iter().map(|&local_node| nodes[local_node]).collect())
+            .collect(),
+    )
+}
+
+/// Global nodes of the face shared by two elements, ordered as in `elem_a`.
+///
+/// Returns `None` if the elements only touch at an edge or a vertex, are not adjacent,
+/// or if either of them does not exist.
+pub fn shared_face(converter: &MeshNodeConverter, elem_a: u32, elem_b: u32, kind: ShapeFunctionKind) -> Option<Vec<u32>> {
+    let faces_a = element_faces(converter, elem_a, kind)?;
+    let keys_b: Vec<Vec<u32>> = element_faces(converter, elem_b, kind)?
+        .iter()
+        .map(|face| sorted_key(face))
+        .collect();
+
+    faces_a
+        .into_iter()
+        .find(|face| keys_b.contains(&sorted_key(face)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mesh::locate_nodes_o_log_n::converter_from_lines;
+
+    #[test]
+    fn test_shared_face_of_adjacent_hexes() {
+        // 2x1x1 block: nodes 0..5 on z=0 and 6..11 on z=1, x varying fastest
+        let converter = converter_from_lines(&["0 0 1 3 4 6 7 9 10", "1 1 2 4 5 7 8 10 11"]);
+
+        // x=1 face of element 0, x=0 face of element 1
+        assert_eq!(shared_face(&converter, 0, 1, ShapeFunctionKind::Cube1), Some(vec![1, 4, 7, 10]));
+        assert_eq!(shared_face(&converter, 1, 0, ShapeFunctionKind::Cube1), Some(vec![1, 4, 7, 10]));
+        assert_eq!(shared_face(&converter, 0, 2, ShapeFunctionKind::Cube1), None);
+
+        let faces = element_faces(&converter, 1, ShapeFunctionKind::Cube1).unwrap();
+        assert_eq!(faces.len(), 6);
+        assert_eq!(faces[1], vec![2, 5, 8, 11]);
+    }
+
+    #[test]
+    fn test_diagonal_elements_share_no_face() {
+        // Hexes in a 2x2x1 block: nodes 0..8 on z=0 and 9..17 on z=1, x varying fastest.
+        // Elements 0 and 3 only share the edge (4, 13)
+        let converter = converter_from_lines(&["0 0 1 3 4 9 10 12 13", "3 4 5 7 8 13 14 16 17"]);
+        assert_eq!(shared_face(&converter, 0, 3, ShapeFunctionKind::Cube1), None);
+
+        // Quads touching at vertex 4
+        let converter = converter_from_lines(&["0 0 1 3 4", "1 4 5 7 8"]);
+        assert_eq!(shared_face(&converter, 0, 1, ShapeFunctionKind::Square1), None);
+
+        let converter = converter_from_lines(&["0 0 1 3 4", "1 1 2 4 5"]);
+        assert_eq!(shared_face(&converter, 0, 1, ShapeFunctionKind::Square1), Some(vec![1, 4]));
+    }
+}
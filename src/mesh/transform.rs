@@ -0,0 +1,129 @@
+//! In-place transforms of node coordinate arrays.
+//!
+//! Coordinates are stored as `(DIM, n_nodes)` arrays, one column per node, as returned by
+//! `read_nodes`. Every function checks that its arguments match the spatial dimension `DIM`
+//! before touching the coordinates, so a failed call leaves them unchanged.
+
+use ndarray::{Array2, ArrayView1, Axis};
+
+/// Error types for coordinate transforms.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TransformError {
+    /// Offset or factor vector does not match the spatial dimension
+    DimensionMismatch { expected: usize, found: usize },
+    /// Affine matrix is not `DIM x DIM`
+    InvalidMatrixShape { expected: (usize, usize), found: (usize, usize) },
+}
+
+impl std::fmt::Display for TransformError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TransformError::DimensionMismatch { expected, found } => {
+                write!(f, "Expected {} components, found {}", expected, found)
+            }
+            TransformError::InvalidMatrixShape { expected, found } => {
+                write!(f, "Expected a {}x{} matrix, found {}x{}", expected.0, expected.1, found.0, found.1)
+            }
+        }
+    }
+}
+
+impl std::error::Error for TransformError {}
+
+fn check_length(coords: &Array2<f64>, values: &[f64]) -> Result<(), TransformError> {
+    if values.len() != coords.nrows() {
+        return Err(TransformError::DimensionMismatch { expected: coords.nrows(), found: values.len() });
+    }
+    Ok(())
+}
+
+/// Adds `offset` to every node
+pub fn translate(coords: &mut Array2<f64>, offset: &[f64]) -> Result<(), TransformError> {
+    check_length(coords, offset)?;
+    *coords += &ArrayView1::from(offset).insert_axis(Axis(1));
+    Ok(())
+}
+
+/// Multiplies each coordinate direction by its factor, e.g. for unit conversion
+pub fn scale(coords: &mut Array2<f64>, factors: &[f64]) -> Result<(), TransformError> {
+    check_length(coords, factors)?;
+    *coords *= &ArrayView1::from(factors).insert_axis(Axis(1));
+    Ok(())
+}
+
+/// Maps every node x to `matrix * x + offset`; rotations are the orthogonal case
+pub fn apply_affine(coords: &mut Array2<f64>, matrix: &Array2<f64>, offset: &[f64]) -> Result<(), TransformError> {
+    let dim = coords.nrows();
+    if matrix.dim() != (dim, dim) {
+        return Err(TransformError::InvalidMatrixShape { expected: (dim, dim), found: matrix.dim() });
+    }
+    check_length(coords, offset)?;
+
+    *coords = matrix.dot(coords);
+    translate(coords, offset)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::array;
+
+    fn bounding_box(coords: &Array2<f64>) -> Vec<(f64, f64)> {
+        coords
+            .rows()
+            .into_iter()
+            .map(|row| row.iter().fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), &x| (lo.min(x), hi.max(x))))
+            .collect()
+    }
+
+    #[test]
+    fn test_translate_inverse_is_identity() {
+        let original = array![[0.0, 1.0, 2.5], [-1.0, 0.5, 3.0], [4.0, 0.0, -2.0]];
+        let mut coords = original.clone();
+
+        translate(&mut coords, &[1.5, -2.0, 0.25]).unwrap();
+        assert_ne!(coords, original);
+        translate(&mut coords, &[-1.5, 2.0, -0.25]).unwrap();
+        for (a, b) in coords.iter().zip(original.iter()) {
+            assert!((a - b).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_scale_doubles_bounding_box() {
+        let mut coords = array![[0.0, 1.0, 3.0], [-1.0, 2.0, 0.5]];
+        let before = bounding_box(&coords);
+
+        scale(&mut coords, &[2.0, 2.0]).unwrap();
+        for ((lo, hi), (lo0, hi0)) in bounding_box(&coords).into_iter().zip(before) {
+            assert_eq!(hi - lo, 2.0 * (hi0 - lo0));
+        }
+    }
+
+    #[test]
+    fn test_affine_rotation() {
+        // Quarter turn about z, then shift along x
+        let mut coords = array![[1.0, 0.0], [0.0, 1.0], [0.0, 2.0]];
+        let rotation = array![[0.0, -1.0, 0.0], [1.0, 0.0, 0.0], [0.0, 0.0, 1.0]];
+        apply_affine(&mut coords, &rotation, &[1.0, 0.0, 0.0]).unwrap();
+        assert_eq!(coords, array![[1.0, 0.0], [1.0, 0.0], [0.0, 2.0]]);
+    }
+
+    #[test]
+    fn test_dimension_mismatch() {
+        let mut coords = array![[0.0, 1.0], [0.0, 1.0]];
+        let original = coords.clone();
+
+        assert_eq!(translate(&mut coords, &[1.0, 2.0, 3.0]), Err(TransformError::DimensionMismatch { expected: 2, found: 3 }));
+        assert_eq!(scale(&mut coords, &[2.0]), Err(TransformError::DimensionMismatch { expected: 2, found: 1 }));
+        assert_eq!(
+            apply_affine(&mut coords, &Array2::eye(3), &[0.0, 0.0]),
+            Err(TransformError::InvalidMatrixShape { expected: (2, 2), found: (3, 3) })
+        );
+        assert_eq!(
+            apply_affine(&mut coords, &Array2::eye(2), &[0.0]),
+            Err(TransformError::DimensionMismatch { expected: 2, found: 1 })
+        );
+        assert_eq!(coords, original);
+    }
+}